@@ -41,8 +41,12 @@ pub fn arm_wait() {
 
 /// Stabs the bot in front of you, killing it.
 ///
-/// If there's no bot there, nothing happens (but the cooldown is still
-/// applied).
+/// In worlds with hit points, the stab deals damage instead and only kills the
+/// bot once it runs out of hp - see [`health_hp()`].
+///
+/// If there's no bot there, but there's a door (`'+'`), the door gets opened or
+/// closed; stabbing a cracked wall (`'#'`) three times breaks it. Otherwise
+/// nothing happens (but the cooldown is still applied).
 ///
 /// # Cooldown
 ///
@@ -0,0 +1,24 @@
+use crate::{rdi, MEM_HEALTH};
+
+/// Returns how many hit points the bot has left.
+///
+/// Bots start with 100 hp and each stab takes away 40 of them - when the hit
+/// points drop to zero, the bot dies.
+///
+/// Note that some worlds use instant-kill combat instead, in which case the
+/// bot always has 100 hp and a single stab is enough to kill it.
+///
+/// # Example
+///
+/// ```no_run
+/// # use kartoffel::*;
+/// #
+/// if health_hp() < 50 {
+///     motor_wait();
+///     motor_step_back();
+/// }
+/// ```
+#[inline(always)]
+pub fn health_hp() -> u32 {
+    rdi(MEM_HEALTH, 0)
+}
@@ -0,0 +1,77 @@
+use crate::{rdi, MEM_ARM};
+
+/// Returns how many objects there are in the inventory.
+///
+/// # Example
+///
+/// ```no_run
+/// # use kartoffel::*;
+/// #
+/// if inventory_len() > 0 {
+///     arm_wait();
+///     arm_drop(0);
+/// }
+/// ```
+#[inline(always)]
+pub fn inventory_len() -> u8 {
+    rdi(MEM_ARM, 1) as u8
+}
+
+/// Returns kind of the object under given index in the inventory or `None` if
+/// there's no such object.
+///
+/// Kinds are the same as reported by the radar, e.g. `'='` for a flag or `'*'`
+/// for a gem.
+///
+/// See also: [`inventory_meta()`].
+///
+/// # Example
+///
+/// ```no_run
+/// # use kartoffel::*;
+/// #
+/// for idx in 0..inventory_len() {
+///     if inventory_kind(idx) == Some('*') {
+///         arm_wait();
+///         arm_drop(idx);
+///         break;
+///     }
+/// }
+/// ```
+#[inline(always)]
+pub fn inventory_kind(idx: u8) -> Option<char> {
+    match inventory_slot(idx)[0] {
+        0 => None,
+        kind => Some(kind as char),
+    }
+}
+
+/// Returns metadata of the object under given index in the inventory or `None`
+/// if there's no such object.
+///
+/// Meaning of the metadata depends on the object's kind - for most objects
+/// it's all zeros.
+///
+/// See also: [`inventory_kind()`].
+///
+/// # Example
+///
+/// ```no_run
+/// # use kartoffel::*;
+/// #
+/// if inventory_meta(0) == Some([0, 0, 0]) {
+///     serial_write("boring object");
+/// }
+/// ```
+#[inline(always)]
+pub fn inventory_meta(idx: u8) -> Option<[u8; 3]> {
+    match inventory_slot(idx) {
+        [0, ..] => None,
+        [_, meta @ ..] => Some(meta),
+    }
+}
+
+#[inline(always)]
+fn inventory_slot(idx: u8) -> [u8; 4] {
+    rdi(MEM_ARM, 2 + idx as usize).to_be_bytes()
+}
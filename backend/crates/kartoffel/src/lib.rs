@@ -9,6 +9,8 @@ mod allocator;
 mod arm;
 mod battery;
 mod compass;
+mod health;
+mod inventory;
 mod motor;
 mod panic;
 mod radar;
@@ -18,6 +20,8 @@ mod timer;
 pub use self::arm::*;
 pub use self::battery::*;
 pub use self::compass::*;
+pub use self::health::*;
+pub use self::inventory::*;
 pub use self::motor::*;
 pub use self::radar::*;
 pub use self::serial::*;
@@ -32,6 +36,7 @@ const MEM_MOTOR: *mut u32 = MEM.wrapping_byte_add(3 * 1024);
 const MEM_ARM: *mut u32 = MEM.wrapping_byte_add(4 * 1024);
 const MEM_RADAR: *mut u32 = MEM.wrapping_byte_add(5 * 1024);
 const MEM_COMPASS: *mut u32 = MEM.wrapping_byte_add(6 * 1024);
+const MEM_HEALTH: *mut u32 = MEM.wrapping_byte_add(7 * 1024);
 
 #[inline(always)]
 fn rdi(ptr: *mut u32, off: usize) -> u32 {
@@ -1,7 +1,7 @@
 use crate::{rdi, wri, MEM_MOTOR};
 
-/// Returns whether the motor is ready and [`motor_step()`] or [`motor_turn()`]
-/// can be invoked.
+/// Returns whether the motor is ready and [`motor_step()`], [`motor_turn()`]
+/// etc. can be invoked.
 ///
 /// See also: [`motor_wait()`].
 ///
@@ -55,6 +55,8 @@ pub fn motor_wait() {
 /// 20_000 +- 15% ticks (~310 ms)
 /// ```
 ///
+/// (twice as much if you're standing in water, `'%'`)
+///
 /// # Example
 ///
 /// ```no_run
@@ -68,6 +70,93 @@ pub fn motor_step() {
     wri(MEM_MOTOR, 0, 1);
 }
 
+/// Moves the bot one tile backward, opposite to the direction it's facing.
+///
+/// The bot keeps facing the same direction.
+///
+/// # Cooldown
+///
+/// ```text
+/// 25_000 +- 15% ticks (~390 ms)
+/// ```
+///
+/// (twice as much if you're standing in water, `'%'`)
+///
+/// # Example
+///
+/// ```no_run
+/// # use kartoffel::*;
+/// #
+/// motor_wait();
+/// motor_step_back();
+/// ```
+#[inline(always)]
+pub fn motor_step_back() {
+    wri(MEM_MOTOR, 0, 2);
+}
+
+/// Moves the bot two tiles forward in the direction it's facing.
+///
+/// If the second tile turns out to be blocked, the bot stops after the first
+/// one (see: [`motor_status()`]).
+///
+/// # Cooldown
+///
+/// ```text
+/// 35_000 +- 15% ticks (~550 ms)
+/// ```
+///
+/// (twice as much if you're standing in water, `'%'`)
+///
+/// # Example
+///
+/// ```no_run
+/// # use kartoffel::*;
+/// #
+/// motor_wait();
+/// motor_sprint();
+/// ```
+#[inline(always)]
+pub fn motor_sprint() {
+    wri(MEM_MOTOR, 0, 3);
+}
+
+/// Returns the outcome of the latest move:
+///
+/// - 0 = no move has been made yet,
+/// - 1 = bot moved successfully,
+/// - 2 = bot bumped into a wall,
+/// - 3 = bot bumped into another bot,
+/// - 4 = bot bumped into an object that couldn't be pushed,
+/// - 5 = bot fell into the void (you'll never see this one, for obvious
+///   reasons),
+/// - 6 = bot sprinted, but stopped after the first tile, because the second
+///   one was blocked.
+///
+/// Moving into an object pushes it one tile further, provided that the tile
+/// behind the object is empty floor.
+///
+/// If [`motor_sprint()`] gets blocked already on the first tile, this reports
+/// why (i.e. 2, 3 or 4).
+///
+/// # Example
+///
+/// ```no_run
+/// # use kartoffel::*;
+/// #
+/// motor_wait();
+/// motor_step();
+///
+/// if motor_status() == 2 {
+///     motor_wait();
+///     motor_turn_left();
+/// }
+/// ```
+#[inline(always)]
+pub fn motor_status() -> u32 {
+    rdi(MEM_MOTOR, 2)
+}
+
 /// Turns the bot left (counterclockwise).
 ///
 /// # Cooldown
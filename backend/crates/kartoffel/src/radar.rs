@@ -135,8 +135,8 @@ fn radar_scan<const R: usize>() -> RadarScan<R> {
 ///
 /// # Coordinate system
 ///
-/// [`Self::at()`] and [`Self::bot_at()`] work in bot-centric coordinate
-/// system, that is:
+/// [`Self::at()`], [`Self::bot_at()`] and [`Self::team_at()`] work in
+/// bot-centric coordinate system, that is:
 ///
 /// - `.at(0, 0)` returns the bot itself (`'@'`),
 /// - `.at(-1, 0)` returns tile to the left of the bot,
@@ -148,7 +148,7 @@ fn radar_scan<const R: usize>() -> RadarScan<R> {
 /// This also means that the 3x3 scan allows you to access `at(-1..=1)`, 5x5
 /// yields `at(-2..=2)` etc.
 ///
-/// The same applies to [`Self::bot_at()`].
+/// The same applies to [`Self::bot_at()`] and [`Self::team_at()`].
 ///
 /// # Lazyness
 ///
@@ -214,6 +214,75 @@ impl<const R: usize> RadarScan<R> {
         NonZeroU64::new((d1 << 32) | d2)
     }
 
+    /// Returns team of the bot at given coordinates or `None` if there's no bot
+    /// there or the bot doesn't belong to any team.
+    ///
+    /// Use `team_at(0, 0)` to find out your own team.
+    ///
+    /// # Coordinate system
+    ///
+    /// This function uses bot-centric coordinates, i.e. `team_at(0, -1)`
+    /// points at the bot right in front of you - see [`RadarScan`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use kartoffel::*;
+    /// #
+    /// radar_wait();
+    ///
+    /// let scan = radar_scan_3x3();
+    ///
+    /// if scan.at(0, -1) == '@'
+    ///     && scan.team_at(0, -1) != scan.team_at(0, 0)
+    ///     && is_arm_ready()
+    /// {
+    ///     arm_stab();
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn team_at(&self, dx: i8, dy: i8) -> Option<u8> {
+        match (self.get_ex(dx, dy, 0) >> 8) as u8 {
+            0 => None,
+            team => Some(team - 1),
+        }
+    }
+
+    /// Returns state of the tile at given coordinates:
+    ///
+    /// - for doors (`'+'`), it's non-zero if the door is open,
+    /// - for cracked walls (`'#'`), it's the number of stabs the wall has
+    ///   already taken (it breaks after three),
+    /// - for teleporters (`'&'`), it's the channel - stepping on a teleporter
+    ///   moves you to the other teleporter on the same channel,
+    /// - for conveyors (`'>'`), it's the direction the conveyor pushes bots in
+    ///   (0 = north, 1 = east, 2 = south, 3 = west; in world coordinates),
+    /// - for everything else, it's zero.
+    ///
+    /// # Coordinate system
+    ///
+    /// This function uses bot-centric coordinates, i.e. `meta_at(0, -1)`
+    /// points at the tile right in front of you - see [`RadarScan`] for
+    /// details.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use kartoffel::*;
+    /// #
+    /// radar_wait();
+    ///
+    /// let scan = radar_scan_3x3();
+    ///
+    /// if scan.at(0, -1) == '+' && scan.meta_at(0, -1) == 0 && is_arm_ready() {
+    ///     arm_stab(); // open the door
+    /// }
+    /// ```
+    #[inline(always)]
+    pub fn meta_at(&self, dx: i8, dy: i8) -> u8 {
+        (self.get_ex(dx, dy, 0) >> 16) as u8
+    }
+
     fn get_d1(&self, dx: i8, dy: i8) -> u32 {
         self.get_ex(dx, dy, 1)
     }
@@ -56,6 +56,36 @@ impl Cpu {
         }
     }
 
+    /// Returns whether the next instruction is going to access MMIO.
+    ///
+    /// Instructions that don't access MMIO depend only on CPU's own state, so
+    /// they can be executed out of order with respect to other CPUs.
+    pub fn is_mmio_next(&self) -> bool {
+        let Ok(word) = self.mem_load_ram::<4>(self.pc as u32) else {
+            return false;
+        };
+
+        let word = word as u32;
+        let rs1 = ((word >> 15) & 0x1f) as usize;
+
+        let imm = match word & 0x7f {
+            // load
+            0b0000011 => (word as i32 as i64) >> 20,
+
+            // store
+            0b0100011 => {
+                ((word & 0xfe000000) as i32 as i64 >> 20)
+                    | (((word >> 7) & 0x1f) as i64)
+            }
+
+            _ => {
+                return false;
+            }
+        };
+
+        self.regs[rs1].wrapping_add(imm) as u32 >= Self::MMIO_BASE
+    }
+
     pub fn pc(&self) -> u64 {
         self.pc
     }
@@ -74,3 +104,86 @@ impl fmt::Debug for Cpu {
         f.debug_struct("Cpu").field("pc", &self.pc).finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use test_case::test_case;
+
+    const RAM: i64 = Cpu::RAM_BASE as i64;
+    const MMIO: i64 = Cpu::MMIO_BASE as i64;
+
+    fn lw(rd: u32, rs1: u32, imm: i32) -> u32 {
+        ((imm as u32 & 0xfff) << 20)
+            | (rs1 << 15)
+            | (0b010 << 12)
+            | (rd << 7)
+            | 0b0000011
+    }
+
+    fn sw(rs2: u32, rs1: u32, imm: i32) -> u32 {
+        let imm = imm as u32;
+
+        (((imm >> 5) & 0x7f) << 25)
+            | (rs2 << 20)
+            | (rs1 << 15)
+            | (0b010 << 12)
+            | ((imm & 0x1f) << 7)
+            | 0b0100011
+    }
+
+    fn addi(rd: u32, rs1: u32, imm: i32) -> u32 {
+        ((imm as u32 & 0xfff) << 20) | (rs1 << 15) | (rd << 7) | 0b0010011
+    }
+
+    struct SpyMmio<'a>(&'a Cell<bool>);
+
+    impl Mmio for SpyMmio<'_> {
+        fn load(self, _: u32) -> Result<u32, ()> {
+            self.0.set(true);
+            Ok(0)
+        }
+
+        fn store(self, _: u32, _: u32) -> Result<(), ()> {
+            self.0.set(true);
+            Ok(())
+        }
+    }
+
+    #[test_case(lw(1, 2, 0), RAM, false ; "lw from ram")]
+    #[test_case(lw(1, 2, 4), MMIO, true ; "lw from mmio")]
+    #[test_case(lw(1, 2, -4), MMIO, false ; "lw from below mmio")]
+    #[test_case(lw(1, 2, -2048), MMIO + 2048, true ; "lw with min imm")]
+    #[test_case(lw(1, 0, 16), MMIO, false ; "lw from x0")]
+    #[test_case(lw(1, 0, -4), MMIO, true ; "lw from x0 with negative imm")]
+    #[test_case(sw(1, 2, 0), RAM, false ; "sw into ram")]
+    #[test_case(sw(1, 2, 8), MMIO, true ; "sw into mmio")]
+    #[test_case(sw(1, 2, 2047), MMIO - 2047, true ; "sw with max imm")]
+    #[test_case(sw(1, 2, -2048), MMIO + 2048, true ; "sw with min imm")]
+    #[test_case(sw(1, 2, -2048), MMIO + 2044, false ; "sw below mmio")]
+    #[test_case(sw(1, 2, -36), MMIO + 40, true ; "sw with split imm")]
+    #[test_case(sw(1, 0, -4), MMIO, true ; "sw into x0 with negative imm")]
+    #[test_case(addi(1, 2, 4), MMIO, false ; "non-memory instr")]
+    fn is_mmio_next(instr: u32, rs1: i64, expected: bool) {
+        let mut cpu = Cpu {
+            pc: Cpu::RAM_BASE as u64,
+            ram: vec![0; Cpu::RAM_SIZE as usize].into_boxed_slice(),
+            regs: Box::new([0; 32]),
+        };
+
+        cpu.ram[0..4].copy_from_slice(&instr.to_le_bytes());
+        cpu.regs[2] = rs1;
+
+        assert_eq!(expected, cpu.is_mmio_next());
+
+        // Cross-check with the actual execution - the instruction might fail
+        // (e.g. due to an out-of-bounds access), but it mustn't touch MMIO
+        // unless we've said so
+        let touched = Cell::new(false);
+
+        _ = cpu.tick(SpyMmio(&touched));
+
+        assert_eq!(expected, touched.get());
+    }
+}
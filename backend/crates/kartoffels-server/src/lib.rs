@@ -8,7 +8,7 @@ use anyhow::{Context, Result};
 use clap::Parser;
 use indoc::indoc;
 use kartoffels_store::Store;
-use kartoffels_world::prelude::Clock;
+use kartoffels_world::prelude::{Clock, EventFilter, EventKind};
 use std::env;
 use std::net::SocketAddr;
 use std::path::PathBuf;
@@ -44,6 +44,22 @@ pub struct Cmd {
     #[clap(long)]
     bench: bool,
 
+    /// Record public worlds, storing the recordings next to them (see:
+    /// `kartoffels toolbox replay`); long recordings get split into many
+    /// files, of which only the newest few are kept
+    #[clap(long)]
+    record: bool,
+
+    /// Log events of public worlds into files next to them (see: `kartoffels
+    /// toolbox events`)
+    #[clap(long)]
+    log_events: bool,
+
+    /// Kind of events to log (e.g. `bot-moved`), can be repeated; defaults
+    /// to bots' lifecycle (uploads, spawns and kills)
+    #[clap(long)]
+    log_event_kind: Vec<EventKind>,
+
     #[clap(long)]
     log_time: bool,
 }
@@ -102,6 +118,24 @@ impl Cmd {
             }
         }
 
+        if self.record {
+            for world in store.public_worlds() {
+                world.start_recording().await?;
+            }
+        }
+
+        if self.log_events {
+            let filter = if self.log_event_kind.is_empty() {
+                EventFilter::lifecycle()
+            } else {
+                EventFilter::only(self.log_event_kind.iter().copied())
+            };
+
+            for world in store.public_worlds() {
+                world.start_event_log(filter.clone()).await?;
+            }
+        }
+
         let store = Arc::new(store);
         let shutdown = CancellationToken::new();
 
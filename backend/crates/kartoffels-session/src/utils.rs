@@ -1,6 +1,8 @@
 mod background;
 mod bot_id_ext;
 pub mod map;
+mod snapshot_alive_bot_ext;
 
 pub use self::background::*;
 pub use self::bot_id_ext::*;
+pub use self::snapshot_alive_bot_ext::*;
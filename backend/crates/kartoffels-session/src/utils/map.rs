@@ -1,9 +1,7 @@
 mod build;
 mod draw_borders;
 mod draw_holes;
-mod draw_maze;
 
 pub use self::build::*;
 pub use self::draw_borders::*;
 pub use self::draw_holes::*;
-pub use self::draw_maze::*;
@@ -0,0 +1,23 @@
+use crate::BotIdExt;
+use kartoffels_world::prelude::SnapshotAliveBot;
+use ratatui::style::Color;
+
+pub trait SnapshotAliveBotExt {
+    fn color(&self) -> Color;
+}
+
+impl SnapshotAliveBotExt for SnapshotAliveBot {
+    fn color(&self) -> Color {
+        match self.team {
+            Some(team) => team_color(team),
+            None => self.id.color(),
+        }
+    }
+}
+
+pub fn team_color(team: u8) -> Color {
+    // Golden angle, so that consecutive teams get far-apart hues
+    let hue = (team as f64 * 137.5) % 360.0;
+
+    Color::from_hsl(hue, 100.0, 50.0)
+}
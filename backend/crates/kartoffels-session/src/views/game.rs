@@ -24,7 +24,8 @@ use glam::{IVec2, UVec2};
 use kartoffels_store::{SessionId, Store};
 use kartoffels_ui::{Clear, Fade, FadeDir, Render, Term, Ui};
 use kartoffels_world::prelude::{
-    BotId, Handle as WorldHandle, Snapshot as WorldSnapshot, SnapshotStream,
+    BotId, Handle as WorldHandle, Map as WorldMap, Snapshot as WorldSnapshot,
+    SnapshotStream,
 };
 use ratatui::layout::{Constraint, Layout};
 use std::future::Future;
@@ -114,6 +115,15 @@ struct State {
     snapshot: Arc<WorldSnapshot>,
     snapshots: Option<SnapshotStream>,
     status: Option<(String, Instant)>,
+
+    /// Rendered map of `snapshot`, patched with deltas as snapshots arrive
+    /// (see: [`Self::update_snapshot()`])
+    tiles: WorldMap,
+
+    /// Version of the snapshot `tiles` correspond to - `None` if they have to
+    /// be rebuilt from scratch (e.g. after switching worlds, since versions
+    /// of snapshots coming from different worlds don't line up)
+    tiles_version: Option<u64>,
 }
 
 impl State {
@@ -225,6 +235,20 @@ impl State {
             self.camera.set(snapshot.raw_map().center());
         }
 
+        // Instead of cloning the entire map for each snapshot, let's patch our
+        // copy with tiles that have changed - unless we've missed a snapshot,
+        // in which case there's nothing to patch
+        match snapshot.delta() {
+            Some(delta) if Some(delta.base) == self.tiles_version => {
+                delta.apply_to_map(&mut self.tiles);
+            }
+
+            _ => {
+                self.tiles = snapshot.map().clone();
+            }
+        }
+
+        self.tiles_version = Some(snapshot.version());
         self.snapshot = snapshot;
 
         if let Some(bot) = &mut self.bot {
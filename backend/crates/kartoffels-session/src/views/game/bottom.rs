@@ -12,10 +12,11 @@ pub struct BottomPanel;
 impl BottomPanel {
     pub fn render(ui: &mut Ui<Event>, state: &State) {
         ui.row(|ui| {
-            Self::render_go_back_btn(ui);
+            Self::go_back_btn().render(ui);
 
             if state.restart.is_some() {
-                Self::render_restart_btn(ui);
+                ui.space(2);
+                Self::restart_btn().render(ui);
                 return;
             }
 
@@ -23,10 +24,10 @@ impl BottomPanel {
                 Mode::Default => {
                     if state.handle.is_some() {
                         ui.enable(state.config.enabled, |ui| {
-                            Self::render_help_btn(ui, state);
-                            Self::render_pause_btn(ui, state);
-                            Self::render_bots_btn(ui, state);
-                            Self::render_overclock_btn(ui, state);
+                            for btn in Self::btns(state) {
+                                ui.space(2);
+                                btn.render(ui);
+                            }
                         });
                     }
                 }
@@ -42,76 +43,99 @@ impl BottomPanel {
         }
     }
 
-    fn render_go_back_btn(ui: &mut Ui<Event>) {
-        Button::new(KeyCode::Escape, "go-back")
-            .throwing(Event::GoBack {
-                needs_confirmation: true,
-            })
-            .render(ui);
+    fn btns(state: &State) -> Vec<Button<'static, Event>> {
+        let mut btns = Vec::new();
+
+        btns.push(Self::help_btn().enabled(state.help.is_some()));
+        btns.push(
+            Self::pause_btn(state.paused).enabled(state.config.can_pause),
+        );
+
+        if state.config.sync_pause && state.config.can_pause {
+            btns.push(Self::rewind_btn());
+        }
+
+        if !state.config.hero_mode {
+            btns.push(Self::bots_btn());
+        }
+
+        if state.snapshot.rounds().is_some() {
+            btns.push(Self::rounds_btn());
+        }
+
+        if state.snapshot.leaderboard().is_some() {
+            btns.push(Self::leaderboard_btn());
+        }
+
+        if state.config.can_overclock {
+            btns.push(Self::overclock_btn());
+        }
+
+        if state.config.can_fork {
+            btns.push(Self::fork_btn());
+        }
+
+        btns
     }
 
-    fn render_restart_btn(ui: &mut Ui<Event>) {
-        ui.space(2);
+    pub(super) fn go_back_btn() -> Button<'static, Event> {
+        Button::new(KeyCode::Escape, "go-back").throwing(Event::GoBack {
+            needs_confirmation: true,
+        })
+    }
 
-        Button::new(KeyCode::Char('r'), "restart")
-            .throwing(Event::Restart)
-            .render(ui);
+    pub(super) fn restart_btn() -> Button<'static, Event> {
+        Button::new(KeyCode::Char('r'), "restart").throwing(Event::Restart)
     }
 
-    fn render_pause_btn(ui: &mut Ui<Event>, state: &State) {
-        ui.space(2);
+    pub(super) fn pause_btn(paused: bool) -> Button<'static, Event> {
+        let label = if paused { "resume" } else { "pause" };
 
-        let label = if state.paused { "resume" } else { "pause" };
-        let enabled = state.config.can_pause;
+        Button::new(KeyCode::Char(' '), label).throwing(Event::TogglePause)
+    }
 
-        Button::new(KeyCode::Char(' '), label)
-            .throwing(Event::TogglePause)
-            .enabled(enabled)
-            .render(ui);
+    pub(super) fn rewind_btn() -> Button<'static, Event> {
+        Button::new(KeyCode::Char('z'), "rewind").throwing(Event::Rewind)
     }
 
-    fn render_help_btn(ui: &mut Ui<Event>, state: &State) {
-        ui.space(2);
+    pub(super) fn help_btn() -> Button<'static, Event> {
+        Button::new(KeyCode::Char('h'), "help").throwing(Event::OpenHelpModal)
+    }
 
-        Button::new(KeyCode::Char('h'), "help")
-            .throwing(Event::OpenHelpModal)
-            .enabled(state.help.is_some())
-            .render(ui);
+    pub(super) fn bots_btn() -> Button<'static, Event> {
+        Button::new(KeyCode::Char('b'), "bots").throwing(Event::OpenBotsModal)
     }
 
-    fn render_bots_btn(ui: &mut Ui<Event>, state: &State) {
-        if !state.config.hero_mode {
-            ui.space(2);
+    pub(super) fn rounds_btn() -> Button<'static, Event> {
+        Button::new(KeyCode::Char('r'), "rounds")
+            .throwing(Event::OpenRoundsModal)
+    }
 
-            Button::new(KeyCode::Char('b'), "bots")
-                .throwing(Event::OpenBotsModal)
-                .render(ui);
-        }
+    pub(super) fn leaderboard_btn() -> Button<'static, Event> {
+        // Lowercase `l` is taken by the side panel's `leave-bot`
+        Button::new(KeyCode::Char('L'), "leaderboard")
+            .throwing(Event::OpenLeaderboardModal)
     }
 
-    fn render_overclock_btn(ui: &mut Ui<Event>, state: &State) {
-        if state.config.can_overclock {
-            ui.space(2);
-
-            Button::multi("overclock")
-                .option(
-                    KeyCode::Char('1'),
-                    Event::Overclock {
-                        clock: Clock::Normal,
-                    },
-                )
-                .option(
-                    KeyCode::Char('2'),
-                    Event::Overclock { clock: Clock::Fast },
-                )
-                .option(
-                    KeyCode::Char('3'),
-                    Event::Overclock {
-                        clock: Clock::Faster,
-                    },
-                )
-                .render(ui);
-        }
+    pub(super) fn overclock_btn() -> Button<'static, Event> {
+        Button::multi("overclock")
+            .option(
+                KeyCode::Char('1'),
+                Event::Overclock {
+                    clock: Clock::Normal,
+                },
+            )
+            .option(KeyCode::Char('2'), Event::Overclock { clock: Clock::Fast })
+            .option(
+                KeyCode::Char('3'),
+                Event::Overclock {
+                    clock: Clock::Faster,
+                },
+            )
+    }
+
+    pub(super) fn fork_btn() -> Button<'static, Event> {
+        Button::new(KeyCode::Char('k'), "fork").throwing(Event::ForkWorld)
     }
 
     fn render_status(ui: &mut Ui<Event>, state: &State) {
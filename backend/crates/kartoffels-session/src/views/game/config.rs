@@ -16,6 +16,11 @@ pub struct Config {
     pub sync_pause: bool,
 
     pub can_delete_bots: bool,
+
+    /// Whether user can fork the world into a private sandbox - makes sense
+    /// only for online play, since other worlds are private already.
+    pub can_fork: bool,
+
     pub can_join_bots: bool,
     pub can_overclock: bool,
     pub can_pause: bool,
@@ -39,6 +44,7 @@ impl Default for Config {
             sync_pause: false,
 
             can_delete_bots: false,
+            can_fork: true,
             can_join_bots: true,
             can_overclock: false,
             can_pause: true,
@@ -178,7 +178,8 @@ impl GameCtrlEvent {
             GameCtrlEvent::Join(handle) => {
                 let mut snapshots = handle.snapshots();
 
-                state.snapshot = snapshots.next().await?;
+                state.tiles_version = None;
+                state.update_snapshot(snapshots.next().await?);
                 state.snapshots = Some(snapshots);
                 state.camera.set(state.snapshot.raw_map().center());
                 state.handle = Some(handle);
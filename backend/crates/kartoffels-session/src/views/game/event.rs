@@ -1,7 +1,7 @@
 use super::{
     BotPosition, BotSource, BotSourceType, BotsModal, ErrorModal, GoBackModal,
-    InspectBotModal, JoinBotModal, Modal, Mode, SpawnBotModal, State,
-    UploadBotModal, UploadBotRequest,
+    InspectBotModal, JoinBotModal, LeaderboardModal, Modal, Mode, RoundsModal,
+    SpawnBotModal, State, UploadBotModal, UploadBotRequest,
 };
 use anyhow::{anyhow, Result};
 use base64::prelude::BASE64_STANDARD;
@@ -26,6 +26,7 @@ pub enum Event {
         delta: IVec2,
     },
     TogglePause,
+    Rewind,
     CloseModal,
     OpenBotsModal,
     OpenErrorModal {
@@ -33,6 +34,8 @@ pub enum Event {
     },
     OpenHelpModal,
     OpenJoinBotModal,
+    OpenLeaderboardModal,
+    OpenRoundsModal,
     OpenUploadBotModal {
         request: UploadBotRequest<BotSourceType>,
     },
@@ -53,6 +56,7 @@ pub enum Event {
     Overclock {
         clock: Clock,
     },
+    ForkWorld,
 }
 
 impl Event {
@@ -109,6 +113,10 @@ impl Event {
                 }
             }
 
+            Event::Rewind => {
+                state.rewind().await?;
+            }
+
             Event::CloseModal => {
                 state.modal = None;
             }
@@ -117,6 +125,15 @@ impl Event {
                 state.modal = Some(Modal::Bots(BotsModal::default()));
             }
 
+            Event::OpenLeaderboardModal => {
+                state.modal =
+                    Some(Modal::Leaderboard(LeaderboardModal::default()));
+            }
+
+            Event::OpenRoundsModal => {
+                state.modal = Some(Modal::Rounds(RoundsModal::default()));
+            }
+
             Event::OpenHelpModal => {
                 state.modal = Some(Modal::Help(state.help.unwrap()));
             }
@@ -202,6 +219,10 @@ impl Event {
             Event::Overclock { clock } => {
                 state.handle.as_ref().unwrap().overclock(clock).await?;
             }
+
+            Event::ForkWorld => {
+                state.fork_world(store).await?;
+            }
         }
 
         Ok(ControlFlow::Continue(()))
@@ -209,6 +230,79 @@ impl Event {
 }
 
 impl State {
+    /// How far back to go when rewinding, in ticks (about five seconds on the
+    /// normal clock).
+    const REWIND_BY: u64 = 1250;
+
+    async fn rewind(&mut self) -> Result<()> {
+        let Some(handle) = &self.handle else {
+            return Ok(());
+        };
+
+        if let Err(err) = handle.rewind(Self::REWIND_BY).await {
+            self.modal =
+                Some(Modal::Error(ErrorModal::new(format!("{err:?}"))));
+
+            return Ok(());
+        }
+
+        // The world might've been paused (or not) back then, so we have to
+        // synchronize it with the interface again
+        if self.paused {
+            handle.pause().await?;
+        } else {
+            handle.resume().await?;
+        }
+
+        // If we're paused, we're not listening to snapshots, so refresh the
+        // current one manually
+        let snapshot = handle.snapshots().next().await?;
+
+        self.update_snapshot(snapshot);
+
+        Ok(())
+    }
+
+    async fn fork_world(&mut self, store: &Store) -> Result<()> {
+        let world = store.fork_world(self.handle.as_ref().unwrap()).await;
+
+        let world = match world {
+            Ok(world) => world,
+
+            Err(err) => {
+                self.modal =
+                    Some(Modal::Error(ErrorModal::new(format!("{err:?}"))));
+
+                return Ok(());
+            }
+        };
+
+        // The fork is a private world, so let's make it feel like sandbox;
+        // note that the bot we've joined (if any) remains joined, since bot ids
+        // are carried over
+        self.config.sync_pause = true;
+        self.config.can_delete_bots = true;
+        self.config.can_fork = false;
+        self.config.can_pause = true;
+        self.config.can_restart_bots = true;
+        self.config.can_spawn_bots = true;
+
+        let mut snapshots = world.snapshots();
+
+        self.tiles_version = None;
+        self.update_snapshot(snapshots.next().await?);
+
+        if self.paused {
+            world.pause().await?;
+        } else {
+            self.snapshots = Some(snapshots);
+        }
+
+        self.handle = Some(world);
+
+        Ok(())
+    }
+
     async fn upload_bot(
         &mut self,
         request: UploadBotRequest<BotSource>,
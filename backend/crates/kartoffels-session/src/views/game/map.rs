@@ -1,9 +1,10 @@
 use super::{Event, Mode, State};
-use crate::BotIdExt;
-use glam::ivec2;
+use crate::SnapshotAliveBotExt;
+use glam::{ivec2, IVec2};
 use kartoffels_ui::{theme, Ui};
 use kartoffels_world::prelude::{Dir, ObjectKind, Tile, TileKind};
 use ratatui::layout::Rect;
+use ratatui::style::Color;
 use std::time::Instant;
 use termwiz::input::{KeyCode, Modifiers};
 
@@ -33,17 +34,22 @@ impl Map {
                 };
 
                 ui.clamp(area, |ui| {
-                    let tile = state
-                        .snapshot
-                        .map()
-                        .get(offset + ivec2(dx as i32, dy as i32));
+                    let pos = offset + ivec2(dx as i32, dy as i32);
+                    let tile = state.tiles.get(pos);
 
-                    self.render_tile(ui, state, tile);
+                    self.render_tile(ui, state, pos, tile);
                 });
             }
         }
     }
 
+    fn bot_color(state: &State, tile: Tile) -> Color {
+        tile.bot_idx()
+            .and_then(|idx| state.snapshot.bots().alive().get_by_idx(idx))
+            .map(|bot| bot.color())
+            .unwrap()
+    }
+
     fn render_cursor(&self, ui: &mut Ui<Event>, state: &State) {
         if let Mode::SpawningBot {
             cursor_screen: Some(cursor_screen),
@@ -76,7 +82,13 @@ impl Map {
         }
     }
 
-    fn render_tile(&self, ui: &mut Ui<Event>, state: &State, tile: Tile) {
+    fn render_tile(
+        &self,
+        ui: &mut Ui<Event>,
+        state: &State,
+        pos: IVec2,
+        tile: Tile,
+    ) {
         let ch;
         let mut fg;
         let mut bg;
@@ -84,46 +96,87 @@ impl Map {
         match tile.kind {
             TileKind::BOT => {
                 ch = '@';
-
-                fg = state
-                    .snapshot
-                    .bots()
-                    .alive()
-                    .get_by_idx(tile.meta[0])
-                    .map(|bot| bot.id.color())
-                    .unwrap();
+                fg = Self::bot_color(state, tile);
 
                 bg = theme::BG;
             }
 
             TileKind::BOT_CHEVRON => {
-                ch = match Dir::from(tile.meta[1]) {
+                let dir = Dir::from(tile.meta[1]);
+
+                ch = match dir {
                     Dir::N => '↑',
                     Dir::E => '→',
                     Dir::S => '↓',
                     Dir::W => '←',
                 };
 
-                fg = state
-                    .snapshot
-                    .bots()
-                    .alive()
-                    .get_by_idx(tile.meta[0])
-                    .map(|bot| bot.id.color())
-                    .unwrap();
+                // Chevron is placed right in front of the bot it belongs to
+                let bot = state.tiles.get(pos + dir.turned_back());
+
+                fg = Self::bot_color(state, bot);
 
                 bg = theme::BG;
             }
 
+            TileKind::CONVEYOR => {
+                ch = match Dir::from(tile.meta[0]) {
+                    Dir::N => '^',
+                    Dir::E => '>',
+                    Dir::S => 'v',
+                    Dir::W => '<',
+                };
+
+                fg = theme::GRAY;
+                bg = theme::BG;
+            }
+
             TileKind::DOOR => {
                 ch = '+';
-                fg = theme::GRAY;
+
+                fg = if tile.is_open_door() {
+                    theme::DARK_GRAY
+                } else {
+                    theme::GRAY
+                };
+
                 bg = theme::BG;
             }
 
             TileKind::FLOOR => {
-                ch = '.';
-                fg = theme::DARK_GRAY;
+                bg = theme::BG;
+
+                let checkpoint = state
+                    .snapshot
+                    .checkpoints()
+                    .iter()
+                    .position(|checkpoint| *checkpoint == pos);
+
+                if let Some(idx) = checkpoint {
+                    ch = char::from_digit((idx % 10) as u32, 10).unwrap();
+                    fg = theme::YELLOW;
+                } else {
+                    ch = '.';
+
+                    fg = if let Some(zone) = state.snapshot.zone()
+                        && zone.contains(pos)
+                    {
+                        theme::YELLOW
+                    } else {
+                        theme::DARK_GRAY
+                    };
+                }
+            }
+
+            TileKind::TELEPORTER => {
+                ch = '&';
+                fg = theme::PINK;
+                bg = theme::BG;
+            }
+
+            TileKind::WALL_CRACKED => {
+                ch = '#';
+                fg = theme::GRAY;
                 bg = theme::BG;
             }
 
@@ -139,6 +192,12 @@ impl Map {
                 bg = theme::BG;
             }
 
+            TileKind::WATER => {
+                ch = '%';
+                fg = theme::BLUE;
+                bg = theme::BG;
+            }
+
             ObjectKind::FLAG => {
                 ch = '=';
                 fg = theme::YELLOW;
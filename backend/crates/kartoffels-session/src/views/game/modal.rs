@@ -4,6 +4,8 @@ mod go_back;
 mod help;
 mod inspect_bot;
 mod join_bot;
+mod leaderboard;
+mod rounds;
 mod spawn_bot;
 mod upload_bot;
 
@@ -13,6 +15,8 @@ pub use self::go_back::*;
 pub use self::help::*;
 pub use self::inspect_bot::*;
 pub use self::join_bot::*;
+pub use self::leaderboard::*;
+pub use self::rounds::*;
 pub use self::spawn_bot::*;
 pub use self::upload_bot::*;
 use super::Event;
@@ -28,6 +32,8 @@ pub enum Modal {
     GoBack(GoBackModal),
     InspectBot(InspectBotModal),
     JoinBot(JoinBotModal),
+    Leaderboard(LeaderboardModal),
+    Rounds(RoundsModal),
     SpawnBot(SpawnBotModal),
     UploadBot(UploadBotModal),
 
@@ -60,6 +66,12 @@ impl Modal {
             Modal::JoinBot(this) => {
                 this.render(ui, world);
             }
+            Modal::Leaderboard(this) => {
+                this.render(ui, world);
+            }
+            Modal::Rounds(this) => {
+                this.render(ui, world);
+            }
             Modal::SpawnBot(this) => {
                 this.render(ui);
             }
@@ -1,5 +1,5 @@
 use crate::views::game::Event;
-use crate::BotIdExt;
+use crate::{team_color, BotIdExt};
 use kartoffels_ui::{
     Button, RectExt, Render, Ui, VirtualRow, WidgetList, WidgetListState,
 };
@@ -28,6 +28,25 @@ impl BotsModal {
         let height = ui.area.height - 2;
 
         ui.info_window(width, height, Some(" bots "), |ui| {
+            if !world.teams().is_empty() {
+                for team in world.teams().iter() {
+                    ui.row(|ui| {
+                        ui.span(
+                            format!("team {}", team.id).fg(team_color(team.id)),
+                        );
+
+                        ui.span(format!(
+                            ": {} pts ({} bots)",
+                            team.score, team.bots
+                        ));
+                    });
+
+                    ui.space(1);
+                }
+
+                ui.space(1);
+            }
+
             VirtualRow::new(ui, Self::WIDTHS)
                 .add(Span::raw("nth"))
                 .add(Span::raw("id"))
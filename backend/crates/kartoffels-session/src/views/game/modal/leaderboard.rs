@@ -0,0 +1,113 @@
+use crate::views::game::Event;
+use crate::BotIdExt;
+use kartoffels_ui::{
+    Button, RectExt, Render, Ui, VirtualRow, WidgetList, WidgetListState,
+};
+use kartoffels_world::prelude::{BotId, Snapshot, SnapshotLap};
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Span;
+use termwiz::input::KeyCode;
+
+#[derive(Debug, Default)]
+pub struct LeaderboardModal {
+    state: WidgetListState,
+}
+
+impl LeaderboardModal {
+    const WIDTHS: &[u16] = &[
+        5,                        // nth
+        BotId::LENGTH as u16 + 1, // id
+        10,                       // time
+        6,                        // action
+    ];
+
+    pub fn render(&mut self, ui: &mut Ui<Event>, world: &Snapshot) {
+        let width = Self::WIDTHS.iter().copied().sum();
+        let height = ui.area.height - 2;
+
+        ui.info_window(width, height, Some(" leaderboard "), |ui| {
+            VirtualRow::new(ui, Self::WIDTHS)
+                .add(Span::raw("nth"))
+                .add(Span::raw("id"))
+                .add(Span::raw("best-lap"))
+                .add(Span::raw("action"));
+
+            ui.space(1);
+
+            let rows = world
+                .leaderboard()
+                .unwrap_or_default()
+                .iter()
+                .enumerate()
+                .map(|(nth, lap)| LeaderboardModalRow {
+                    nth,
+                    lap,
+                    alive: world.bots().alive().get(lap.id).is_some(),
+                });
+
+            let area = Rect {
+                height: ui.area.height - 2,
+                ..ui.area
+            };
+
+            ui.clamp(area, |ui| {
+                WidgetList::new(rows, &mut self.state).render(ui);
+            });
+
+            ui.space(2);
+
+            ui.clamp(ui.area.footer(1), |ui| {
+                ui.row(|ui| {
+                    if Button::new(KeyCode::Char('w'), "scroll-up")
+                        .render(ui)
+                        .pressed
+                    {
+                        self.state.offset = self.state.offset.saturating_sub(8);
+                    }
+
+                    ui.space(2);
+
+                    if Button::new(KeyCode::Char('s'), "scroll-down")
+                        .render(ui)
+                        .pressed
+                    {
+                        self.state.offset = self.state.offset.saturating_add(8);
+                    }
+
+                    ui.space(2);
+
+                    Button::new(KeyCode::Escape, "close")
+                        .throwing(Event::CloseModal)
+                        .right_aligned()
+                        .render(ui);
+                });
+            });
+        });
+    }
+}
+
+#[derive(Clone, Debug)]
+struct LeaderboardModalRow<'a> {
+    nth: usize,
+    lap: &'a SnapshotLap,
+    alive: bool,
+}
+
+impl Render<Event> for LeaderboardModalRow<'_> {
+    fn render(self, ui: &mut Ui<Event>) {
+        let nth = Span::raw(format!("#{}", self.nth + 1));
+        let id = Span::raw(self.lap.id.to_string()).fg(self.lap.id.color());
+        let time = Span::raw(format!("{:.2}s", self.lap.seconds()));
+
+        let join = Button::new(None, "join")
+            .throwing(Event::JoinBot { id: self.lap.id })
+            .enabled(self.alive);
+
+        VirtualRow::new(ui, LeaderboardModal::WIDTHS)
+            .add(nth)
+            .add(id)
+            .add(time)
+            .add(join);
+    }
+}
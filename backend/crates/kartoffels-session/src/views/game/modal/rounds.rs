@@ -0,0 +1,117 @@
+use crate::views::game::Event;
+use crate::{team_color, BotIdExt};
+use kartoffels_ui::{
+    Button, RectExt, Render, Ui, VirtualRow, WidgetList, WidgetListState,
+};
+use kartoffels_world::prelude::{BotId, Round, Snapshot};
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Span;
+use termwiz::input::KeyCode;
+
+#[derive(Debug, Default)]
+pub struct RoundsModal {
+    state: WidgetListState,
+}
+
+impl RoundsModal {
+    const WIDTHS: &[u16] = &[
+        7,                        // nth
+        BotId::LENGTH as u16 + 1, // winner
+        8,                        // team
+        6,                        // bots
+        8,                        // ended
+    ];
+
+    pub fn render(&mut self, ui: &mut Ui<Event>, world: &Snapshot) {
+        let width = Self::WIDTHS.iter().copied().sum();
+        let height = ui.area.height - 2;
+
+        ui.info_window(width, height, Some(" rounds "), |ui| {
+            VirtualRow::new(ui, Self::WIDTHS)
+                .add(Span::raw("nth"))
+                .add(Span::raw("winner"))
+                .add(Span::raw("team"))
+                .add(Span::raw("bots"))
+                .add(Span::raw("ended"));
+
+            ui.space(1);
+
+            let rows = world
+                .rounds()
+                .unwrap_or_default()
+                .iter()
+                .map(|round| RoundsModalRow { round });
+
+            let area = Rect {
+                height: ui.area.height - 2,
+                ..ui.area
+            };
+
+            ui.clamp(area, |ui| {
+                WidgetList::new(rows, &mut self.state).render(ui);
+            });
+
+            ui.space(2);
+
+            ui.clamp(ui.area.footer(1), |ui| {
+                ui.row(|ui| {
+                    if Button::new(KeyCode::Char('w'), "scroll-up")
+                        .render(ui)
+                        .pressed
+                    {
+                        self.state.offset = self.state.offset.saturating_sub(8);
+                    }
+
+                    ui.space(2);
+
+                    if Button::new(KeyCode::Char('s'), "scroll-down")
+                        .render(ui)
+                        .pressed
+                    {
+                        self.state.offset = self.state.offset.saturating_add(8);
+                    }
+
+                    ui.space(2);
+
+                    Button::new(KeyCode::Escape, "close")
+                        .throwing(Event::CloseModal)
+                        .right_aligned()
+                        .render(ui);
+                });
+            });
+        });
+    }
+}
+
+#[derive(Clone, Debug)]
+struct RoundsModalRow<'a> {
+    round: &'a Round,
+}
+
+impl Render<Event> for RoundsModalRow<'_> {
+    fn render(self, ui: &mut Ui<Event>) {
+        let nth = Span::raw(format!("#{}", self.round.nth));
+
+        let winner = match self.round.winner {
+            Some(id) => Span::raw(id.to_string()).fg(id.color()),
+            None => Span::raw("-"),
+        };
+
+        let team = match self.round.team {
+            Some(team) => Span::raw(team.to_string()).fg(team_color(team)),
+            None => Span::raw("-"),
+        };
+
+        let bots = Span::raw(self.round.bots.to_string());
+
+        let ended = Span::raw(self.round.ended_at.format("%H:%M").to_string());
+
+        VirtualRow::new(ui, RoundsModal::WIDTHS)
+            .add(nth)
+            .add(winner)
+            .add(team)
+            .add(bots)
+            .add(ended);
+    }
+}
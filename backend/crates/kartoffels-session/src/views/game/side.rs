@@ -29,3 +29,46 @@ impl SidePanel {
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::views::game::BottomPanel;
+    use termwiz::input::KeyCode;
+
+    /// Bottom panel and the joined-bot side panel are visible at the same
+    /// time, so they mustn't share any keys - otherwise one of the buttons
+    /// couldn't be reached.
+    #[test]
+    fn keys() {
+        let bottom = [
+            BottomPanel::go_back_btn(),
+            BottomPanel::help_btn(),
+            BottomPanel::pause_btn(false),
+            BottomPanel::rewind_btn(),
+            BottomPanel::bots_btn(),
+            BottomPanel::rounds_btn(),
+            BottomPanel::leaderboard_btn(),
+            BottomPanel::overclock_btn(),
+            BottomPanel::fork_btn(),
+        ];
+
+        let side = [
+            JoinedSidePanel::follow_btn(false),
+            JoinedSidePanel::inspect_btn(),
+            JoinedSidePanel::restart_btn(),
+            JoinedSidePanel::delete_btn(),
+            JoinedSidePanel::leave_btn(),
+        ];
+
+        let bottom: Vec<KeyCode> =
+            bottom.iter().flat_map(|btn| btn.keys()).collect();
+
+        let side: Vec<KeyCode> =
+            side.iter().flat_map(|btn| btn.keys()).collect();
+
+        for key in &side {
+            assert!(!bottom.contains(key), "key {key:?} is bound twice");
+        }
+    }
+}
@@ -108,46 +108,51 @@ impl JoinedSidePanel {
     fn btns(state: &State, bot: &JoinedBot) -> Vec<Button<'static, Event>> {
         let mut btns = Vec::new();
 
-        btns.push({
-            let label = if bot.follow {
-                "stop-following-bot"
-            } else {
-                "follow-bot"
-            };
-
-            Button::new(KeyCode::Char('f'), label).throwing(Event::FollowBot)
-        });
-
-        btns.push(
-            Button::new(KeyCode::Char('i'), "inspect-bot")
-                .throwing(Event::InspectBot),
-        );
+        btns.push(Self::follow_btn(bot.follow));
+        btns.push(Self::inspect_btn());
 
         if state.config.can_restart_bots {
-            btns.push(
-                Button::new(KeyCode::Char('R'), "restart-bot")
-                    .throwing(Event::RestartBot)
-                    .enabled(!state.paused),
-            );
+            btns.push(Self::restart_btn().enabled(!state.paused));
         }
 
         if state.config.can_delete_bots {
-            btns.push(
-                Button::new(KeyCode::Char('D'), "delete-bot")
-                    .throwing(Event::DeleteBot)
-                    .enabled(!state.paused),
-            );
+            btns.push(Self::delete_btn().enabled(!state.paused));
         }
 
         if !state.config.hero_mode {
-            btns.push(
-                Button::new(KeyCode::Char('l'), "leave-bot")
-                    .throwing(Event::LeaveBot),
-            );
+            btns.push(Self::leave_btn());
         }
 
         btns
     }
+
+    pub(super) fn follow_btn(follow: bool) -> Button<'static, Event> {
+        let label = if follow {
+            "stop-following-bot"
+        } else {
+            "follow-bot"
+        };
+
+        Button::new(KeyCode::Char('f'), label).throwing(Event::FollowBot)
+    }
+
+    pub(super) fn inspect_btn() -> Button<'static, Event> {
+        Button::new(KeyCode::Char('i'), "inspect-bot")
+            .throwing(Event::InspectBot)
+    }
+
+    pub(super) fn restart_btn() -> Button<'static, Event> {
+        Button::new(KeyCode::Char('R'), "restart-bot")
+            .throwing(Event::RestartBot)
+    }
+
+    pub(super) fn delete_btn() -> Button<'static, Event> {
+        Button::new(KeyCode::Char('D'), "delete-bot").throwing(Event::DeleteBot)
+    }
+
+    pub(super) fn leave_btn() -> Button<'static, Event> {
+        Button::new(KeyCode::Char('l'), "leave-bot").throwing(Event::LeaveBot)
+    }
 }
 
 // TODO this should be done by BotSerial and memoized
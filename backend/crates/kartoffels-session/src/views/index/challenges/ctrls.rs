@@ -14,6 +14,7 @@ const CONFIG: Config = Config {
     sync_pause: true,
 
     can_delete_bots: true,
+    can_fork: false,
     can_join_bots: false,
     can_overclock: true,
     can_pause: true,
@@ -8,8 +8,8 @@ use kartoffels_bots::DUMMY;
 use kartoffels_store::Store;
 use kartoffels_ui::{Msg, MsgButton, MsgLine};
 use kartoffels_world::prelude::{
-    BotId, Config, CreateBotRequest, Dir, Handle, Map, MapBuilder, Policy,
-    TileKind,
+    BotId, Combat, Config, CreateBotRequest, Dir, EventFilter, EventKind,
+    Handle, Map, MapBuilder, MazeTheme, Policy, TileKind,
 };
 use rand::RngCore;
 use ratatui::style::Stylize;
@@ -106,8 +106,10 @@ async fn init(store: &Store, game: &GameCtrl) -> Result<(Handle, BotId)> {
     game.set_status(Some("building".into())).await?;
 
     let world = store.create_private_world(Config {
+        checkpoints: true,
         policy: Policy {
             auto_respawn: false,
+            combat: Combat::InstantKill,
             max_alive_bots: 2,
             max_queued_bots: 1,
         },
@@ -144,7 +146,11 @@ async fn create_map(mut map: MapBuilder, mut rng: impl RngCore) -> Result<Map> {
     map.init(SIZE);
 
     utils::map::draw_borders(&mut map, AREA).await;
-    utils::map::draw_maze(&mut map, &mut rng, AREA, TIMMY_POS).await;
+
+    MazeTheme::new(AREA, 1, 0.0)
+        .build(&mut map, &mut rng, TIMMY_POS)
+        .await;
+
     draw_entrance(&mut map).await;
 
     Ok(map.finish())
@@ -181,7 +187,7 @@ async fn draw_entrance(map: &mut MapBuilder) {
 }
 
 async fn watch(world: &Handle, timmy: BotId) -> Result<()> {
-    let mut events = world.events()?;
+    let mut events = world.events(EventFilter::only([EventKind::BotKilled]))?;
 
     loop {
         if events.next_killed_bot().await? == timmy {
@@ -9,8 +9,8 @@ use kartoffels_bots::CHL_DIAMOND_HEIST_GUARD;
 use kartoffels_store::Store;
 use kartoffels_ui::{Msg, MsgButton, MsgLine};
 use kartoffels_world::prelude::{
-    Config, CreateBotRequest, Dir, Event, Handle, Map, Object, ObjectKind,
-    Policy, TileKind,
+    Combat, Config, CreateBotRequest, Dir, Event, EventFilter, EventKind,
+    Handle, Map, Object, ObjectKind, Policy, TileKind,
 };
 use ratatui::style::Stylize;
 use std::ops::ControlFlow;
@@ -129,8 +129,10 @@ async fn init(store: &Store, game: &GameCtrl) -> Result<(Handle, IVec2)> {
     game.set_status(Some("building".into())).await?;
 
     let world = store.create_private_world(Config {
+        checkpoints: true,
         policy: Policy {
             auto_respawn: false,
+            combat: Combat::InstantKill,
             max_alive_bots: 16,
             max_queued_bots: 16,
         },
@@ -153,7 +155,7 @@ async fn init(store: &Store, game: &GameCtrl) -> Result<(Handle, IVec2)> {
        +a...................|
        |----------|.........|
                   -----------
-    "#});
+    "#})?;
 
     anchors.fill(&mut map, TileKind::FLOOR);
 
@@ -202,7 +204,11 @@ async fn watch(
     world: &Handle,
     finish: IVec2,
 ) -> Result<ControlFlow<()>> {
-    let mut events = world.events()?;
+    let mut events = world.events(EventFilter::only([
+        EventKind::BotSpawned,
+        EventKind::BotKilled,
+        EventKind::BotMoved,
+    ]))?;
 
     game.sync(world.version()).await?;
     game.set_status(None).await?;
@@ -212,7 +218,7 @@ async fn watch(
 
     loop {
         match events.next().await?.event {
-            Event::BotKilled { id } => {
+            Event::BotKilled { id, .. } => {
                 if id == player {
                     game.msg(&PLAYER_KILLED_MSG).await?;
                 } else {
@@ -222,7 +228,7 @@ async fn watch(
                 return Ok(ControlFlow::Continue(()));
             }
 
-            Event::BotMoved { id, at } => {
+            Event::BotMoved { id, at, .. } => {
                 if id == player && at == finish {
                     return Ok(ControlFlow::Break(()));
                 }
@@ -7,7 +7,8 @@ use glam::{ivec2, uvec2, UVec2};
 use kartoffels_store::Store;
 use kartoffels_ui::{theme, Msg, MsgButton, MsgLine};
 use kartoffels_world::prelude::{
-    Config, Event, Handle, Object, ObjectId, ObjectKind, Policy,
+    Combat, Config, Event, EventFilter, EventKind, Handle, MazeTheme, Object,
+    ObjectId, ObjectKind, Policy,
 };
 use ratatui::style::Stylize;
 use std::ops::ControlFlow;
@@ -108,8 +109,10 @@ async fn init(
     game.set_status(Some("building".into())).await?;
 
     let world = store.create_private_world(Config {
+        checkpoints: true,
         policy: Policy {
             auto_respawn: false,
+            combat: Combat::InstantKill,
             max_alive_bots: 1,
             max_queued_bots: 1,
         },
@@ -123,7 +126,8 @@ async fn init(
     utils::map::build(store, &world, |mut map, mut rng| async move {
         map.init(SIZE);
 
-        utils::map::draw_maze(&mut map, &mut rng, SIZE, SIZE.as_ivec2() / 2)
+        MazeTheme::new(SIZE, 1, 0.0)
+            .build(&mut map, &mut rng, SIZE.as_ivec2() / 2)
             .await;
 
         utils::map::draw_holes(&mut map, &mut rng, 128).await;
@@ -177,7 +181,10 @@ async fn reset(
 }
 
 async fn watch(game: &GameCtrl, world: &Handle) -> Result<ControlFlow<(), ()>> {
-    let mut events = world.events()?;
+    let mut events = world.events(EventFilter::only([
+        EventKind::BotKilled,
+        EventKind::ObjectPicked,
+    ]))?;
     let mut flags = 4;
 
     game.sync(world.version()).await?;
@@ -33,12 +33,14 @@ static HELP: LazyLock<HelpMsg> = LazyLock::new(|| Msg {
         MsgLine::new(""),
         MsgLine::new("# controls"),
         MsgLine::new(""),
-        MsgLine::new("- use mouse and/or keyboard"),
         MsgLine::new("- press [`u`] to upload a bot"),
         MsgLine::new(
             "- press [`w`/`a`/`s`/`d`] or arrow keys to move the camera",
         ),
         MsgLine::new("- click on any bot visible on map to join it"),
+        MsgLine::new(
+            "- press [`k`] to fork the world into your own, private sandbox",
+        ),
         MsgLine::new(""),
         MsgLine::new("# uploading a bot"),
         MsgLine::new(""),
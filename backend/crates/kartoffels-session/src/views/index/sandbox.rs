@@ -1,7 +1,9 @@
 mod ctrl;
+mod sandbox_mode;
 mod sandbox_size;
 mod sandbox_theme;
 
+use self::sandbox_mode::*;
 use self::sandbox_size::*;
 use self::sandbox_theme::*;
 use crate::views::game;
@@ -10,7 +12,11 @@ use anyhow::Result;
 use glam::uvec2;
 use kartoffels_store::{SessionId, Store};
 use kartoffels_ui::{Button, Fade, FadeDir, Render, Term, Ui};
-use kartoffels_world::prelude::{ArenaTheme, DungeonTheme, Theme};
+use kartoffels_world::prelude::{
+    ArenaTheme, CaptureTheFlagMode, CaveTheme, DungeonTheme, FileTheme,
+    GemCollectionMode, KingOfTheHillMode, LastBotStandingMode, MazeTheme, Mode,
+    RacingMode, Theme,
+};
 use std::ops::ControlFlow;
 use termwiz::input::KeyCode;
 use tracing::debug;
@@ -27,13 +33,17 @@ pub async fn run(
     // when user goes back to the form, for convenience
     let mut size = SandboxSize::Medium;
     let mut theme = SandboxTheme::Dungeon;
+    let mut mode = SandboxMode::Deathmatch;
 
     loop {
-        if let Some(theme) =
-            run_once(store, term, bg, fade_in, &mut size, &mut theme).await?
+        if let Some((theme, mode)) =
+            run_once(store, term, bg, fade_in, &mut size, &mut theme, &mut mode)
+                .await?
         {
-            game::run(store, sess, term, |game| ctrl::run(store, theme, game))
-                .await?;
+            game::run(store, sess, term, |game| {
+                ctrl::run(store, theme, mode, game)
+            })
+            .await?;
 
             fade_in = true;
         } else {
@@ -49,7 +59,8 @@ async fn run_once(
     fade_in: bool,
     size: &mut SandboxSize,
     theme: &mut SandboxTheme,
-) -> Result<Option<Theme>> {
+    mode: &mut SandboxMode,
+) -> Result<Option<(Theme, Mode)>> {
     debug!("run()");
 
     let mut view = {
@@ -67,6 +78,7 @@ async fn run_once(
                 focus: None,
                 size,
                 theme,
+                mode,
             },
         }
     };
@@ -78,9 +90,9 @@ async fn run_once(
             })
             .await?;
 
-        if let Some((fade, theme)) = &view.fade_out {
+        if let Some((fade, theme, mode)) = &view.fade_out {
             if fade.is_completed() {
-                return Ok(Some(theme.clone()));
+                return Ok(Some((theme.clone(), mode.clone())));
             }
 
             continue;
@@ -98,7 +110,7 @@ async fn run_once(
 struct View<'a> {
     bg: &'a Background,
     fade_in: Option<Fade>,
-    fade_out: Option<(Fade, Theme)>,
+    fade_out: Option<(Fade, Theme, Mode)>,
     form: Form<'a>,
 }
 
@@ -113,7 +125,7 @@ impl View<'_> {
             }
         }
 
-        if let Some((fade, _)) = &self.fade_out {
+        if let Some((fade, _, _)) = &self.fade_out {
             fade.render(ui);
         }
     }
@@ -130,9 +142,9 @@ impl View<'_> {
 
             Event::Confirm => {
                 let fade = Fade::new(FadeDir::Out);
-                let theme = self.form.confirm();
+                let (theme, mode) = self.form.confirm();
 
-                self.fade_out = Some((fade, theme));
+                self.fade_out = Some((fade, theme, mode));
             }
 
             Event::FocusOn(val) => {
@@ -148,6 +160,11 @@ impl View<'_> {
                 *self.form.theme = val;
                 self.form.focus = None;
             }
+
+            Event::SetMode(val) => {
+                *self.form.mode = val;
+                self.form.focus = None;
+            }
         }
 
         ControlFlow::Continue(())
@@ -159,6 +176,7 @@ struct Form<'a> {
     focus: Option<Focus>,
     size: &'a mut SandboxSize,
     theme: &'a mut SandboxTheme,
+    mode: &'a mut SandboxMode,
 }
 
 impl Form<'_> {
@@ -177,6 +195,7 @@ impl Form<'_> {
         match &self.focus {
             Some(Focus::SandboxSize) => " sandbox › choose-size ",
             Some(Focus::SandboxTheme) => " sandbox › choose-theme ",
+            Some(Focus::SandboxMode) => " sandbox › choose-mode ",
             None => " sandbox ",
         }
     }
@@ -185,7 +204,8 @@ impl Form<'_> {
         match &self.focus {
             Some(Focus::SandboxSize) => SandboxSize::height() + 2,
             Some(Focus::SandboxTheme) => SandboxTheme::height() + 2,
-            None => 4,
+            Some(Focus::SandboxMode) => SandboxMode::height() + 2,
+            None => 5,
         }
     }
 
@@ -197,9 +217,13 @@ impl Form<'_> {
             Some(Focus::SandboxTheme) => {
                 SandboxTheme::render_choice(ui);
             }
+            Some(Focus::SandboxMode) => {
+                SandboxMode::render_choice(ui);
+            }
             None => {
                 SandboxSize::render_focus(ui, self.size);
                 SandboxTheme::render_focus(ui, self.theme);
+                SandboxMode::render_focus(ui, self.mode);
             }
         }
     }
@@ -221,8 +245,8 @@ impl Form<'_> {
         });
     }
 
-    fn confirm(&self) -> Theme {
-        match &self.theme {
+    fn confirm(&self) -> (Theme, Mode) {
+        let theme = match &self.theme {
             SandboxTheme::Arena => {
                 let radius = match &self.size {
                     SandboxSize::Tiny => 4,
@@ -234,6 +258,17 @@ impl Form<'_> {
                 Theme::Arena(ArenaTheme::new(radius))
             }
 
+            SandboxTheme::Cave => {
+                let size = match &self.size {
+                    SandboxSize::Tiny => uvec2(12, 12),
+                    SandboxSize::Small => uvec2(24, 16),
+                    SandboxSize::Medium => uvec2(60, 30),
+                    SandboxSize::Large => uvec2(80, 50),
+                };
+
+                Theme::Cave(CaveTheme::new(size, 0.45, 4))
+            }
+
             SandboxTheme::Dungeon => {
                 let size = match &self.size {
                     SandboxSize::Tiny => uvec2(10, 10),
@@ -244,7 +279,44 @@ impl Form<'_> {
 
                 Theme::Dungeon(DungeonTheme::new(size))
             }
-        }
+
+            // Hand-authored, so the size doesn't apply
+            SandboxTheme::Islands => {
+                // Unwrap-safety: The map is bundled with us and known to be valid
+                Theme::File(
+                    FileTheme::new(include_str!("sandbox/maps/islands.map"))
+                        .unwrap(),
+                )
+            }
+
+            SandboxTheme::Maze => {
+                let size = match &self.size {
+                    SandboxSize::Tiny => uvec2(11, 11),
+                    SandboxSize::Small => uvec2(21, 15),
+                    SandboxSize::Medium => uvec2(61, 31),
+                    SandboxSize::Large => uvec2(81, 51),
+                };
+
+                Theme::Maze(MazeTheme::new(size, 1, 0.1))
+            }
+        };
+
+        let mode = match &self.mode {
+            SandboxMode::Deathmatch => Mode::default(),
+            SandboxMode::CaptureTheFlag => {
+                Mode::new(CaptureTheFlagMode::new(2))
+            }
+            SandboxMode::KingOfTheHill => Mode::new(KingOfTheHillMode::new(2)),
+            SandboxMode::GemCollection => {
+                Mode::new(GemCollectionMode::default())
+            }
+            SandboxMode::LastBotStanding => {
+                Mode::new(LastBotStandingMode::default())
+            }
+            SandboxMode::Racing => Mode::new(RacingMode::new(4).unwrap()),
+        };
+
+        (theme, mode)
     }
 }
 
@@ -255,10 +327,12 @@ enum Event {
     FocusOn(Option<Focus>),
     SetSize(SandboxSize),
     SetTheme(SandboxTheme),
+    SetMode(SandboxMode),
 }
 
 #[derive(Debug, PartialEq, Eq)]
 enum Focus {
     SandboxSize,
     SandboxTheme,
+    SandboxMode,
 }
@@ -3,7 +3,9 @@ use crate::views::game::{Config, GameCtrl, HelpMsg, HelpMsgResponse};
 use anyhow::Result;
 use kartoffels_store::Store;
 use kartoffels_ui::{Msg, MsgLine};
-use kartoffels_world::prelude::{Config as WorldConfig, Policy, Theme};
+use kartoffels_world::prelude::{
+    Combat, Config as WorldConfig, Mode, Policy, Theme,
+};
 use std::future;
 use std::sync::LazyLock;
 
@@ -46,6 +48,7 @@ const CONFIG: Config = Config {
     sync_pause: true,
 
     can_delete_bots: true,
+    can_fork: false,
     can_join_bots: true,
     can_overclock: false,
     can_pause: true,
@@ -54,8 +57,13 @@ const CONFIG: Config = Config {
     can_upload_bots: true,
 };
 
-pub async fn run(store: &Store, theme: Theme, game: GameCtrl) -> Result<()> {
-    init(store, theme, &game).await?;
+pub async fn run(
+    store: &Store,
+    theme: Theme,
+    mode: Mode,
+    game: GameCtrl,
+) -> Result<()> {
+    init(store, theme, mode, &game).await?;
 
     game.set_config(CONFIG).await?;
     game.set_status(None).await?;
@@ -63,15 +71,23 @@ pub async fn run(store: &Store, theme: Theme, game: GameCtrl) -> Result<()> {
     future::pending().await
 }
 
-async fn init(store: &Store, theme: Theme, game: &GameCtrl) -> Result<()> {
+async fn init(
+    store: &Store,
+    theme: Theme,
+    mode: Mode,
+    game: &GameCtrl,
+) -> Result<()> {
     game.set_help(Some(&*HELP)).await?;
     game.set_config(CONFIG.disabled()).await?;
     game.set_status(Some("building".into())).await?;
 
     let world = store.create_private_world(WorldConfig {
+        checkpoints: true,
+        mode,
         name: "sandbox".into(),
         policy: Policy {
             auto_respawn: true,
+            combat: Combat::InstantKill,
             max_alive_bots: MAX_BOTS,
             max_queued_bots: MAX_BOTS,
         },
@@ -80,6 +96,9 @@ async fn init(store: &Store, theme: Theme, game: &GameCtrl) -> Result<()> {
 
     game.join(world.clone()).await?;
 
+    let spawn_points = theme.spawn_points();
+    let objects = theme.objects();
+
     utils::map::build(store, &world, |mut mapb, mut rng| async move {
         let map = theme.create_map(&mut rng)?;
 
@@ -89,5 +108,11 @@ async fn init(store: &Store, theme: Theme, game: &GameCtrl) -> Result<()> {
     })
     .await?;
 
+    world.set_spawn_points(spawn_points).await?;
+
+    for (obj, pos) in objects {
+        world.create_object(obj, pos).await?;
+    }
+
     Ok(())
 }
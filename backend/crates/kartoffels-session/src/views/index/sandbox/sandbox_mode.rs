@@ -0,0 +1,74 @@
+use super::{Event, Focus};
+use kartoffels_ui::{Button, Render, Ui};
+use std::fmt;
+use termwiz::input::KeyCode;
+
+#[derive(Clone, Debug)]
+pub enum SandboxMode {
+    Deathmatch,
+    CaptureTheFlag,
+    KingOfTheHill,
+    GemCollection,
+    LastBotStanding,
+    Racing,
+}
+
+impl SandboxMode {
+    pub fn render_focus(ui: &mut Ui<Event>, val: &Self) {
+        Button::new(KeyCode::Char('m'), format!("mode: {val}"))
+            .throwing(Event::FocusOn(Some(Focus::SandboxMode)))
+            .render(ui);
+    }
+
+    pub fn render_choice(ui: &mut Ui<Event>) {
+        for val in Self::all() {
+            Button::new(val.key(), val.to_string())
+                .throwing(Event::SetMode(val))
+                .render(ui);
+        }
+    }
+
+    pub fn height() -> u16 {
+        Self::all().count() as u16
+    }
+
+    fn all() -> impl Iterator<Item = Self> {
+        [
+            Self::Deathmatch,
+            Self::CaptureTheFlag,
+            Self::KingOfTheHill,
+            Self::GemCollection,
+            Self::LastBotStanding,
+            Self::Racing,
+        ]
+        .into_iter()
+    }
+
+    fn key(&self) -> KeyCode {
+        KeyCode::Char(match self {
+            Self::Deathmatch => 'd',
+            Self::CaptureTheFlag => 'c',
+            Self::KingOfTheHill => 'k',
+            Self::GemCollection => 'g',
+            Self::LastBotStanding => 'l',
+            Self::Racing => 'r',
+        })
+    }
+}
+
+impl fmt::Display for SandboxMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Deathmatch => "deathmatch",
+                Self::CaptureTheFlag => "capture-the-flag",
+                Self::KingOfTheHill => "king-of-the-hill",
+                Self::GemCollection => "gem-collection",
+                Self::LastBotStanding => "last-bot-standing",
+                Self::Racing => "racing",
+            }
+        )
+    }
+}
@@ -6,7 +6,10 @@ use termwiz::input::KeyCode;
 #[derive(Clone, Debug)]
 pub enum SandboxTheme {
     Arena,
+    Cave,
     Dungeon,
+    Islands,
+    Maze,
 }
 
 impl SandboxTheme {
@@ -29,13 +32,23 @@ impl SandboxTheme {
     }
 
     fn all() -> impl Iterator<Item = Self> {
-        [Self::Arena, Self::Dungeon].into_iter()
+        [
+            Self::Arena,
+            Self::Cave,
+            Self::Dungeon,
+            Self::Islands,
+            Self::Maze,
+        ]
+        .into_iter()
     }
 
     fn key(&self) -> KeyCode {
         KeyCode::Char(match self {
             Self::Arena => 'a',
+            Self::Cave => 'c',
             Self::Dungeon => 'd',
+            Self::Islands => 'i',
+            Self::Maze => 'm',
         })
     }
 }
@@ -47,7 +60,10 @@ impl fmt::Display for SandboxTheme {
             "{}",
             match self {
                 Self::Arena => "arena",
+                Self::Cave => "cave",
                 Self::Dungeon => "dungeon",
+                Self::Islands => "islands",
+                Self::Maze => "maze",
             }
         )
     }
@@ -3,9 +3,10 @@ use anyhow::Result;
 use glam::ivec2;
 use kartoffels_store::Store;
 use kartoffels_world::prelude::{
-    ArenaTheme, Clock, Config as WorldConfig, EventStream, Handle, Policy,
-    SnapshotStream, Theme,
+    ArenaTheme, Clock, Combat, Config as WorldConfig, EventFilter, EventKind,
+    EventStream, Handle, Policy, SnapshotStream, SnapshotUpdate, Theme,
 };
+use std::collections::HashSet;
 
 pub struct TutorialCtxt {
     pub game: GameCtrl,
@@ -22,6 +23,7 @@ impl TutorialCtxt {
             sync_pause: true,
 
             can_delete_bots: true,
+            can_fork: false,
             can_join_bots: false,
             can_overclock: false,
             can_pause: false,
@@ -35,6 +37,7 @@ impl TutorialCtxt {
             clock: Clock::Normal,
             policy: Policy {
                 auto_respawn: false,
+                combat: Combat::InstantKill,
                 max_alive_bots: 16,
                 max_queued_bots: 16,
             },
@@ -46,7 +49,10 @@ impl TutorialCtxt {
         game.join(world.clone()).await?;
 
         Ok(Self {
-            events: world.events()?,
+            events: world.events(EventFilter::only([
+                EventKind::BotSpawned,
+                EventKind::BotKilled,
+            ]))?,
             snapshots: world.snapshots(),
             game,
             world,
@@ -60,25 +66,41 @@ impl TutorialCtxt {
     }
 
     pub async fn delete_bots(&mut self) -> Result<()> {
-        let snapshot = self.snapshots.next().await?;
+        // Instead of polling entire snapshots, let's just watch which bots
+        // disappear
+        let mut updates = self.world.snapshots().into_deltas();
+        let mut alive_ids = HashSet::new();
+        let mut dead_ids = HashSet::new();
 
-        let alive_ids: Vec<_> =
-            snapshot.bots().alive().iter().map(|bot| bot.id).collect();
+        loop {
+            match updates.next().await? {
+                SnapshotUpdate::Full(snapshot) => {
+                    alive_ids = snapshot
+                        .bots()
+                        .alive()
+                        .iter()
+                        .map(|bot| bot.id)
+                        .collect();
 
-        let dead_ids: Vec<_> = snapshot.bots().dead().ids().collect();
+                    dead_ids = snapshot.bots().dead().ids().collect();
 
-        for id in alive_ids.iter().chain(&dead_ids) {
-            self.world.delete_bot(*id).await?;
-        }
+                    for id in alive_ids.iter().chain(&dead_ids) {
+                        self.world.delete_bot(*id).await?;
+                    }
+                }
 
-        loop {
-            let snapshot = self.snapshots.next().await?;
+                SnapshotUpdate::Delta(delta) => {
+                    for id in &delta.bots_removed {
+                        alive_ids.remove(id);
+                    }
 
-            if !snapshot.bots().alive().has_any_of(&alive_ids) {
-                return Ok(());
+                    for id in &delta.dead_removed {
+                        dead_ids.remove(id);
+                    }
+                }
             }
 
-            if !snapshot.bots().dead().has_any_of(&dead_ids) {
+            if alive_ids.is_empty() || dead_ids.is_empty() {
                 return Ok(());
             }
         }
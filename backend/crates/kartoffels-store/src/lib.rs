@@ -15,7 +15,7 @@ use std::collections::hash_map;
 use std::fmt;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
-use tokio::sync::{oneshot, Semaphore};
+use tokio::sync::{oneshot, OwnedSemaphorePermit, Semaphore};
 use tracing::{debug, info};
 
 #[derive(Debug)]
@@ -64,19 +64,33 @@ impl Store {
         &self,
         config: WorldConfig,
     ) -> Result<WorldHandle> {
-        let permit = self
-            .private_worlds_sem
+        let permit = self.acquire_private_world()?;
+        let handle = kartoffels_world::create(config).with_permit(permit);
+
+        Ok(self.register_private_world(handle))
+    }
+
+    /// Creates a private copy of given world (see: [`WorldHandle::fork()`]).
+    pub async fn fork_world(&self, world: &WorldHandle) -> Result<WorldHandle> {
+        let permit = self.acquire_private_world()?;
+        let handle = world.fork().await?.with_permit(permit);
+
+        Ok(self.register_private_world(handle))
+    }
+
+    fn acquire_private_world(&self) -> Result<OwnedSemaphorePermit> {
+        self.private_worlds_sem
             .clone()
             .try_acquire_owned()
-            .map_err(|_| anyhow!("ouch, the server is currently overloaded"))?;
-
-        let handle = kartoffels_world::create(config).with_permit(permit);
+            .map_err(|_| anyhow!("ouch, the server is currently overloaded"))
+    }
 
+    fn register_private_world(&self, handle: WorldHandle) -> WorldHandle {
         if self.testing {
             self.private_worlds.lock().unwrap().push(handle.clone());
         }
 
-        Ok(handle)
+        handle
     }
 
     pub fn first_private_world(&self) -> WorldHandle {
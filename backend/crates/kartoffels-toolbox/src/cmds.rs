@@ -1,3 +1,13 @@
+mod events;
+mod replay;
+mod replay_to_json;
+mod set_file_theme;
+mod set_wasm_mode;
 mod world_to_json;
 
+pub use self::events::*;
+pub use self::replay::*;
+pub use self::replay_to_json::*;
+pub use self::set_file_theme::*;
+pub use self::set_wasm_mode::*;
 pub use self::world_to_json::*;
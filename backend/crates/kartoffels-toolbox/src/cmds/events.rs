@@ -0,0 +1,88 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use clap::Parser;
+use kartoffels_world::prelude::{BotId, EventFilter, EventKind, EventLog};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// Queries world's event log (see: `kartoffels serve --log-events`), printing
+/// matching events as JSON lines.
+#[derive(Debug, Parser)]
+pub struct EventsCmd {
+    /// Path to the log (`*.events`) or the world itself (`*.world`); rotated
+    /// files are picked up automatically
+    log: PathBuf,
+
+    /// Show only events of given kind (e.g. `bot-killed`), can be repeated
+    #[clap(long)]
+    kind: Vec<EventKind>,
+
+    /// Show only events involving given bot, can be repeated
+    #[clap(long)]
+    bot: Vec<BotId>,
+
+    /// Show only events that happened at or after given time (RFC 3339)
+    #[clap(long)]
+    since: Option<DateTime<Utc>>,
+
+    /// Show only events that happened before given time (RFC 3339)
+    #[clap(long)]
+    until: Option<DateTime<Utc>>,
+
+    /// Instead of printing events, print how many of them there are per kind
+    #[clap(long)]
+    count: bool,
+}
+
+impl EventsCmd {
+    pub(crate) fn run(self) -> Result<()> {
+        let path = if self.log.extension().is_some_and(|ext| ext == "world") {
+            self.log.with_extension("events")
+        } else {
+            self.log.clone()
+        };
+
+        let filter = {
+            let mut filter = EventFilter::all();
+
+            if !self.kind.is_empty() {
+                filter = filter.with_kinds(self.kind.iter().copied());
+            }
+
+            for id in &self.bot {
+                filter = filter.with_bot(*id);
+            }
+
+            filter
+        };
+
+        let mut counts = BTreeMap::<_, usize>::new();
+
+        for entry in EventLog::read(&path)? {
+            let entry = entry?;
+
+            if self.since.is_some_and(|since| entry.at < since)
+                || self.until.is_some_and(|until| entry.at >= until)
+                || !filter.matches(&entry.event)
+            {
+                continue;
+            }
+
+            if self.count {
+                *counts
+                    .entry(format!("{:?}", entry.event.kind()))
+                    .or_default() += 1;
+            } else {
+                println!("{}", serde_json::to_string(&entry)?);
+            }
+        }
+
+        if self.count {
+            for (kind, count) in counts {
+                println!("{kind}: {count}");
+            }
+        }
+
+        Ok(())
+    }
+}
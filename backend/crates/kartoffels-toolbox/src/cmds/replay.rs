@@ -0,0 +1,48 @@
+use anyhow::{Context, Result};
+use clap::Parser;
+use kartoffels_world::prelude::{Replay, Replayer};
+use std::num::NonZeroU64;
+use std::path::PathBuf;
+
+/// Re-simulates a recorded world and prints its state.
+#[derive(Debug, Parser)]
+pub struct ReplayCmd {
+    replay: PathBuf,
+
+    /// Print the state every given number of ticks, besides printing it at
+    /// the end
+    #[clap(long)]
+    every: Option<NonZeroU64>,
+}
+
+impl ReplayCmd {
+    pub(crate) fn run(self) -> Result<()> {
+        let replay = Replay::load(&self.replay)?;
+        let ticks = replay.ticks();
+
+        let mut replayer =
+            Replayer::new(replay).context("couldn't start the replay")?;
+
+        while replayer.tick() {
+            let Some(every) = self.every else {
+                continue;
+            };
+
+            if replayer.elapsed() % every.get() == 0
+                && replayer.elapsed() < ticks
+            {
+                print(&mut replayer, ticks);
+            }
+        }
+
+        print(&mut replayer, ticks);
+
+        Ok(())
+    }
+}
+
+fn print(replayer: &mut Replayer, ticks: u64) {
+    println!("> tick {} / {}", replayer.elapsed(), ticks);
+    println!();
+    println!("{}", replayer.snapshot());
+}
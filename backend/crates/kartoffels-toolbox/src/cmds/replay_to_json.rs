@@ -0,0 +1,60 @@
+use anyhow::{anyhow, Context, Result};
+use clap::Parser;
+use kartoffels_utils::{cbor_to_json, CborMapExt};
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::PathBuf;
+
+/// Converts recorded world into JSON, useful for inspecting what got
+/// recorded.
+#[derive(Debug, Parser)]
+pub struct ReplayToJsonCmd {
+    src: PathBuf,
+
+    #[clap(long)]
+    dst: Option<PathBuf>,
+}
+
+impl ReplayToJsonCmd {
+    pub(crate) fn run(self) -> Result<()> {
+        let dst_path =
+            self.dst.unwrap_or_else(|| self.src.with_extension("json"));
+
+        let mut src: ciborium::Value = ciborium::from_reader({
+            let src = File::open(&self.src).with_context(|| {
+                format!("couldn't read from {}", self.src.display())
+            })?;
+
+            BufReader::new(src)
+        })?;
+
+        // The initial world is stored the same way `*.world` files are, so
+        // let's decode it as well - otherwise it'd be just a blob of bytes
+        let entries = src
+            .as_map_mut()
+            .ok_or_else(|| anyhow!("replay is not a map"))?;
+
+        if let Some(world) = entries.remove_entry("world") {
+            let world = world
+                .as_bytes()
+                .ok_or_else(|| anyhow!("world is not a blob"))?;
+
+            let world: ciborium::Value =
+                ciborium::from_reader(world.get(16..).unwrap_or_default())
+                    .context("couldn't read world")?;
+
+            entries.add_entry("world", world);
+        }
+
+        let dst = cbor_to_json(src, true);
+
+        let dst = serde_json::to_string_pretty(&dst)
+            .context("couldn't serialize to json")?;
+
+        fs::write(&dst_path, dst).with_context(|| {
+            format!("couldn't write to {}", dst_path.display())
+        })?;
+
+        Ok(())
+    }
+}
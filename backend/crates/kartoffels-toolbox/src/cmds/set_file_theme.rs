@@ -0,0 +1,23 @@
+use anyhow::Result;
+use clap::Parser;
+use kartoffels_world::prelude::{FileTheme, Theme};
+use std::path::PathBuf;
+
+/// Replaces world's theme and map with given hand-authored map.
+///
+/// Note that this operates directly on the world's file, so the server should
+/// be stopped when running this command.
+#[derive(Debug, Parser)]
+pub struct SetFileThemeCmd {
+    world: PathBuf,
+    map: PathBuf,
+}
+
+impl SetFileThemeCmd {
+    pub(crate) fn run(self) -> Result<()> {
+        let theme = FileTheme::load(&self.map)?;
+        let map = theme.create_map()?;
+
+        kartoffels_world::set_theme(&self.world, Theme::File(theme), map)
+    }
+}
@@ -0,0 +1,27 @@
+use anyhow::{Context, Result};
+use clap::Parser;
+use kartoffels_world::prelude::{Mode, WasmMode};
+use std::fs;
+use std::path::PathBuf;
+
+/// Replaces world's game mode with given WebAssembly module.
+///
+/// Note that this operates directly on the world's file, so the server should
+/// be stopped when running this command.
+#[derive(Debug, Parser)]
+pub struct SetWasmModeCmd {
+    world: PathBuf,
+    module: PathBuf,
+}
+
+impl SetWasmModeCmd {
+    pub(crate) fn run(self) -> Result<()> {
+        let code = fs::read(&self.module).with_context(|| {
+            format!("couldn't read from {}", self.module.display())
+        })?;
+
+        let mode = WasmMode::new(code).context("couldn't load module")?;
+
+        kartoffels_world::set_mode(&self.world, Mode::new(mode))
+    }
+}
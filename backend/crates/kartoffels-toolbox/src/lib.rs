@@ -6,12 +6,22 @@ use clap::Parser;
 
 #[derive(Debug, Parser)]
 pub enum Cmd {
+    Events(EventsCmd),
+    Replay(ReplayCmd),
+    ReplayToJson(ReplayToJsonCmd),
+    SetFileTheme(SetFileThemeCmd),
+    SetWasmMode(SetWasmModeCmd),
     WorldToJson(WorldToJsonCmd),
 }
 
 impl Cmd {
     pub fn run(self) -> Result<()> {
         match self {
+            Cmd::Events(cmd) => cmd.run(),
+            Cmd::Replay(cmd) => cmd.run(),
+            Cmd::ReplayToJson(cmd) => cmd.run(),
+            Cmd::SetFileTheme(cmd) => cmd.run(),
+            Cmd::SetWasmMode(cmd) => cmd.run(),
             Cmd::WorldToJson(cmd) => cmd.run(),
         }
     }
@@ -68,6 +68,11 @@ impl<'a, T> Button<'a, T> {
         self
     }
 
+    /// Returns keys that activate this button.
+    pub fn keys(&self) -> impl Iterator<Item = KeyCode> + '_ {
+        self.options.iter().filter_map(|(key, _)| *key)
+    }
+
     pub fn width(&self) -> u16 {
         if self.is_mouse_only() {
             self.label.len() as u16 + 2
@@ -3,6 +3,7 @@ mod arm;
 mod battery;
 mod compass;
 mod events;
+mod health;
 mod id;
 mod inventory;
 mod mmio;
@@ -16,6 +17,7 @@ pub use self::arm::*;
 pub use self::battery::*;
 pub use self::compass::*;
 pub use self::events::*;
+pub use self::health::*;
 pub use self::id::*;
 pub use self::inventory::*;
 pub use self::mmio::*;
@@ -25,7 +27,7 @@ pub use self::serial::*;
 pub use self::timer::*;
 use crate::{Dir, World};
 use glam::IVec2;
-use kartoffels_cpu::{Cpu, Firmware};
+use kartoffels_cpu::{Cpu, Firmware, Mmio};
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
@@ -41,6 +43,7 @@ pub struct AliveBot {
     pub dir: Dir,
     pub events: BotEvents,
     pub fw: Firmware,
+    pub health: BotHealth,
     pub id: BotId,
     pub inventory: BotInventory,
     pub motor: BotMotor,
@@ -48,6 +51,7 @@ pub struct AliveBot {
     pub pos: IVec2,
     pub radar: BotRadar,
     pub serial: BotSerial,
+    pub team: Option<u8>,
     pub timer: BotTimer,
 }
 
@@ -59,6 +63,7 @@ impl AliveBot {
     const MEM_ARM: u32 = 4 * 1024;
     const MEM_RADAR: u32 = 5 * 1024;
     const MEM_COMPASS: u32 = 6 * 1024;
+    const MEM_HEALTH: u32 = 7 * 1024;
 
     pub fn new(
         rng: &mut impl RngCore,
@@ -77,6 +82,7 @@ impl AliveBot {
             dir,
             events: bot.events,
             fw: bot.fw,
+            health: Default::default(),
             id: bot.id,
             inventory: Default::default(),
             motor: Default::default(),
@@ -84,6 +90,7 @@ impl AliveBot {
             pos,
             radar: Default::default(),
             serial: Default::default(),
+            team: bot.team,
             timer: BotTimer::new(rng),
         }
     }
@@ -98,17 +105,14 @@ impl AliveBot {
     ) -> Result<Option<BotAction>, Box<str>> {
         let mut action = None;
 
-        self.timer.tick();
-        self.serial.tick();
-        self.arm.tick();
-        self.motor.tick();
-        self.radar.tick();
-        self.compass.tick(self.dir);
+        self.tick_peripherals();
 
         self.cpu.tick(BotMmio {
             arm: &mut self.arm,
             battery: &mut self.battery,
             compass: &mut self.compass,
+            health: &mut self.health,
+            inventory: &mut self.inventory,
             motor: &mut self.motor,
             radar: &mut self.radar,
             serial: &mut self.serial,
@@ -122,11 +126,49 @@ impl AliveBot {
                 objects: &world.objects,
                 pos: self.pos,
                 rng: &mut world.rng,
+                team: self.team,
             },
         })?;
 
         Ok(action)
     }
+
+    /// Ticks the bot without giving it access to the world, which allows to
+    /// tick many bots in parallel.
+    ///
+    /// Returns `Ok(false)` (without ticking anything) if the bot is about to
+    /// access MMIO, in which case it must be ticked through [`Self::tick()`].
+    pub fn tick_isolated(&mut self) -> Result<bool, Box<str>> {
+        if self.cpu.is_mmio_next() {
+            return Ok(false);
+        }
+
+        self.tick_peripherals();
+        self.cpu.tick(NoMmio)?;
+
+        Ok(true)
+    }
+
+    fn tick_peripherals(&mut self) {
+        self.timer.tick();
+        self.serial.tick();
+        self.arm.tick();
+        self.motor.tick();
+        self.radar.tick();
+        self.compass.tick(self.dir);
+    }
+}
+
+struct NoMmio;
+
+impl Mmio for NoMmio {
+    fn load(self, _: u32) -> Result<u32, ()> {
+        Err(())
+    }
+
+    fn store(self, _: u32, _: u32) -> Result<(), ()> {
+        Err(())
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -146,4 +188,5 @@ pub struct QueuedBot {
     pub pos: Option<IVec2>,
     pub requeued: bool,
     pub serial: BotSerial,
+    pub team: Option<u8>,
 }
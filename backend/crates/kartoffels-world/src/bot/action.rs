@@ -1,3 +1,4 @@
+use crate::Dir;
 use glam::IVec2;
 
 /// Action to apply on the world after [`AliveBot::tick()`] finishes.
@@ -5,10 +6,17 @@ use glam::IVec2;
 /// This exists mostly to avoid borrowck issues - e.g. moving a robot requires
 /// unique access to the entire `world.bots`, which conflicts with `.tick()`
 /// that needs unique access to the bot itself.
+///
+/// Some actions (e.g. [`BotAction::MotorTurn`]) have already been applied by
+/// the time the tick finishes - they are reported here only so that the world
+/// can announce them through [`crate::Event`]s.
 #[derive(Debug)]
 pub enum BotAction {
     ArmDrop { at: IVec2, idx: u8 },
     ArmPick { at: IVec2 },
     ArmStab { at: IVec2 },
-    MotorMove { at: IVec2 },
+    MotorMove { step: IVec2, len: u8 },
+    MotorTurn { dir: Dir },
+    RadarScan { range: u8 },
+    SerialWrite { value: u32 },
 }
@@ -9,6 +9,8 @@ pub struct BotArm {
 }
 
 impl BotArm {
+    pub const STAB_DAMAGE: u32 = 40;
+
     pub fn tick(&mut self) {
         self.cooldown = self.cooldown.saturating_sub(1);
     }
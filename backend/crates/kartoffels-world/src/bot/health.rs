@@ -0,0 +1,52 @@
+use crate::AliveBot;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BotHealth {
+    hp: u32,
+}
+
+impl BotHealth {
+    pub const MAX_HP: u32 = 100;
+
+    pub fn hp(&self) -> u32 {
+        self.hp
+    }
+
+    /// Deals given amount of damage and returns whether the bot has died.
+    pub fn damage(&mut self, amount: u32) -> bool {
+        self.hp = self.hp.saturating_sub(amount);
+        self.hp == 0
+    }
+
+    pub fn mmio_load(&self, addr: u32) -> Result<u32, ()> {
+        match addr {
+            AliveBot::MEM_HEALTH => Ok(self.hp),
+
+            _ => Err(()),
+        }
+    }
+}
+
+impl Default for BotHealth {
+    fn default() -> Self {
+        Self { hp: Self::MAX_HP }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn smoke() {
+        let mut target = BotHealth::default();
+
+        assert_eq!(100, target.mmio_load(AliveBot::MEM_HEALTH).unwrap());
+        assert!(!target.damage(40));
+        assert_eq!(60, target.mmio_load(AliveBot::MEM_HEALTH).unwrap());
+        assert!(!target.damage(40));
+        assert!(target.damage(40));
+        assert_eq!(0, target.mmio_load(AliveBot::MEM_HEALTH).unwrap());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{Object, ObjectId};
+use crate::{AliveBot, Object, ObjectId};
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 
@@ -25,6 +25,41 @@ impl BotInventory {
             .remove(idx as usize)
             .map(|obj| (obj.id, obj.obj))
     }
+
+    pub fn iter(&self) -> impl Iterator<Item = (ObjectId, Object)> + '_ {
+        self.objects.iter().map(|obj| (obj.id, obj.obj))
+    }
+
+    pub fn has(&self, id: ObjectId) -> bool {
+        self.objects.iter().any(|obj| obj.id == id)
+    }
+
+    pub fn mmio_load(&self, addr: u32) -> Result<u32, ()> {
+        match addr {
+            const { AliveBot::MEM_ARM + 4 } => Ok(self.objects.len() as u32),
+
+            addr if addr >= AliveBot::MEM_ARM + 8
+                && addr < AliveBot::MEM_ARM + 8 + 4 * (Self::SIZE as u32) =>
+            {
+                let idx = (addr - AliveBot::MEM_ARM - 8) / 4;
+
+                Ok(self
+                    .objects
+                    .get(idx as usize)
+                    .map(|obj| {
+                        u32::from_be_bytes([
+                            obj.obj.kind,
+                            obj.obj.meta[0],
+                            obj.obj.meta[1],
+                            obj.obj.meta[2],
+                        ])
+                    })
+                    .unwrap_or(0))
+            }
+
+            _ => Err(()),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -38,6 +73,7 @@ struct BotInventoryObject {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ObjectKind;
 
     #[test]
     fn smoke() {
@@ -62,4 +98,48 @@ mod tests {
         assert_eq!(255, target.take(0).unwrap().1.kind);
         assert_eq!(1, target.take(28).unwrap().1.kind);
     }
+
+    #[test]
+    fn mmio() {
+        let mut target = BotInventory::default();
+
+        let len = |target: &BotInventory| {
+            target.mmio_load(AliveBot::MEM_ARM + 4).unwrap()
+        };
+
+        let slot = |target: &BotInventory, idx: u32| {
+            target.mmio_load(AliveBot::MEM_ARM + 8 + 4 * idx).unwrap()
+        };
+
+        assert_eq!(0, len(&target));
+        assert_eq!(0, slot(&target, 0));
+
+        target
+            .add(
+                ObjectId::new(1),
+                Object {
+                    kind: ObjectKind::FLAG,
+                    meta: [1, 2, 3],
+                },
+            )
+            .unwrap();
+
+        target
+            .add(ObjectId::new(2), Object::new(ObjectKind::GEM))
+            .unwrap();
+
+        assert_eq!(2, len(&target));
+        assert_eq!(u32::from_be_bytes([b'*', 0, 0, 0]), slot(&target, 0));
+        assert_eq!(u32::from_be_bytes([b'=', 1, 2, 3]), slot(&target, 1));
+        assert_eq!(0, slot(&target, 2));
+        assert_eq!(0, slot(&target, 31));
+
+        assert!(target.mmio_load(AliveBot::MEM_ARM).is_err());
+        assert!(target.mmio_load(AliveBot::MEM_ARM + 8 + 4 * 32).is_err());
+
+        target.take(0).unwrap();
+
+        assert_eq!(1, len(&target));
+        assert_eq!(u32::from_be_bytes([b'=', 1, 2, 3]), slot(&target, 0));
+    }
 }
@@ -1,6 +1,6 @@
 use super::{
-    BotAction, BotArm, BotBattery, BotCompass, BotMotor, BotRadar, BotSerial,
-    BotTimer,
+    BotAction, BotArm, BotBattery, BotCompass, BotHealth, BotInventory,
+    BotMotor, BotRadar, BotSerial, BotTimer,
 };
 use crate::{AliveBots, Dir, Map, Objects};
 use glam::IVec2;
@@ -11,6 +11,8 @@ pub struct BotMmio<'a> {
     pub arm: &'a mut BotArm,
     pub battery: &'a mut BotBattery,
     pub compass: &'a mut BotCompass,
+    pub health: &'a mut BotHealth,
+    pub inventory: &'a mut BotInventory,
     pub motor: &'a mut BotMotor,
     pub radar: &'a mut BotRadar,
     pub serial: &'a mut BotSerial,
@@ -26,15 +28,17 @@ impl Mmio for BotMmio<'_> {
             .or_else(|_| self.serial.mmio_load(addr))
             .or_else(|_| self.motor.mmio_load(addr))
             .or_else(|_| self.arm.mmio_load(addr))
+            .or_else(|_| self.inventory.mmio_load(addr))
             .or_else(|_| self.radar.mmio_load(addr))
             .or_else(|_| self.compass.mmio_load(addr))
+            .or_else(|_| self.health.mmio_load(addr))
     }
 
     fn store(mut self, addr: u32, val: u32) -> Result<(), ()> {
         self.timer
             .mmio_store(addr, val)
             .or_else(|_| self.battery.mmio_store(addr, val))
-            .or_else(|_| self.serial.mmio_store(addr, val))
+            .or_else(|_| self.serial.mmio_store(&mut self.ctxt, addr, val))
             .or_else(|_| self.motor.mmio_store(&mut self.ctxt, addr, val))
             .or_else(|_| self.arm.mmio_store(&mut self.ctxt, addr, val))
             .or_else(|_| self.radar.mmio_store(&mut self.ctxt, addr, val))
@@ -49,6 +53,7 @@ pub struct BotMmioContext<'a> {
     pub objects: &'a Objects,
     pub pos: IVec2,
     pub rng: &'a mut dyn RngCore,
+    pub team: Option<u8>,
 }
 
 impl BotMmioContext<'_> {
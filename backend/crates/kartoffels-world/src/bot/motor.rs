@@ -5,16 +5,30 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct BotMotor {
     cooldown: u32,
+    status: BotMotorStatus,
 }
 
 impl BotMotor {
+    /// How many times slower the bot moves when it's standing in water.
+    pub const WATER_SLOWDOWN: u32 = 2;
+
     pub fn tick(&mut self) {
         self.cooldown = self.cooldown.saturating_sub(1);
     }
 
+    pub fn report(&mut self, status: BotMotorStatus) {
+        self.status = status;
+    }
+
+    #[cfg(test)]
+    pub fn status(&self) -> BotMotorStatus {
+        self.status
+    }
+
     pub fn mmio_load(&self, addr: u32) -> Result<u32, ()> {
         match addr {
             AliveBot::MEM_MOTOR => Ok((self.cooldown == 0) as u32),
+            const { AliveBot::MEM_MOTOR + 8 } => Ok(self.status as u32),
 
             _ => Err(()),
         }
@@ -28,12 +42,23 @@ impl BotMotor {
     ) -> Result<(), ()> {
         match addr {
             AliveBot::MEM_MOTOR => {
-                if self.cooldown == 0 && val > 0 {
-                    *ctxt.action = Some(BotAction::MotorMove {
-                        at: ctxt.pos + *ctxt.dir,
-                    });
+                if self.cooldown == 0 {
+                    let (step, len, cooldown) = match val {
+                        1 => (ctxt.dir.as_vec(), 1, ctxt.cooldown(20_000, 15)),
+                        2 => (-ctxt.dir.as_vec(), 1, ctxt.cooldown(25_000, 15)),
+                        3 => (ctxt.dir.as_vec(), 2, ctxt.cooldown(35_000, 15)),
+                        _ => return Ok(()),
+                    };
 
-                    self.cooldown = ctxt.cooldown(20_000, 15);
+                    let cooldown = if ctxt.map.get(ctxt.pos).is_water() {
+                        cooldown * Self::WATER_SLOWDOWN
+                    } else {
+                        cooldown
+                    };
+
+                    *ctxt.action = Some(BotAction::MotorMove { step, len });
+
+                    self.cooldown = cooldown;
                 }
 
                 Ok(())
@@ -51,6 +76,9 @@ impl BotMotor {
                     }
 
                     if val != 0 {
+                        *ctxt.action =
+                            Some(BotAction::MotorTurn { dir: *ctxt.dir });
+
                         self.cooldown = ctxt.cooldown(15000, 15);
                     }
                 }
@@ -62,3 +90,145 @@ impl BotMotor {
         }
     }
 }
+
+/// Outcome of the latest move, readable by the firmware.
+#[repr(u8)]
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub enum BotMotorStatus {
+    #[default]
+    #[serde(rename = "idle")]
+    Idle = 0,
+
+    #[serde(rename = "moved")]
+    Moved = 1,
+
+    #[serde(rename = "blocked-by-wall")]
+    BlockedByWall = 2,
+
+    #[serde(rename = "blocked-by-bot")]
+    BlockedByBot = 3,
+
+    #[serde(rename = "blocked-by-object")]
+    BlockedByObject = 4,
+
+    #[serde(rename = "fell-into-void")]
+    FellIntoVoid = 5,
+
+    /// Bot sprinted, but stopped after the first tile, because the second
+    /// one was blocked
+    #[serde(rename = "moved-partially")]
+    MovedPartially = 6,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AliveBots, Dir, Map, Objects, TileKind};
+    use glam::{ivec2, uvec2, IVec2};
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use test_case::test_case;
+
+    #[test_case(1, ivec2(0, -1), 1 ; "forward")]
+    #[test_case(2, ivec2(0, 1), 1 ; "backward")]
+    #[test_case(3, ivec2(0, -1), 2 ; "sprint")]
+    fn r#move(cmd: u32, expected_step: IVec2, expected_len: u8) {
+        let mut map = Map::new(uvec2(3, 3));
+
+        map.fill(TileKind::FLOOR);
+
+        let bots = AliveBots::default();
+        let objects = Objects::default();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut motor = BotMotor::default();
+        let mut action = None;
+        let mut dir = Dir::N;
+
+        let mut ctxt = BotMmioContext {
+            action: &mut action,
+            bots: &bots,
+            dir: &mut dir,
+            map: &map,
+            objects: &objects,
+            pos: ivec2(1, 1),
+            rng: &mut rng,
+            team: None,
+        };
+
+        motor
+            .mmio_store(&mut ctxt, AliveBot::MEM_MOTOR, cmd)
+            .unwrap();
+
+        assert_eq!(Ok(0), motor.mmio_load(AliveBot::MEM_MOTOR));
+
+        match action {
+            Some(BotAction::MotorMove { step, len }) => {
+                assert_eq!(expected_step, step);
+                assert_eq!(expected_len, len);
+            }
+
+            action => panic!("unexpected action: {action:?}"),
+        }
+    }
+
+    #[test]
+    fn water() {
+        let cooldown = |tile: u8| {
+            let mut map = Map::new(uvec2(3, 3));
+
+            map.fill(TileKind::FLOOR);
+            map.set(ivec2(1, 1), tile);
+
+            let bots = AliveBots::default();
+            let objects = Objects::default();
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+            let mut motor = BotMotor::default();
+            let mut action = None;
+            let mut dir = Dir::N;
+
+            let mut ctxt = BotMmioContext {
+                action: &mut action,
+                bots: &bots,
+                dir: &mut dir,
+                map: &map,
+                objects: &objects,
+                pos: ivec2(1, 1),
+                rng: &mut rng,
+                team: None,
+            };
+
+            motor.mmio_store(&mut ctxt, AliveBot::MEM_MOTOR, 1).unwrap();
+
+            let mut ticks = 0;
+
+            while motor.mmio_load(AliveBot::MEM_MOTOR) == Ok(0) {
+                motor.tick();
+                ticks += 1;
+            }
+
+            ticks
+        };
+
+        assert_eq!(
+            cooldown(TileKind::FLOOR) * BotMotor::WATER_SLOWDOWN,
+            cooldown(TileKind::WATER)
+        );
+    }
+
+    #[test]
+    fn status() {
+        let mut motor = BotMotor::default();
+
+        assert_eq!(Ok(0), motor.mmio_load(AliveBot::MEM_MOTOR + 8));
+
+        motor.report(BotMotorStatus::BlockedByObject);
+
+        assert_eq!(Ok(4), motor.mmio_load(AliveBot::MEM_MOTOR + 8));
+
+        motor.report(BotMotorStatus::Moved);
+
+        assert_eq!(Ok(1), motor.mmio_load(AliveBot::MEM_MOTOR + 8));
+    }
+}
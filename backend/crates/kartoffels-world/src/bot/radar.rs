@@ -1,4 +1,4 @@
-use crate::{AliveBot, BotMmioContext, TileKind};
+use crate::{AliveBot, BotAction, BotMmioContext, TileKind};
 use glam::{ivec2, IVec2};
 use serde::{Deserialize, Serialize};
 
@@ -63,9 +63,18 @@ impl BotRadar {
                 let out_z2;
 
                 if let Some(bot_id) = ctxt.bots.lookup_at(pos) {
+                    // Our own bot is not present in `ctxt.bots` while it's
+                    // being ticked, so we have to special-case it
+                    let team = if pos == ctxt.pos {
+                        ctxt.team
+                    } else {
+                        ctxt.bots.get(bot_id).and_then(|bot| bot.team)
+                    };
+
+                    let team = team.map_or(0, |team| team.saturating_add(1));
                     let bot_id = bot_id.get().get();
 
-                    out_z0 = TileKind::BOT as u32;
+                    out_z0 = u32::from_be_bytes([0, 0, team, TileKind::BOT]);
                     out_z1 = (bot_id >> 32) as u32;
                     out_z2 = bot_id as u32;
                 } else if let Some(object) = ctxt.objects.get_at(pos) {
@@ -73,7 +82,13 @@ impl BotRadar {
                     out_z1 = 0;
                     out_z2 = 0;
                 } else {
-                    out_z0 = ctxt.map.get(pos).kind as u32;
+                    let tile = ctxt.map.get(pos);
+
+                    // Tile's state (e.g. whether a door is open) goes into
+                    // the third byte, so that it doesn't get confused with
+                    // bot's team
+                    out_z0 =
+                        u32::from_be_bytes([0, tile.meta[0], 0, tile.kind]);
                     out_z1 = 0;
                     out_z2 = 0;
                 }
@@ -84,6 +99,10 @@ impl BotRadar {
             }
         }
 
+        *ctxt.action = Some(BotAction::RadarScan {
+            range: range.len() as u8,
+        });
+
         self.cooldown = range.cooldown(ctxt);
     }
 }
@@ -363,6 +382,7 @@ mod tests {
             objects: &objects,
             pos: case.pos,
             rng: &mut rng,
+            team: None,
         };
 
         radar
@@ -380,4 +400,108 @@ mod tests {
 
         assert_eq!(case.expected_cooldown, radar.cooldown);
     }
+
+    #[test]
+    fn teams() {
+        let map = {
+            let mut map = Map::new(uvec2(3, 3));
+
+            map.rect(ivec2(0, 0), ivec2(2, 2), TileKind::FLOOR);
+            map
+        };
+
+        let bots = {
+            let mut bots = AliveBots::default();
+
+            bots.add(AliveBot {
+                id: BotId::new(1),
+                pos: ivec2(1, 0),
+                team: Some(3),
+                ..Default::default()
+            });
+
+            bots.add(AliveBot {
+                id: BotId::new(2),
+                pos: ivec2(1, 1),
+                ..Default::default()
+            });
+
+            bots.add(AliveBot {
+                id: BotId::new(3),
+                pos: ivec2(1, 2),
+                ..Default::default()
+            });
+
+            bots
+        };
+
+        let mut radar = BotRadar::default();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut ctxt = BotMmioContext {
+            action: &mut None,
+            bots: &bots,
+            dir: &mut Dir::N,
+            map: &map,
+            objects: &Objects::default(),
+            pos: ivec2(1, 1),
+            rng: &mut rng,
+            team: Some(0),
+        };
+
+        radar.mmio_store(&mut ctxt, AliveBot::MEM_RADAR, 3).unwrap();
+
+        let tile = |x, y| {
+            let addr = BotRadarRange::D3.addr(x, y, 0);
+
+            radar.mmio_load(addr).unwrap().to_be_bytes()
+        };
+
+        assert_eq!([0, 0, 4, b'@'], tile(1, 0));
+        assert_eq!([0, 0, 1, b'@'], tile(1, 1));
+        assert_eq!([0, 0, 0, b'@'], tile(1, 2));
+        assert_eq!([0, 0, 0, b'.'], tile(0, 0));
+    }
+
+    #[test]
+    fn tile_meta() {
+        let map = {
+            let mut map = Map::new(uvec2(3, 3));
+
+            map.rect(ivec2(0, 0), ivec2(2, 2), TileKind::FLOOR);
+            map.set(ivec2(0, 0), TileKind::DOOR);
+            map.set(ivec2(1, 0), TileKind::DOOR);
+            map.set(ivec2(2, 0), TileKind::WALL_CRACKED);
+            map.get_mut(ivec2(1, 0)).meta[0] = 1;
+            map.get_mut(ivec2(2, 0)).meta[0] = 2;
+            map
+        };
+
+        let mut radar = BotRadar::default();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut ctxt = BotMmioContext {
+            action: &mut None,
+            bots: &AliveBots::default(),
+            dir: &mut Dir::N,
+            map: &map,
+            objects: &Objects::default(),
+            pos: ivec2(1, 1),
+            rng: &mut rng,
+            team: None,
+        };
+
+        radar.mmio_store(&mut ctxt, AliveBot::MEM_RADAR, 3).unwrap();
+
+        let tile = |x, y| {
+            let addr = BotRadarRange::D3.addr(x, y, 0);
+
+            radar.mmio_load(addr).unwrap().to_be_bytes()
+        };
+
+        assert_eq!([0, 0, 0, b'+'], tile(0, 0));
+        assert_eq!([0, 1, 0, b'+'], tile(1, 0));
+        assert_eq!([0, 2, 0, b'#'], tile(2, 0));
+        assert_eq!([0, 0, 0, b'.'], tile(1, 1));
+    }
 }
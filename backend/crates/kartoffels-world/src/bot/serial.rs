@@ -1,4 +1,4 @@
-use crate::AliveBot;
+use crate::{AliveBot, BotAction, BotMmioContext};
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 use std::sync::Arc;
@@ -29,7 +29,12 @@ impl BotSerial {
         Err(())
     }
 
-    pub fn mmio_store(&mut self, addr: u32, val: u32) -> Result<(), ()> {
+    pub fn mmio_store(
+        &mut self,
+        ctxt: &mut BotMmioContext,
+        addr: u32,
+        val: u32,
+    ) -> Result<(), ()> {
         match addr {
             AliveBot::MEM_SERIAL => {
                 if self.buffer.len() >= Self::CAPACITY {
@@ -39,6 +44,8 @@ impl BotSerial {
                 self.buffer.push_back(val);
                 self.snapshot = None;
 
+                *ctxt.action = Some(BotAction::SerialWrite { value: val });
+
                 Ok(())
             }
 
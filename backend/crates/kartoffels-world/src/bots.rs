@@ -7,7 +7,7 @@ pub use self::alive::*;
 pub use self::dead::*;
 pub use self::queued::*;
 pub use self::systems::*;
-use crate::{AliveBot, BotId};
+use crate::{AliveBot, BotId, QueuedBot};
 use itertools::Either;
 use serde::{Deserialize, Serialize};
 
@@ -16,6 +16,10 @@ pub struct Bots {
     pub alive: AliveBots,
     pub dead: DeadBots,
     pub queued: QueuedBots,
+
+    /// Bots waiting for the world to get reset (see:
+    /// `GameMode::benches_bots()`)
+    pub benched: Vec<QueuedBot>,
 }
 
 impl Bots {
@@ -29,6 +33,7 @@ impl Bots {
         self.alive.remove(id);
         self.dead.remove(id);
         self.queued.remove(id);
+        self.benched.retain(|bot| bot.id != id);
     }
 }
 
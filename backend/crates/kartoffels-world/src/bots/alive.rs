@@ -7,14 +7,14 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 #[derive(Clone, Debug, Default)]
 pub struct AliveBots {
     entries: Vec<Option<Box<AliveBot>>>,
-    id_to_idx: AHashMap<BotId, u8>,
+    id_to_idx: AHashMap<BotId, u32>,
     pos_to_id: AHashMap<IVec2, BotId>,
 }
 
 impl AliveBots {
     pub fn add(&mut self, bot: AliveBot) {
         for (idx, slot) in self.entries.iter_mut().enumerate() {
-            let idx = idx as u8;
+            let idx = idx as u32;
 
             if slot.is_none() {
                 self.id_to_idx.insert(bot.id, idx);
@@ -26,7 +26,7 @@ impl AliveBots {
         }
 
         let idx =
-            u8::try_from(self.entries.len()).expect("too many alive robots");
+            u32::try_from(self.entries.len()).expect("too many alive robots");
 
         self.id_to_idx.insert(bot.id, idx);
         self.pos_to_id.insert(bot.pos, bot.id);
@@ -42,6 +42,18 @@ impl AliveBots {
         Some(*bot)
     }
 
+    pub fn get(&self, id: BotId) -> Option<&AliveBot> {
+        let idx = *self.id_to_idx.get(&id)?;
+
+        self.entries[idx as usize].as_deref()
+    }
+
+    pub fn get_mut(&mut self, id: BotId) -> Option<&mut AliveBot> {
+        let idx = *self.id_to_idx.get(&id)?;
+
+        self.entries[idx as usize].as_deref_mut()
+    }
+
     pub fn lookup_at(&self, pos: IVec2) -> Option<BotId> {
         self.pos_to_id.get(&pos).copied()
     }
@@ -70,10 +82,26 @@ impl AliveBots {
         }
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = &AliveBot> {
+        self.entries.iter().flatten().map(|bot| &**bot)
+    }
+
     pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut AliveBot> {
         self.entries.iter_mut().flatten().map(|bot| &mut **bot)
     }
 
+    /// Returns the slots, in the order bots get ticked.
+    ///
+    /// Callers mustn't move, add or remove bots through the slots, since bots'
+    /// ids and positions are indexed separately.
+    pub fn slots_mut(&mut self) -> &mut [Option<Box<AliveBot>>] {
+        &mut self.entries
+    }
+
+    pub fn id_at(&self, idx: usize) -> Option<BotId> {
+        self.entries.get(idx)?.as_ref().map(|bot| bot.id)
+    }
+
     pub fn contains(&self, id: BotId) -> bool {
         self.id_to_idx.contains_key(&id)
     }
@@ -85,6 +113,35 @@ impl AliveBots {
     pub fn count(&self) -> usize {
         self.entries.iter().flatten().count()
     }
+
+    /// Returns which slots are occupied.
+    ///
+    /// Serializing skips empty slots, which affects the order in which bots
+    /// get ticked later - keeping the layout around allows to restore it when
+    /// the exact order matters (see: [`Self::restore_layout()`]).
+    pub fn layout(&self) -> Vec<bool> {
+        self.entries.iter().map(|entry| entry.is_some()).collect()
+    }
+
+    pub fn restore_layout(&mut self, layout: &[bool]) {
+        let mut bots = self.entries.drain(..).flatten();
+        let mut entries = Vec::with_capacity(layout.len());
+
+        for occupied in layout {
+            entries.push(if *occupied { bots.next() } else { None });
+        }
+
+        entries.extend(bots.map(Some));
+
+        self.entries = entries;
+        self.id_to_idx.clear();
+
+        for (idx, bot) in self.entries.iter().enumerate() {
+            if let Some(bot) = bot {
+                self.id_to_idx.insert(bot.id, idx as u32);
+            }
+        }
+    }
 }
 
 impl Serialize for AliveBots {
@@ -172,4 +229,36 @@ mod tests {
         assert!(!target.contains(BotId::new(4)));
         assert!(target.contains(BotId::new(5)));
     }
+
+    #[test]
+    fn restore_layout() {
+        let mut target = AliveBots::default();
+
+        target.add(bot(1, ivec2(10, 10)));
+        target.add(bot(2, ivec2(20, 20)));
+        target.add(bot(3, ivec2(30, 30)));
+        target.remove(BotId::new(2));
+
+        let layout = target.layout();
+
+        assert_eq!(vec![true, false, true], layout);
+
+        let mut target: AliveBots = ciborium::Value::serialized(&target)
+            .unwrap()
+            .deserialized()
+            .unwrap();
+
+        assert_eq!(vec![true, true], target.layout());
+
+        target.restore_layout(&layout);
+
+        assert_eq!(layout, target.layout());
+        assert_eq!(BotId::new(3), target.get(BotId::new(3)).unwrap().id);
+
+        // The empty slot gets reused, as it would've been originally
+        target.add(bot(4, ivec2(40, 40)));
+
+        assert_eq!(vec![true, true, true], target.layout());
+        assert_eq!(Some(BotId::new(4)), target.lookup_at(ivec2(40, 40)));
+    }
 }
@@ -6,7 +6,7 @@ use std::collections::VecDeque;
 #[derive(Clone, Debug, Default)]
 pub struct QueuedBots {
     entries: VecDeque<QueuedBot>,
-    index: AHashMap<BotId, u8>,
+    index: AHashMap<BotId, u32>,
 }
 
 impl QueuedBots {
@@ -40,11 +40,15 @@ impl QueuedBots {
         self.index.contains_key(&id)
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = &QueuedBot> {
+        self.entries.iter()
+    }
+
     pub fn iter_mut(&mut self) -> impl Iterator<Item = QueuedBotEntryMut> {
         self.entries.iter_mut().enumerate().map(|(idx, bot)| {
             QueuedBotEntryMut {
                 bot,
-                place: (idx + 1) as u8,
+                place: (idx + 1) as u32,
             }
         })
     }
@@ -53,6 +57,10 @@ impl QueuedBots {
         self.entries.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
     fn reindex(&mut self) {
         self.index.clear();
 
@@ -60,7 +68,7 @@ impl QueuedBots {
             self.entries
                 .iter()
                 .enumerate()
-                .map(|(place, bot)| (bot.id, place as u8)),
+                .map(|(place, bot)| (bot.id, place as u32)),
         );
     }
 }
@@ -95,7 +103,7 @@ impl<'de> Deserialize<'de> for QueuedBots {
 #[derive(Debug)]
 pub struct QueuedBotEntryMut<'a> {
     pub bot: &'a mut QueuedBot,
-    pub place: u8,
+    pub place: u32,
 }
 
 #[cfg(test)]
@@ -112,6 +120,7 @@ mod tests {
             pos: None,
             requeued: false,
             serial: Default::default(),
+            team: None,
         }
     }
 
@@ -1,4 +1,7 @@
+pub mod convey;
 pub mod create;
 pub mod kill;
 pub mod spawn;
 pub mod tick;
+
+pub use self::convey::ConveyTimer;
@@ -0,0 +1,63 @@
+use crate::{AliveBot, BotMotorStatus, KillBot, World};
+use itertools::Either;
+use serde::{Deserialize, Serialize};
+
+/// How often conveyors push bots standing on them, in bot-ticks (~500 ms).
+const PERIOD: u32 = 32_000;
+
+/// Time since conveyors have pushed bots the last time - kept within the
+/// world (instead of being a system's state), so that it gets persisted
+/// together with it.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ConveyTimer {
+    ticks: u32,
+}
+
+pub fn run(world: &mut World) {
+    world.convey.ticks += world.clock.steps();
+
+    while world.convey.ticks >= PERIOD {
+        world.convey.ticks -= PERIOD;
+
+        tick(world);
+    }
+}
+
+fn tick(world: &mut World) {
+    let len = world.bots.alive.len();
+
+    for idx in 0..len {
+        if let Some(bot) = world.bots.alive.take(idx) {
+            let id = bot.id;
+            let pos = bot.pos;
+            let bot = bot_convey(world, bot);
+
+            world.bots.alive.insert(idx, id, pos, bot);
+        }
+    }
+}
+
+fn bot_convey(
+    world: &mut World,
+    mut bot: Box<AliveBot>,
+) -> Option<Box<AliveBot>> {
+    let Some(dir) = world.map.get(bot.pos).conveyor_dir() else {
+        return Some(bot);
+    };
+
+    match super::tick::bot_move(world, &mut bot, dir.as_vec()) {
+        BotMotorStatus::FellIntoVoid => {
+            let kill = KillBot {
+                killed: Either::Right(bot),
+                reason: "fell into the void (conveyor)".into(),
+                killer: None,
+            };
+
+            super::kill::run(world, kill);
+
+            None
+        }
+
+        _ => Some(bot),
+    }
+}
@@ -1,4 +1,6 @@
-use crate::{BotEvents, BotId, CreateBotRequest, QueuedBot, World};
+use crate::{
+    BotEvents, BotId, CreateBotRequest, Event, ModeContext, QueuedBot, World,
+};
 use anyhow::{anyhow, Context, Result};
 use kartoffels_cpu::Firmware;
 use rand::Rng;
@@ -11,6 +13,7 @@ pub fn run(world: &mut World, req: CreateBotRequest) -> Result<BotId> {
         dir,
         instant,
         oneshot,
+        team,
     } = req;
 
     debug!(
@@ -19,9 +22,16 @@ pub fn run(world: &mut World, req: CreateBotRequest) -> Result<BotId> {
         ?dir,
         ?instant,
         ?oneshot,
+        ?team,
         "creating bot",
     );
 
+    // Radar reports teams as `team + 1` (so that zero can mean `no team`),
+    // which leaves no room for the last one
+    if team == Some(u8::MAX) {
+        return Err(anyhow!("team must be less than {}", u8::MAX));
+    }
+
     let events = {
         let mut events = BotEvents::default();
 
@@ -41,6 +51,15 @@ pub fn run(world: &mut World, req: CreateBotRequest) -> Result<BotId> {
     };
 
     let fw = Firmware::from_elf(&src).context("couldn't parse firmware")?;
+    let team = team.or_else(|| {
+        world.mode.team_for(&mut ModeContext {
+            bots: &world.bots,
+            events: &mut world.events,
+            map: &world.map,
+            objects: &mut world.objects,
+            rng: &mut world.rng,
+        })
+    });
 
     let bot = QueuedBot {
         dir,
@@ -51,9 +70,12 @@ pub fn run(world: &mut World, req: CreateBotRequest) -> Result<BotId> {
         pos,
         requeued: false,
         serial: Default::default(),
+        team,
     };
 
     if instant {
+        world.events.add(Event::BotCreated { id });
+
         super::spawn::run_now(world, bot)?;
     } else {
         if world.bots.queued.len() >= world.policy.max_queued_bots {
@@ -62,6 +84,7 @@ pub fn run(world: &mut World, req: CreateBotRequest) -> Result<BotId> {
             ));
         }
 
+        world.events.add(Event::BotCreated { id });
         world.bots.queued.push(bot);
     }
 
@@ -1,4 +1,6 @@
-use crate::{DeadBot, Event, KillBot, QueuedBot, World};
+use crate::{
+    AliveBot, DeadBot, Event, KillBot, ModeBot, ModeContext, QueuedBot, World,
+};
 use itertools::Either;
 use tracing::trace;
 
@@ -32,12 +34,30 @@ pub fn run(world: &mut World, cmd: KillBot) {
         Either::Right(bot) => *bot,
     };
 
-    world.events.add(Event::BotKilled { id: killed.id });
-    world.mode.on_bot_killed(killed.id, killer);
+    world.events.add(Event::BotKilled {
+        id: killed.id,
+        at: killed.pos,
+        killer,
+        reason: reason.clone(),
+    });
+
+    world.mode.on_bot_killed(
+        &mut ModeContext {
+            bots: &world.bots,
+            events: &mut world.events,
+            map: &world.map,
+            objects: &mut world.objects,
+            rng: &mut world.rng,
+        },
+        ModeBot(&killed),
+        killer,
+    );
 
     killed.log(reason);
 
-    let decision = if !killed.oneshot
+    let decision = if !killed.oneshot && world.mode.benches_bots() {
+        Decision::Bench
+    } else if !killed.oneshot
         && world.policy.auto_respawn
         && world.bots.queued.len() < world.policy.max_queued_bots
     {
@@ -50,16 +70,19 @@ pub fn run(world: &mut World, cmd: KillBot) {
         Decision::Queue => {
             killed.log("requeued");
 
-            world.bots.queued.push(QueuedBot {
-                dir: None,
-                events: killed.events,
-                fw: killed.fw,
+            world.bots.queued.push(requeue(killed));
+        }
+
+        Decision::Bench => {
+            killed.log("benched until the next round");
+
+            world.bots.dead.add(DeadBot {
+                events: killed.events.snapshot(),
                 id: killed.id,
-                oneshot: false,
-                pos: None,
-                requeued: true,
-                serial: killed.serial,
+                serial: killed.serial.snapshot(),
             });
+
+            world.bots.benched.push(requeue(killed));
         }
 
         Decision::Discard => {
@@ -72,8 +95,23 @@ pub fn run(world: &mut World, cmd: KillBot) {
     }
 }
 
+fn requeue(bot: AliveBot) -> QueuedBot {
+    QueuedBot {
+        dir: None,
+        events: bot.events,
+        fw: bot.fw,
+        id: bot.id,
+        oneshot: false,
+        pos: None,
+        requeued: true,
+        serial: bot.serial,
+        team: bot.team,
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 enum Decision {
     Queue,
+    Bench,
     Discard,
 }
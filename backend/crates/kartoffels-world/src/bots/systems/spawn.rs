@@ -1,4 +1,7 @@
-use crate::{AliveBot, Bots, Dir, Event, Map, Objects, QueuedBot, World};
+use crate::{
+    AliveBot, Bots, Dir, Event, Map, ModeBot, ModeContext, Objects, QueuedBot,
+    World,
+};
 use anyhow::{anyhow, Context, Result};
 use glam::IVec2;
 use rand::{Rng, RngCore};
@@ -19,6 +22,7 @@ pub fn run(world: &mut World) {
         &world.bots,
         &world.objects,
         world.spawn,
+        &world.spawn_points,
         bot,
     ) else {
         return;
@@ -28,10 +32,7 @@ pub fn run(world: &mut World) {
     let bot = world.bots.queued.pop().unwrap();
     let bot = AliveBot::new(&mut world.rng, pos, dir, bot);
 
-    trace!(id=?bot.id, ?pos, ?dir, "spawning bot");
-
-    world.events.add(Event::BotSpawned { id: bot.id });
-    world.bots.alive.add(bot);
+    spawn(world, bot);
 }
 
 // TODO parts of logic are duplicated with `run()`
@@ -46,18 +47,43 @@ pub fn run_now(world: &mut World, bot: QueuedBot) -> Result<()> {
         &world.bots,
         &world.objects,
         world.spawn,
+        &world.spawn_points,
         &bot,
     )
     .context("couldn't determine spawn point")?;
 
     let bot = AliveBot::new(&mut world.rng, pos, dir, bot);
 
-    trace!(id=?bot.id, ?pos, ?dir, "spawning bot");
+    spawn(world, bot);
+
+    Ok(())
+}
+
+fn spawn(world: &mut World, bot: AliveBot) {
+    let id = bot.id;
 
-    world.events.add(Event::BotSpawned { id: bot.id });
+    trace!(?id, pos=?bot.pos, dir=?bot.dir, "spawning bot");
+
+    world.events.add(Event::BotSpawned {
+        id,
+        at: bot.pos,
+        dir: bot.dir,
+    });
     world.bots.alive.add(bot);
 
-    Ok(())
+    // Unwrap-safety: We've just added this bot
+    let bot = world.bots.alive.get(id).unwrap();
+
+    world.mode.on_bot_spawned(
+        &mut ModeContext {
+            bots: &world.bots,
+            events: &mut world.events,
+            map: &world.map,
+            objects: &mut world.objects,
+            rng: &mut world.rng,
+        },
+        ModeBot(bot),
+    );
 }
 
 fn determine_spawn_point(
@@ -66,6 +92,7 @@ fn determine_spawn_point(
     bots: &Bots,
     objs: &Objects,
     spawn: (Option<IVec2>, Option<Dir>),
+    spawn_points: &[IVec2],
     bot: &QueuedBot,
 ) -> Option<(IVec2, Dir)> {
     if let Some(pos) = bot.pos {
@@ -88,9 +115,32 @@ fn determine_spawn_point(
         };
     }
 
+    if !spawn_points.is_empty() {
+        return sample_spawn_points(rng, map, bots, objs, spawn_points, bot);
+    }
+
     sample_map(rng, map, bots, objs, bot)
 }
 
+fn sample_spawn_points(
+    rng: &mut impl RngCore,
+    map: &Map,
+    bots: &Bots,
+    objs: &Objects,
+    spawn_points: &[IVec2],
+    bot: &QueuedBot,
+) -> Option<(IVec2, Dir)> {
+    let offset = rng.gen_range(0..spawn_points.len());
+
+    let pos = (0..spawn_points.len())
+        .map(|idx| spawn_points[(offset + idx) % spawn_points.len()])
+        .find(|pos| is_pos_legal(map, bots, objs, *pos))?;
+
+    let dir = bot.dir.unwrap_or_else(|| rng.gen());
+
+    Some((pos, dir))
+}
+
 fn sample_map(
     rng: &mut impl RngCore,
     map: &Map,
@@ -1,21 +1,106 @@
-use crate::{AliveBot, BotAction, Event, KillBot, TileKind, World};
+use crate::{
+    AliveBot, BotAction, BotArm, BotId, BotMotorStatus, Combat, Event, KillBot,
+    ModeBot, ModeContext, Tile, TileKind, World,
+};
+use glam::IVec2;
 use itertools::Either;
+use rayon::prelude::*;
 
 pub fn run(world: &mut World) {
-    for _ in 0..world.clock.steps() {
-        tick(world);
+    let steps = world.clock.steps();
+    let mut runs = run_ahead(world, steps);
+
+    for step in 0..steps {
+        tick(world, step, &mut runs);
     }
 }
 
-fn tick(world: &mut World) {
+/// Outcome of ticking a bot ahead of the world, see: [`run_ahead()`].
+#[derive(Debug)]
+struct RunAhead {
+    id: BotId,
+
+    /// Number of steps the bot has already completed
+    steps: u32,
+
+    /// Error the bot has crashed with while performing the next step
+    crash: Option<Box<str>>,
+}
+
+/// Ticks all bots in parallel for as long as they don't access MMIO.
+///
+/// Such ticks depend only on bot's own state, so the results are the same as
+/// if the bots were ticked one-by-one - the only thing to keep in mind is that
+/// the bot might get killed in the meantime, in which case the progress made
+/// here simply gets discarded together with the bot.
+///
+/// Everything that affects the world (actions, crashes) is still applied
+/// sequentially, in the original order, by [`tick()`].
+fn run_ahead(world: &mut World, steps: u32) -> Vec<Option<RunAhead>> {
+    world
+        .bots
+        .alive
+        .slots_mut()
+        .par_iter_mut()
+        .map(|bot| {
+            let bot = bot.as_mut()?;
+
+            let mut run = RunAhead {
+                id: bot.id,
+                steps: 0,
+                crash: None,
+            };
+
+            while run.steps < steps {
+                match bot.tick_isolated() {
+                    Ok(true) => {
+                        run.steps += 1;
+                    }
+
+                    Ok(false) => {
+                        break;
+                    }
+
+                    Err(err) => {
+                        run.crash = Some(err);
+                        break;
+                    }
+                }
+            }
+
+            Some(run)
+        })
+        .collect()
+}
+
+fn tick(world: &mut World, step: u32, runs: &mut [Option<RunAhead>]) {
     let len = world.bots.alive.len();
     let mut idx = 0;
 
     while idx < len {
+        let run = runs.get_mut(idx).and_then(|run| {
+            run.as_mut()
+                .filter(|run| Some(run.id) == world.bots.alive.id_at(idx))
+        });
+
+        let crash = match run {
+            Some(run) if step < run.steps => {
+                idx += 1;
+                continue;
+            }
+
+            Some(run) if step == run.steps => run.crash.take(),
+            _ => None,
+        };
+
         if let Some(bot) = world.bots.alive.take(idx) {
             let id = bot.id;
             let pos = bot.pos;
-            let bot = bot_tick(world, bot);
+
+            let bot = match crash {
+                Some(err) => bot_act(world, bot, Err(err)),
+                None => bot_tick(world, bot),
+            };
 
             world.bots.alive.insert(idx, id, pos, bot);
         }
@@ -28,13 +113,38 @@ fn bot_tick(
     world: &mut World,
     mut bot: Box<AliveBot>,
 ) -> Option<Box<AliveBot>> {
-    match bot.tick(world) {
+    let action = bot.tick(world);
+
+    bot_act(world, bot, action)
+}
+
+fn bot_act(
+    world: &mut World,
+    mut bot: Box<AliveBot>,
+    action: Result<Option<BotAction>, Box<str>>,
+) -> Option<Box<AliveBot>> {
+    match action {
         Ok(Some(BotAction::ArmDrop { at, idx })) => {
             if let Some((id, obj)) = bot.inventory.take(idx) {
                 bot.log(format!("dropped {} at {},{}", obj.name(), at.x, at.y));
 
-                world.events.add(Event::ObjectDropped { id });
+                world
+                    .events
+                    .add(Event::ObjectDropped { id, by: bot.id, at });
                 world.objects.add(id, obj, Some(at));
+
+                world.mode.on_object_dropped(
+                    &mut ModeContext {
+                        bots: &world.bots,
+                        events: &mut world.events,
+                        map: &world.map,
+                        objects: &mut world.objects,
+                        rng: &mut world.rng,
+                    },
+                    ModeBot(&bot),
+                    id,
+                    at,
+                );
             } else {
                 bot.log("dropped nothing");
             }
@@ -44,7 +154,21 @@ fn bot_tick(
             if let Some((id, obj)) = world.objects.remove_at(at) {
                 match bot.inventory.add(id, obj) {
                     Ok(_) => {
-                        world.events.add(Event::ObjectPicked { id });
+                        world
+                            .events
+                            .add(Event::ObjectPicked { id, by: bot.id });
+
+                        world.mode.on_object_picked(
+                            &mut ModeContext {
+                                bots: &world.bots,
+                                events: &mut world.events,
+                                map: &world.map,
+                                objects: &mut world.objects,
+                                rng: &mut world.rng,
+                            },
+                            ModeBot(&bot),
+                            id,
+                        );
 
                         bot.log(format!(
                             "picked {} from {},{}",
@@ -71,52 +195,118 @@ fn bot_tick(
         }
 
         Ok(Some(BotAction::ArmStab { at })) => {
-            if let Some(killed_id) = world.bots.alive.lookup_at(at) {
-                bot.log(format!("killed {killed_id} (knife)"));
+            let hit = world.bots.alive.lookup_at(at);
+
+            world.events.add(Event::BotStabbed {
+                id: bot.id,
+                at,
+                hit,
+            });
 
-                let kill = KillBot {
-                    killed: Either::Left(killed_id),
-                    reason: format!("killed by {} (knife)", bot.id),
-                    killer: Some(bot.id),
+            if let Some(killed_id) = hit {
+                let killed = match world.policy.combat {
+                    Combat::InstantKill => true,
+                    Combat::Hp => bot_damage(world, &mut bot, killed_id),
                 };
 
-                super::kill::run(world, kill);
+                if killed {
+                    bot.log(format!("killed {killed_id} (knife)"));
+
+                    let killed_team = world
+                        .bots
+                        .alive
+                        .get(killed_id)
+                        .and_then(|bot| bot.team);
+
+                    // Killing your teammate doesn't count as a score
+                    let killer = (bot.team.is_none()
+                        || bot.team != killed_team)
+                        .then_some(bot.id);
+
+                    let kill = KillBot {
+                        killed: Either::Left(killed_id),
+                        reason: format!("killed by {} (knife)", bot.id),
+                        killer,
+                    };
+
+                    super::kill::run(world, kill);
+                }
             } else {
-                bot.log("stabbed fresh air");
+                match world.map.get(at).kind {
+                    TileKind::DOOR => bot_toggle_door(world, &mut bot, at),
+                    TileKind::WALL_CRACKED => {
+                        bot_crack_wall(world, &mut bot, at)
+                    }
+                    _ => bot.log("stabbed fresh air"),
+                }
             }
         }
 
-        Ok(Some(BotAction::MotorMove { at })) => match world.map.get(at).kind {
-            TileKind::VOID => {
-                let kill = KillBot {
-                    killed: Either::Right(bot),
-                    reason: "fell into the void".into(),
-                    killer: None,
-                };
+        Ok(Some(BotAction::MotorMove { step, len })) => {
+            for idx in 0..len {
+                let status = bot_move(world, &mut bot, step);
 
-                super::kill::run(world, kill);
+                match status {
+                    BotMotorStatus::Moved => {
+                        bot.motor.report(status);
+                    }
 
-                return None;
-            }
+                    BotMotorStatus::FellIntoVoid => {
+                        let kill = KillBot {
+                            killed: Either::Right(bot),
+                            reason: "fell into the void".into(),
+                            killer: None,
+                        };
 
-            TileKind::FLOOR => {
-                if world.bots.alive.lookup_at(at).is_none()
-                    && world.objects.lookup_at(at).is_none()
-                {
-                    bot.pos = at;
+                        super::kill::run(world, kill);
 
-                    world.events.add(Event::BotMoved { id: bot.id, at });
+                        return None;
+                    }
+
+                    _ => {
+                        // If we've managed to move at least a single tile,
+                        // the firmware should know the bot's changed position
+                        bot.motor.report(if idx > 0 {
+                            BotMotorStatus::MovedPartially
+                        } else {
+                            status
+                        });
+
+                        break;
+                    }
                 }
             }
+        }
 
-            _ => (),
-        },
+        Ok(Some(BotAction::MotorTurn { dir })) => {
+            world.events.add(Event::BotTurned { id: bot.id, dir });
+        }
+
+        Ok(Some(BotAction::RadarScan { range })) => {
+            world.events.add(Event::BotScanned {
+                id: bot.id,
+                at: bot.pos,
+                dir: bot.dir,
+                range,
+            });
+        }
+
+        Ok(Some(BotAction::SerialWrite { value })) => {
+            world
+                .events
+                .add(Event::BotSerialWritten { id: bot.id, value });
+        }
 
         Ok(None) => {
             //
         }
 
         Err(err) => {
+            world.events.add(Event::BotCrashed {
+                id: bot.id,
+                err: err.to_string(),
+            });
+
             let kill = KillBot {
                 killed: Either::Right(bot),
                 reason: format!("firmware crashed: {err}"),
@@ -131,3 +321,643 @@ fn bot_tick(
 
     Some(bot)
 }
+
+fn bot_damage(world: &mut World, bot: &mut AliveBot, target_id: BotId) -> bool {
+    let damage = BotArm::STAB_DAMAGE;
+
+    let Some(target) = world.bots.alive.get_mut(target_id) else {
+        return false;
+    };
+
+    let killed = target.health.damage(damage);
+
+    target.log(format!("took {damage} damage from {} (knife)", bot.id));
+    bot.log(format!("dealt {damage} damage to {target_id} (knife)"));
+
+    world.events.add(Event::BotDamaged {
+        id: target_id,
+        by: bot.id,
+        damage,
+    });
+
+    killed
+}
+
+fn bot_toggle_door(world: &mut World, bot: &mut AliveBot, at: IVec2) {
+    let tile = world.map.get_mut(at);
+
+    if tile.is_open_door() {
+        // Door can't be closed while something's standing in it - note that
+        // there can't be any bot there, because then we'd stab it instead
+        if world.objects.lookup_at(at).is_some() {
+            bot.log(format!("failed to close door at {},{}", at.x, at.y));
+        } else {
+            tile.meta[0] = 0;
+            bot.log(format!("closed door at {},{}", at.x, at.y));
+            world.events.add(Event::TileChanged { at });
+        }
+    } else {
+        tile.meta[0] = 1;
+        bot.log(format!("opened door at {},{}", at.x, at.y));
+        world.events.add(Event::TileChanged { at });
+    }
+}
+
+fn bot_crack_wall(world: &mut World, bot: &mut AliveBot, at: IVec2) {
+    let tile = world.map.get_mut(at);
+
+    tile.meta[0] = tile.meta[0].saturating_add(1);
+
+    if tile.meta[0] >= Tile::CRACKED_WALL_DURABILITY {
+        *tile = Tile::new(TileKind::FLOOR);
+        bot.log(format!("broke wall at {},{}", at.x, at.y));
+    } else {
+        bot.log(format!("cracked wall at {},{}", at.x, at.y));
+    }
+
+    world.events.add(Event::TileChanged { at });
+}
+
+pub fn bot_move(
+    world: &mut World,
+    bot: &mut AliveBot,
+    step: IVec2,
+) -> BotMotorStatus {
+    let from = bot.pos;
+    let at = bot.pos + step;
+
+    let tile = world.map.get(at);
+
+    if tile.is_void() {
+        return BotMotorStatus::FellIntoVoid;
+    }
+
+    if !tile.is_walkable() {
+        return BotMotorStatus::BlockedByWall;
+    }
+
+    if world.bots.alive.lookup_at(at).is_some() {
+        return BotMotorStatus::BlockedByBot;
+    }
+
+    if world.objects.lookup_at(at).is_some() {
+        let obj_at = at + step;
+
+        let can_push = world.map.get(obj_at).is_walkable()
+            && world.bots.alive.lookup_at(obj_at).is_none()
+            && world.objects.lookup_at(obj_at).is_none();
+
+        if !can_push {
+            return BotMotorStatus::BlockedByObject;
+        }
+
+        let (id, obj) = world.objects.remove_at(at).unwrap();
+
+        world.objects.add(id, obj, Some(obj_at));
+    }
+
+    bot.pos = at;
+
+    if let Some(dst) = world.map.teleporter_pair(at)
+        && world.bots.alive.lookup_at(dst).is_none()
+        && world.objects.lookup_at(dst).is_none()
+    {
+        bot.log(format!("teleported to {},{}", dst.x, dst.y));
+        bot.pos = dst;
+    }
+
+    world.events.add(Event::BotMoved {
+        id: bot.id,
+        from,
+        at: bot.pos,
+    });
+
+    world.mode.on_bot_moved(
+        &mut ModeContext {
+            bots: &world.bots,
+            events: &mut world.events,
+            map: &world.map,
+            objects: &mut world.objects,
+            rng: &mut world.rng,
+        },
+        ModeBot(bot),
+        from,
+    );
+
+    BotMotorStatus::Moved
+}
+
+/// Applies given action as if the bot has just performed it, see:
+/// [`World::test()`].
+#[cfg(test)]
+pub fn act(world: &mut World, id: BotId, action: BotAction) {
+    let idx = (0..world.bots.alive.len())
+        .find(|idx| world.bots.alive.id_at(*idx) == Some(id))
+        .unwrap();
+
+    let bot = world.bots.alive.take(idx).unwrap();
+    let pos = bot.pos;
+    let bot = bot_act(world, bot, Ok(Some(action)));
+
+    world.bots.alive.insert(idx, id, pos, bot);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapshots::send;
+    use crate::{BotHealth, Dir, Map, Object, ObjectId, ObjectKind};
+    use glam::ivec2;
+    use itertools::Itertools;
+    use kartoffels_cpu::{Cpu, Firmware};
+
+    fn world(map: &str) -> (World, BotId) {
+        let (map, anchors) = Map::parse(map.trim()).unwrap();
+        let mut world = World::test(map);
+        let id = BotId::new(1);
+
+        world.bots.alive.add(AliveBot {
+            id,
+            pos: anchors.get('a'),
+            dir: Dir::E,
+            ..Default::default()
+        });
+
+        world.map.set(anchors.get('a'), TileKind::FLOOR);
+
+        (world, id)
+    }
+
+    fn status(world: &World, id: BotId) -> BotMotorStatus {
+        world.bots.alive.get(id).unwrap().motor.status()
+    }
+
+    #[test]
+    fn move_backward() {
+        let (mut world, id) = world("|...a.|");
+
+        act(
+            &mut world,
+            id,
+            BotAction::MotorMove {
+                step: ivec2(-1, 0),
+                len: 1,
+            },
+        );
+
+        let bot = world.bots.alive.get(id).unwrap();
+
+        assert_eq!(ivec2(3, 0), bot.pos);
+        assert_eq!(Dir::E, bot.dir);
+        assert_eq!(BotMotorStatus::Moved, status(&world, id));
+    }
+
+    #[test]
+    fn sprint() {
+        let (mut world, id) = world("|a....|");
+
+        act(
+            &mut world,
+            id,
+            BotAction::MotorMove {
+                step: ivec2(1, 0),
+                len: 2,
+            },
+        );
+
+        assert_eq!(ivec2(3, 0), world.bots.alive.get(id).unwrap().pos);
+        assert_eq!(BotMotorStatus::Moved, status(&world, id));
+
+        let moves = world
+            .events
+            .pending()
+            .iter()
+            .filter(|event| matches!(event, Event::BotMoved { .. }))
+            .count();
+
+        assert_eq!(2, moves);
+    }
+
+    #[test]
+    fn sprint_into_wall() {
+        let (mut world, id) = world("|...a.|");
+
+        act(
+            &mut world,
+            id,
+            BotAction::MotorMove {
+                step: ivec2(1, 0),
+                len: 2,
+            },
+        );
+
+        assert_eq!(ivec2(5, 0), world.bots.alive.get(id).unwrap().pos);
+        assert_eq!(BotMotorStatus::MovedPartially, status(&world, id));
+
+        // ---
+
+        act(
+            &mut world,
+            id,
+            BotAction::MotorMove {
+                step: ivec2(1, 0),
+                len: 2,
+            },
+        );
+
+        assert_eq!(ivec2(5, 0), world.bots.alive.get(id).unwrap().pos);
+        assert_eq!(BotMotorStatus::BlockedByWall, status(&world, id));
+    }
+
+    #[test]
+    fn sprint_into_bot() {
+        let (mut world, id) = world("|a...|");
+
+        world.bots.alive.add(AliveBot {
+            id: BotId::new(2),
+            pos: ivec2(3, 0),
+            ..Default::default()
+        });
+
+        act(
+            &mut world,
+            id,
+            BotAction::MotorMove {
+                step: ivec2(1, 0),
+                len: 2,
+            },
+        );
+
+        assert_eq!(ivec2(2, 0), world.bots.alive.get(id).unwrap().pos);
+        assert_eq!(BotMotorStatus::MovedPartially, status(&world, id));
+    }
+
+    #[test]
+    fn move_into_bot() {
+        let (mut world, id) = world("|a..|");
+
+        world.bots.alive.add(AliveBot {
+            id: BotId::new(2),
+            pos: ivec2(2, 0),
+            ..Default::default()
+        });
+
+        act(
+            &mut world,
+            id,
+            BotAction::MotorMove {
+                step: ivec2(1, 0),
+                len: 1,
+            },
+        );
+
+        assert_eq!(ivec2(1, 0), world.bots.alive.get(id).unwrap().pos);
+        assert_eq!(BotMotorStatus::BlockedByBot, status(&world, id));
+    }
+
+    #[test]
+    fn push_object() {
+        let (mut world, id) = world("|a..|");
+        let obj = ObjectId::new(1);
+
+        world
+            .objects
+            .add(obj, Object::new(ObjectKind::GEM), Some(ivec2(2, 0)));
+
+        act(
+            &mut world,
+            id,
+            BotAction::MotorMove {
+                step: ivec2(1, 0),
+                len: 1,
+            },
+        );
+
+        assert_eq!(ivec2(2, 0), world.bots.alive.get(id).unwrap().pos);
+        assert_eq!(Some(obj), world.objects.lookup_at(ivec2(3, 0)));
+        assert_eq!(BotMotorStatus::Moved, status(&world, id));
+
+        // ---
+
+        act(
+            &mut world,
+            id,
+            BotAction::MotorMove {
+                step: ivec2(1, 0),
+                len: 1,
+            },
+        );
+
+        assert_eq!(ivec2(2, 0), world.bots.alive.get(id).unwrap().pos);
+        assert_eq!(Some(obj), world.objects.lookup_at(ivec2(3, 0)));
+        assert_eq!(BotMotorStatus::BlockedByObject, status(&world, id));
+    }
+
+    #[test]
+    fn toggle_door() {
+        let (mut world, id) = world("|a+.|");
+        let stab = || BotAction::ArmStab { at: ivec2(2, 0) };
+        let step = || BotAction::MotorMove {
+            step: ivec2(1, 0),
+            len: 1,
+        };
+
+        act(&mut world, id, step());
+
+        assert_eq!(BotMotorStatus::BlockedByWall, status(&world, id));
+
+        // ---
+
+        act(&mut world, id, stab());
+
+        assert!(world.map.get(ivec2(2, 0)).is_open_door());
+
+        assert!(world
+            .events
+            .pending()
+            .contains(&Event::TileChanged { at: ivec2(2, 0) }));
+
+        act(&mut world, id, step());
+
+        assert_eq!(BotMotorStatus::Moved, status(&world, id));
+        assert_eq!(ivec2(2, 0), world.bots.alive.get(id).unwrap().pos);
+
+        // ---
+
+        act(
+            &mut world,
+            id,
+            BotAction::MotorMove {
+                step: ivec2(-1, 0),
+                len: 1,
+            },
+        );
+
+        act(&mut world, id, stab());
+
+        assert!(!world.map.get(ivec2(2, 0)).is_open_door());
+
+        act(&mut world, id, step());
+
+        assert_eq!(BotMotorStatus::BlockedByWall, status(&world, id));
+    }
+
+    #[test]
+    fn crack_wall() {
+        let (mut world, id) = world("|a##|");
+        let stab = |at| BotAction::ArmStab { at };
+
+        for meta in 1..Tile::CRACKED_WALL_DURABILITY {
+            act(&mut world, id, stab(ivec2(2, 0)));
+
+            assert_eq!(TileKind::WALL_CRACKED, world.map.get(ivec2(2, 0)).kind);
+            assert_eq!(meta, world.map.get(ivec2(2, 0)).meta[0]);
+        }
+
+        act(&mut world, id, stab(ivec2(2, 0)));
+
+        assert_eq!(TileKind::FLOOR, world.map.get(ivec2(2, 0)).kind);
+
+        // ---
+
+        // Walls with out-of-range durability (e.g. coming from a hand-made
+        // map) break on the next stab
+        world.map.get_mut(ivec2(3, 0)).meta[0] = u8::MAX;
+
+        act(&mut world, id, stab(ivec2(3, 0)));
+
+        assert_eq!(TileKind::FLOOR, world.map.get(ivec2(3, 0)).kind);
+    }
+
+    #[test]
+    fn stab_with_hp() {
+        let (mut world, id) = world("|a..|");
+        let target = BotId::new(2);
+
+        world.policy.combat = Combat::Hp;
+
+        world.bots.alive.add(AliveBot {
+            id: target,
+            pos: ivec2(2, 0),
+            ..Default::default()
+        });
+
+        act(&mut world, id, BotAction::ArmStab { at: ivec2(2, 0) });
+
+        let hp = BotHealth::MAX_HP - BotArm::STAB_DAMAGE;
+
+        assert_eq!(hp, world.bots.alive.get(target).unwrap().health.hp());
+
+        assert_eq!(
+            &[
+                Event::BotStabbed {
+                    id,
+                    at: ivec2(2, 0),
+                    hit: Some(target),
+                },
+                Event::BotDamaged {
+                    id: target,
+                    by: id,
+                    damage: BotArm::STAB_DAMAGE,
+                },
+            ],
+            world.events.pending(),
+        );
+
+        send::run_now(&mut world, &mut Default::default());
+
+        let snapshot = world.snapshots.borrow().clone();
+
+        assert_eq!(hp, snapshot.bots().alive().get(target).unwrap().hp);
+        assert_eq!(
+            BotHealth::MAX_HP,
+            snapshot.bots().alive().get(id).unwrap().hp
+        );
+
+        // ---
+
+        act(&mut world, id, BotAction::ArmStab { at: ivec2(2, 0) });
+        act(&mut world, id, BotAction::ArmStab { at: ivec2(2, 0) });
+
+        assert!(!world.bots.alive.contains(target));
+
+        let killed = world.events.pending().iter().any(|event| {
+            matches!(
+                event,
+                Event::BotKilled { id: killed, killer: Some(killer), .. }
+                    if *killed == target && *killer == id
+            )
+        });
+
+        assert!(killed);
+    }
+
+    /// Runs a couple of bots (a killer, its victim, a bot pushing an object
+    /// and a bot that crashes) through both parallel and sequential ticking,
+    /// making sure both produce the same world.
+    #[test]
+    fn parallel_ticks() {
+        let mut world_par = parallel_world();
+        let mut world_seq = parallel_world();
+        let steps = world_par.clock.steps();
+
+        for _ in 0..3 {
+            run(&mut world_par);
+
+            for step in 0..steps {
+                tick(&mut world_seq, step, &mut []);
+            }
+
+            assert_eq!(parallel_state(&world_seq), parallel_state(&world_par));
+        }
+
+        let events = world_par.events.pending();
+
+        assert!(events.iter().any(|event| matches!(
+            event,
+            Event::BotKilled { id, killer: Some(killer), .. }
+                if *id == BotId::new(2) && *killer == BotId::new(1)
+        )));
+
+        assert!(events.iter().any(|event| matches!(
+            event,
+            Event::BotCrashed { id, .. } if *id == BotId::new(5)
+        )));
+
+        assert_eq!(
+            Some(ObjectId::new(1)),
+            world_par.objects.lookup_at(ivec2(8, 0))
+        );
+    }
+
+    fn parallel_world() -> World {
+        // Each bot computes for a while (without touching MMIO) and then
+        // performs a single instruction - a store into given peripheral or
+        // an invalid instruction, for the crasher
+        let bots = [
+            (1, 1, 700, sw(7, 6, 0)),     // stabs (arm)
+            (2, 2, 900, sw(5, 6, -2048)), // writes to serial
+            (3, 4, 800, sw(5, 6, -2048)), // writes to serial
+            (4, 6, 300, sw(7, 6, -1024)), // moves forward (motor)
+            (5, 12, 500, 0),              // crashes
+        ];
+
+        let (map, _) = Map::parse("|.............|").unwrap();
+        let mut world = World::test(map);
+
+        world.policy.combat = Combat::InstantKill;
+
+        for (id, x, steps, instr) in bots {
+            let fw = firmware(&[
+                lui(6, 0x8001), // x6 = MMIO_BASE + 4096 (arm)
+                addi(7, 0, 1),
+                addi(8, 0, steps),
+                addi(5, 5, 1),
+                bne(5, 8, -4),
+                instr,
+                addi(5, 0, 0),
+                jal(0, -16),
+            ]);
+
+            world.bots.alive.add(AliveBot {
+                id: BotId::new(id),
+                pos: ivec2(x, 0),
+                dir: Dir::E,
+                cpu: Cpu::new(&fw),
+                fw,
+                ..Default::default()
+            });
+        }
+
+        world.objects.add(
+            ObjectId::new(1),
+            Object::new(ObjectKind::GEM),
+            Some(ivec2(7, 0)),
+        );
+
+        world
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn parallel_state(
+        world: &World,
+    ) -> (
+        Vec<(BotId, IVec2, Dir, u64, [i64; 32])>,
+        Vec<Event>,
+        Vec<(ObjectId, Option<IVec2>)>,
+    ) {
+        let bots = world
+            .bots
+            .alive
+            .iter()
+            .map(|bot| {
+                (bot.id, bot.pos, bot.dir, bot.cpu.pc(), *bot.cpu.regs())
+            })
+            .collect();
+
+        let events = world.events.pending().to_vec();
+
+        let objects = world
+            .objects
+            .iter()
+            .map(|obj| (obj.id, obj.pos))
+            .sorted_by_key(|(id, _)| *id)
+            .collect();
+
+        (bots, events, objects)
+    }
+
+    fn firmware(code: &[u32]) -> Firmware {
+        let data: Vec<_> =
+            code.iter().flat_map(|instr| instr.to_le_bytes()).collect();
+
+        serde_json::from_value(serde_json::json!({
+            "segments": [{ "addr": 0, "data": data }],
+            "entry_pc": 0x00100000,
+        }))
+        .unwrap()
+    }
+
+    fn lui(rd: u32, imm: u32) -> u32 {
+        (imm << 12) | (rd << 7) | 0b0110111
+    }
+
+    fn addi(rd: u32, rs1: u32, imm: i32) -> u32 {
+        ((imm as u32 & 0xfff) << 20) | (rs1 << 15) | (rd << 7) | 0b0010011
+    }
+
+    fn sw(rs2: u32, rs1: u32, imm: i32) -> u32 {
+        let imm = imm as u32;
+
+        (((imm >> 5) & 0x7f) << 25)
+            | (rs2 << 20)
+            | (rs1 << 15)
+            | (0b010 << 12)
+            | ((imm & 0x1f) << 7)
+            | 0b0100011
+    }
+
+    fn bne(rs1: u32, rs2: u32, imm: i32) -> u32 {
+        let imm = imm as u32;
+
+        (((imm >> 12) & 1) << 31)
+            | (((imm >> 5) & 0x3f) << 25)
+            | (rs2 << 20)
+            | (rs1 << 15)
+            | (0b001 << 12)
+            | (((imm >> 1) & 0xf) << 8)
+            | (((imm >> 11) & 1) << 7)
+            | 0b1100011
+    }
+
+    fn jal(rd: u32, imm: i32) -> u32 {
+        let imm = imm as u32;
+
+        (((imm >> 20) & 1) << 31)
+            | (((imm >> 1) & 0x3ff) << 21)
+            | (((imm >> 11) & 1) << 20)
+            | (((imm >> 12) & 0xff) << 12)
+            | (rd << 7)
+            | 0b1101111
+    }
+}
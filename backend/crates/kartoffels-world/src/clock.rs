@@ -1,24 +1,32 @@
 mod metronome;
 
 pub use self::metronome::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
 pub enum Clock {
     /// Simulates at 64k bot-ticks per second
     #[default]
+    #[serde(rename = "normal")]
     Normal,
 
     /// Simulates 128k bot-ticks per second
+    #[serde(rename = "fast")]
     Fast,
 
     /// Simulates 256k bot-ticks per second
+    #[serde(rename = "faster")]
     Faster,
 
     /// Simulates as many ticks per second as the server can do
+    #[serde(rename = "unlimited")]
     Unlimited,
 
     /// Manual clock, requires calling [`Handle::tick()`] for world to progress;
     /// useful for testing.
+    #[serde(rename = "manual")]
     Manual,
 }
 
@@ -1,16 +1,21 @@
 use crate::{Clock, Mode, Policy, Theme};
-use rand::rngs::SmallRng;
 use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
 use std::path::PathBuf;
 
 #[derive(Clone, Debug, Default)]
 pub struct Config {
+    /// Whether to keep periodic checkpoints of the world in memory, allowing
+    /// to rewind it (see: [`Handle::rewind()`]).
+    ///
+    /// [`Handle::rewind()`]: crate::Handle::rewind
+    pub checkpoints: bool,
     pub clock: Clock,
     pub events: bool,
     pub mode: Mode,
     pub name: String,
     pub path: Option<PathBuf>,
     pub policy: Policy,
-    pub seed: Option<<SmallRng as SeedableRng>::Seed>,
+    pub seed: Option<<ChaCha8Rng as SeedableRng>::Seed>,
     pub theme: Option<Theme>,
 }
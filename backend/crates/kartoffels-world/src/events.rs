@@ -1,53 +1,251 @@
+mod filter;
+mod log;
 mod stream;
 
+pub use self::filter::*;
+pub use self::log::*;
 pub use self::stream::*;
-use crate::{BotId, ObjectId};
+use crate::{BotId, Dir, ObjectId};
+use anyhow::{Context, Error, Result};
 use glam::IVec2;
-use tokio::sync::broadcast;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::warn;
 
 #[derive(Debug)]
 pub struct Events {
-    tx: Option<broadcast::Sender<EventLetter>>,
+    subscribers: Option<Arc<EventSubscribers>>,
+    log: Option<EventLog>,
     pending: Vec<Event>,
 }
 
 impl Events {
-    pub fn new(tx: Option<broadcast::Sender<EventLetter>>) -> Self {
+    pub fn new(subscribers: Option<Arc<EventSubscribers>>) -> Self {
         Self {
-            tx,
+            subscribers,
+            log: None,
             pending: Default::default(),
         }
     }
 
+    pub fn is_enabled(&self) -> bool {
+        self.subscribers.is_some() || self.log.is_some()
+    }
+
+    pub fn is_streamed(&self) -> bool {
+        self.subscribers.is_some()
+    }
+
+    pub fn set_log(&mut self, log: EventLog) {
+        self.log = Some(log);
+    }
+
+    /// Closes the log (if any), waiting for all of its pending writes.
+    pub fn close_log(&mut self) {
+        if let Some(log) = self.log.take() {
+            log.close();
+        }
+    }
+
     pub fn add(&mut self, event: Event) {
-        if self.tx.is_none() {
+        if !self.is_enabled() {
             return;
         }
 
         self.pending.push(event);
     }
 
+    /// Returns events added since the last call to [`Self::send()`].
+    #[cfg(test)]
+    pub fn pending(&self) -> &[Event] {
+        &self.pending
+    }
+
     pub fn send(&mut self, version: u64) {
-        let Some(tx) = &self.tx else {
-            return;
-        };
+        let letters: Vec<_> = self
+            .pending
+            .drain(..)
+            .map(|event| EventLetter { event, version })
+            .collect();
 
-        for event in self.pending.drain(..) {
-            _ = tx.send(EventLetter { event, version });
+        if let Some(log) = &mut self.log
+            && let Err(err) = log.write(&letters)
+        {
+            warn!("couldn't write to event log, closing it: {err:?}");
+            self.log = None;
+        }
+
+        if let Some(subscribers) = &self.subscribers {
+            subscribers.send(&letters);
         }
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
 pub enum Event {
-    BotSpawned { id: BotId },
-    BotKilled { id: BotId },
-    BotMoved { id: BotId, at: IVec2 },
-    ObjectPicked { id: ObjectId },
-    ObjectDropped { id: ObjectId },
+    BotCreated {
+        id: BotId,
+    },
+    BotSpawned {
+        id: BotId,
+        at: IVec2,
+        dir: Dir,
+    },
+    BotKilled {
+        id: BotId,
+        at: IVec2,
+        killer: Option<BotId>,
+        reason: String,
+    },
+    BotCrashed {
+        id: BotId,
+        err: String,
+    },
+    BotDamaged {
+        id: BotId,
+        by: BotId,
+        damage: u32,
+    },
+    BotMoved {
+        id: BotId,
+        from: IVec2,
+        at: IVec2,
+    },
+    BotTurned {
+        id: BotId,
+        dir: Dir,
+    },
+    BotStabbed {
+        id: BotId,
+        at: IVec2,
+
+        /// Bot that got stabbed, if any
+        hit: Option<BotId>,
+    },
+    BotScanned {
+        id: BotId,
+        at: IVec2,
+        dir: Dir,
+        range: u8,
+    },
+    BotSerialWritten {
+        id: BotId,
+        value: u32,
+    },
+    ObjectCreated {
+        id: ObjectId,
+        at: Option<IVec2>,
+    },
+    ObjectDeleted {
+        id: ObjectId,
+    },
+    ObjectPicked {
+        id: ObjectId,
+        by: BotId,
+    },
+    ObjectDropped {
+        id: ObjectId,
+        by: BotId,
+        at: IVec2,
+    },
+    TileChanged {
+        at: IVec2,
+    },
+    MapChanged,
+    ScoreChanged {
+        id: BotId,
+        score: u32,
+    },
+}
+
+impl Event {
+    pub fn kind(&self) -> EventKind {
+        match self {
+            Event::BotCreated { .. } => EventKind::BotCreated,
+            Event::BotSpawned { .. } => EventKind::BotSpawned,
+            Event::BotKilled { .. } => EventKind::BotKilled,
+            Event::BotCrashed { .. } => EventKind::BotCrashed,
+            Event::BotDamaged { .. } => EventKind::BotDamaged,
+            Event::BotMoved { .. } => EventKind::BotMoved,
+            Event::BotTurned { .. } => EventKind::BotTurned,
+            Event::BotStabbed { .. } => EventKind::BotStabbed,
+            Event::BotScanned { .. } => EventKind::BotScanned,
+            Event::BotSerialWritten { .. } => EventKind::BotSerialWritten,
+            Event::ObjectCreated { .. } => EventKind::ObjectCreated,
+            Event::ObjectDeleted { .. } => EventKind::ObjectDeleted,
+            Event::ObjectPicked { .. } => EventKind::ObjectPicked,
+            Event::ObjectDropped { .. } => EventKind::ObjectDropped,
+            Event::TileChanged { .. } => EventKind::TileChanged,
+            Event::MapChanged => EventKind::MapChanged,
+            Event::ScoreChanged { .. } => EventKind::ScoreChanged,
+        }
+    }
+
+    /// Returns whether given bot takes part in this event, either as the
+    /// subject (e.g. the bot that got killed) or the other side (e.g. the
+    /// killer).
+    pub fn involves(&self, bot: BotId) -> bool {
+        let (id, other) = match self {
+            Event::BotCreated { id }
+            | Event::BotSpawned { id, .. }
+            | Event::BotCrashed { id, .. }
+            | Event::BotMoved { id, .. }
+            | Event::BotTurned { id, .. }
+            | Event::BotScanned { id, .. }
+            | Event::BotSerialWritten { id, .. }
+            | Event::ScoreChanged { id, .. } => (Some(*id), None),
+
+            Event::BotKilled { id, killer, .. } => (Some(*id), *killer),
+            Event::BotDamaged { id, by, .. } => (Some(*id), Some(*by)),
+            Event::BotStabbed { id, hit, .. } => (Some(*id), *hit),
+
+            Event::ObjectPicked { by, .. }
+            | Event::ObjectDropped { by, .. } => (Some(*by), None),
+
+            Event::ObjectCreated { .. }
+            | Event::ObjectDeleted { .. }
+            | Event::TileChanged { .. }
+            | Event::MapChanged => (None, None),
+        };
+
+        id == Some(bot) || other == Some(bot)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EventKind {
+    BotCreated,
+    BotSpawned,
+    BotKilled,
+    BotCrashed,
+    BotDamaged,
+    BotMoved,
+    BotTurned,
+    BotStabbed,
+    BotScanned,
+    BotSerialWritten,
+    ObjectCreated,
+    ObjectDeleted,
+    ObjectPicked,
+    ObjectDropped,
+    TileChanged,
+    MapChanged,
+    ScoreChanged,
+}
+
+impl FromStr for EventKind {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        serde_json::from_value(s.into())
+            .with_context(|| format!("unknown event kind: {s}"))
+    }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub struct EventLetter {
     pub event: Event,
     pub version: u64,
@@ -0,0 +1,67 @@
+use super::{Event, EventKind};
+use crate::BotId;
+use ahash::AHashSet;
+
+/// Describes which events a subscriber is interested in, see:
+/// [`crate::Handle::events()`].
+///
+/// Default filter lets through everything.
+#[derive(Clone, Debug, Default)]
+pub struct EventFilter {
+    kinds: Option<AHashSet<EventKind>>,
+    bots: Option<AHashSet<BotId>>,
+}
+
+impl EventFilter {
+    /// Lets through everything.
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// Lets through only events related to bots' lifecycle - uploads, spawns
+    /// and kills.
+    pub fn lifecycle() -> Self {
+        Self::only([
+            EventKind::BotCreated,
+            EventKind::BotSpawned,
+            EventKind::BotKilled,
+        ])
+    }
+
+    /// Lets through only events of given kinds.
+    pub fn only(kinds: impl IntoIterator<Item = EventKind>) -> Self {
+        Self::default().with_kinds(kinds)
+    }
+
+    pub fn with_kinds(
+        mut self,
+        kinds: impl IntoIterator<Item = EventKind>,
+    ) -> Self {
+        self.kinds.get_or_insert_default().extend(kinds);
+        self
+    }
+
+    /// Lets through only events involving given bot, see: [`Event::involves()`].
+    ///
+    /// Can be called many times to watch many bots at once.
+    pub fn with_bot(mut self, id: BotId) -> Self {
+        self.bots.get_or_insert_default().insert(id);
+        self
+    }
+
+    pub fn matches(&self, event: &Event) -> bool {
+        if let Some(kinds) = &self.kinds
+            && !kinds.contains(&event.kind())
+        {
+            return false;
+        }
+
+        if let Some(bots) = &self.bots
+            && !bots.iter().any(|id| event.involves(*id))
+        {
+            return false;
+        }
+
+        true
+    }
+}
@@ -0,0 +1,282 @@
+use super::{Event, EventFilter, EventLetter};
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+use tokio::{runtime, task};
+use tracing::{info, warn, Span};
+
+/// Append-only log of world's events, stored next to the world's file as
+/// JSONL (see: [`crate::Handle::start_event_log()`]).
+///
+/// Only events matching the log's filter get stored and the I/O itself
+/// happens on a thread pool, so that a slow disk doesn't stall the world.
+///
+/// Once the log grows over [`EventLog::MAX_SIZE`], it gets rotated - the
+/// current file becomes `*.events.1`, the previous `*.events.1` becomes
+/// `*.events.2` and so on, up to [`EventLog::MAX_FILES`].
+#[derive(Debug)]
+pub struct EventLog {
+    filter: EventFilter,
+    tx: Option<mpsc::SyncSender<Vec<EventLogEntry>>>,
+    task: Option<task::JoinHandle<()>>,
+}
+
+impl EventLog {
+    pub const MAX_SIZE: u64 = 32 * 1024 * 1024;
+    pub const MAX_FILES: usize = 4;
+
+    /// How many batches of events can wait for the I/O before we start
+    /// dropping them
+    const CAPACITY: usize = 1024;
+
+    pub fn open(path: impl Into<PathBuf>, filter: EventFilter) -> Result<Self> {
+        let writer = EventLogWriter::open(path.into())?;
+        let (tx, rx) = mpsc::sync_channel(Self::CAPACITY);
+
+        let task = task::spawn_blocking({
+            let span = Span::current();
+
+            move || span.in_scope(|| writer.run(rx))
+        });
+
+        Ok(Self {
+            filter,
+            tx: Some(tx),
+            task: Some(task),
+        })
+    }
+
+    /// Sends matching events to the log, returning an error if the log has
+    /// been closed in the meantime (e.g. due to an I/O error).
+    pub fn write(&mut self, letters: &[EventLetter]) -> Result<()> {
+        let at = Utc::now();
+
+        let entries: Vec<_> = letters
+            .iter()
+            .filter(|letter| self.filter.matches(&letter.event))
+            .map(|letter| EventLogEntry {
+                at,
+                version: letter.version,
+                event: letter.event.clone(),
+            })
+            .collect();
+
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        let tx = self.tx.as_ref().context("log is closed")?;
+
+        match tx.try_send(entries) {
+            Ok(()) => Ok(()),
+
+            Err(mpsc::TrySendError::Full(entries)) => {
+                warn!(
+                    "event log is lagging behind, dropping {} events",
+                    entries.len()
+                );
+                Ok(())
+            }
+
+            Err(mpsc::TrySendError::Disconnected(_)) => {
+                Err(anyhow!("log has been closed"))
+            }
+        }
+    }
+
+    /// Closes the log, waiting until all of the pending events are written.
+    pub fn close(mut self) {
+        self.tx = None;
+
+        if let Some(task) = self.task.take() {
+            _ = runtime::Handle::current().block_on(task);
+        }
+    }
+
+    /// Returns paths of all files that make up given log, from the newest to
+    /// the oldest one - note that not all of them have to exist.
+    pub fn paths(path: &Path) -> Vec<PathBuf> {
+        let mut paths = vec![path.to_owned()];
+
+        for idx in 1..Self::MAX_FILES {
+            let mut rotated = path.as_os_str().to_owned();
+
+            rotated.push(format!(".{idx}"));
+            paths.push(rotated.into());
+        }
+
+        paths
+    }
+
+    /// Reads all entries from given log, including the rotated files, from
+    /// the oldest to the newest one.
+    pub fn read(
+        path: &Path,
+    ) -> Result<impl Iterator<Item = Result<EventLogEntry>>> {
+        let mut files = Vec::new();
+
+        for path in Self::paths(path).into_iter().rev() {
+            if !path.exists() {
+                continue;
+            }
+
+            let file = File::open(&path)
+                .with_context(|| format!("couldn't open {}", path.display()))?;
+
+            files.push((path, BufReader::new(file)));
+        }
+
+        Ok(files.into_iter().flat_map(|(path, file)| {
+            file.lines().enumerate().map(move |(idx, line)| {
+                let line = line?;
+
+                serde_json::from_str(&line).with_context(|| {
+                    format!(
+                        "couldn't parse {}, line {}",
+                        path.display(),
+                        idx + 1
+                    )
+                })
+            })
+        }))
+    }
+}
+
+/// Part of [`EventLog`] that does the actual I/O, running on a thread pool.
+#[derive(Debug)]
+struct EventLogWriter {
+    path: PathBuf,
+    file: BufWriter<File>,
+    size: u64,
+    flushed_at: Instant,
+}
+
+impl EventLogWriter {
+    const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+    fn open(path: PathBuf) -> Result<Self> {
+        let file = Self::open_file(&path)?;
+
+        let size = file
+            .metadata()
+            .with_context(|| format!("couldn't stat {}", path.display()))?
+            .len();
+
+        info!("logging events into {}", path.display());
+
+        Ok(Self {
+            path,
+            file: BufWriter::new(file),
+            size,
+            flushed_at: Instant::now(),
+        })
+    }
+
+    fn run(mut self, rx: mpsc::Receiver<Vec<EventLogEntry>>) {
+        loop {
+            match rx.recv_timeout(Self::FLUSH_INTERVAL) {
+                Ok(entries) => {
+                    for entry in entries {
+                        if let Err(err) = self.write(&entry) {
+                            warn!("couldn't write to event log, closing it: {err:?}");
+                            return;
+                        }
+                    }
+                }
+
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    //
+                }
+
+                Err(mpsc::RecvTimeoutError::Disconnected) => {
+                    return;
+                }
+            }
+
+            if let Err(err) = self.flush() {
+                warn!("couldn't flush event log, closing it: {err:?}");
+                return;
+            }
+        }
+    }
+
+    fn write(&mut self, entry: &EventLogEntry) -> Result<()> {
+        let mut line = serde_json::to_vec(entry)?;
+
+        line.push(b'\n');
+
+        if self.size > 0 && self.size + line.len() as u64 > EventLog::MAX_SIZE {
+            self.rotate()?;
+        }
+
+        self.file.write_all(&line)?;
+        self.size += line.len() as u64;
+
+        Ok(())
+    }
+
+    /// Flushes the log, but not more often than once in a while - so that
+    /// the events land on the disk reasonably soon, without hitting it on
+    /// every batch.
+    fn flush(&mut self) -> Result<()> {
+        if self.flushed_at.elapsed() < Self::FLUSH_INTERVAL {
+            return Ok(());
+        }
+
+        self.file.flush()?;
+        self.flushed_at = Instant::now();
+
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> Result<()> {
+        self.file.flush()?;
+
+        let paths = EventLog::paths(&self.path);
+
+        for (src, dst) in paths.iter().zip(&paths[1..]).rev() {
+            if src.exists() {
+                fs::rename(src, dst).with_context(|| {
+                    format!(
+                        "couldn't rename {} to {}",
+                        src.display(),
+                        dst.display()
+                    )
+                })?;
+            }
+        }
+
+        self.file = BufWriter::new(Self::open_file(&self.path)?);
+        self.size = 0;
+
+        Ok(())
+    }
+
+    fn open_file(path: &Path) -> Result<File> {
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("couldn't open {}", path.display()))
+    }
+}
+
+impl Drop for EventLogWriter {
+    fn drop(&mut self) {
+        if let Err(err) = self.file.flush() {
+            warn!("couldn't flush event log: {err:?}");
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventLogEntry {
+    pub at: DateTime<Utc>,
+    pub version: u64,
+    pub event: Event,
+}
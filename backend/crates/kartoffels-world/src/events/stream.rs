@@ -1,12 +1,60 @@
-use super::{Event, EventLetter};
-use crate::{BotId, Handle};
+use super::{Event, EventFilter, EventLetter};
+use crate::{cfg, BotId, Handle};
 use anyhow::{Context, Result};
+use std::sync::Mutex;
 use tokio::sync::broadcast;
 use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
 use tokio_stream::wrappers::BroadcastStream;
 use tokio_stream::StreamExt;
 use tracing::warn;
 
+/// Subscribers of world's events, shared between the world (which sends the
+/// events) and its handles (which subscribe to them).
+///
+/// Each subscriber gets its own channel and events get filtered before being
+/// enqueued there, so that a subscriber interested only in, say, kills doesn't
+/// lag behind just because there's lots of bots moving around.
+#[derive(Debug, Default)]
+pub struct EventSubscribers {
+    entries: Mutex<Vec<EventSubscriber>>,
+}
+
+impl EventSubscribers {
+    pub fn subscribe(&self, filter: EventFilter) -> EventStream {
+        let (tx, rx) = broadcast::channel(cfg::EVENT_STREAM_CAPACITY);
+
+        self.entries
+            .lock()
+            .unwrap()
+            .push(EventSubscriber { filter, tx });
+
+        EventStream {
+            stream: BroadcastStream::new(rx),
+            pending: None,
+        }
+    }
+
+    pub fn send(&self, letters: &[EventLetter]) {
+        let mut entries = self.entries.lock().unwrap();
+
+        entries.retain(|entry| entry.tx.receiver_count() > 0);
+
+        for entry in entries.iter() {
+            for letter in letters {
+                if entry.filter.matches(&letter.event) {
+                    _ = entry.tx.send(letter.clone());
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct EventSubscriber {
+    filter: EventFilter,
+    tx: broadcast::Sender<EventLetter>,
+}
+
 #[derive(Debug)]
 pub struct EventStream {
     stream: BroadcastStream<EventLetter>,
@@ -14,13 +62,6 @@ pub struct EventStream {
 }
 
 impl EventStream {
-    pub(crate) fn new(tx: &broadcast::Sender<EventLetter>) -> Self {
-        Self {
-            stream: BroadcastStream::new(tx.subscribe()),
-            pending: None,
-        }
-    }
-
     pub async fn next(&mut self) -> Result<EventLetter> {
         if let Some(event) = self.pending.take() {
             return Ok(event);
@@ -55,7 +96,7 @@ impl EventStream {
 
     pub async fn next_spawned_bot(&mut self) -> Result<BotId> {
         loop {
-            if let Event::BotSpawned { id } = self.next().await?.event {
+            if let Event::BotSpawned { id, .. } = self.next().await?.event {
                 return Ok(id);
             }
         }
@@ -63,9 +104,63 @@ impl EventStream {
 
     pub async fn next_killed_bot(&mut self) -> Result<BotId> {
         loop {
-            if let Event::BotKilled { id } = self.next().await?.event {
+            if let Event::BotKilled { id, .. } = self.next().await?.event {
                 return Ok(id);
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EventKind;
+    use futures_util::FutureExt;
+    use glam::ivec2;
+
+    #[test]
+    fn subscribers() {
+        let subscribers = EventSubscribers::default();
+
+        let mut stream =
+            subscribers.subscribe(EventFilter::only([EventKind::BotKilled]));
+
+        let moved = EventLetter {
+            event: Event::BotMoved {
+                id: BotId::new(1),
+                from: ivec2(1, 0),
+                at: ivec2(2, 0),
+            },
+            version: 1,
+        };
+
+        let killed = EventLetter {
+            event: Event::BotKilled {
+                id: BotId::new(1),
+                at: ivec2(2, 0),
+                killer: None,
+                reason: "fell into the void".into(),
+            },
+            version: 2,
+        };
+
+        // Events the subscriber isn't interested in never reach its channel,
+        // so they can't make it lag
+        let moves = vec![moved; 2 * cfg::EVENT_STREAM_CAPACITY];
+
+        subscribers.send(&moves);
+        subscribers.send(&[killed.clone()]);
+
+        let actual = stream.next().now_or_never().unwrap().unwrap();
+
+        assert_eq!(killed.event, actual.event);
+        assert_eq!(killed.version, actual.version);
+
+        // ---
+
+        drop(stream);
+        subscribers.send(&moves);
+
+        assert!(subscribers.entries.lock().unwrap().is_empty());
+    }
+}
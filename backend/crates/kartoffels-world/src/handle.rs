@@ -2,16 +2,18 @@ mod systems;
 
 pub use self::systems::*;
 use crate::{
-    BotId, Clock, Dir, EventLetter, EventStream, Map, Object, ObjectId,
-    Snapshot, SnapshotStream,
+    BotId, Clock, Dir, EventFilter, EventStream, EventSubscribers, Map, Object,
+    ObjectId, Replay, Snapshot, SnapshotStream,
 };
 use anyhow::{anyhow, Context, Result};
 use derivative::Derivative;
 use glam::IVec2;
 use kartoffels_utils::Id;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_bytes::ByteBuf;
 use std::borrow::Cow;
 use std::sync::Arc;
-use tokio::sync::{broadcast, mpsc, oneshot, watch, OwnedSemaphorePermit};
+use tokio::sync::{mpsc, oneshot, watch, OwnedSemaphorePermit};
 
 #[derive(Clone, Debug)]
 pub struct Handle {
@@ -30,14 +32,14 @@ impl Handle {
         &self.shared.name
     }
 
-    pub fn events(&self) -> Result<EventStream> {
+    pub fn events(&self, filter: EventFilter) -> Result<EventStream> {
         let events = self
             .shared
             .events
             .as_ref()
             .context("world doesn't have events enabled")?;
 
-        Ok(EventStream::new(events))
+        Ok(events.subscribe(filter))
     }
 
     pub fn snapshots(&self) -> SnapshotStream {
@@ -158,6 +160,14 @@ impl Handle {
         rx.await.context(Self::ERR)
     }
 
+    pub async fn set_spawn_points(&self, points: Vec<IVec2>) -> Result<()> {
+        let (tx, rx) = oneshot::channel();
+
+        self.send(Request::SetSpawnPoints { points, tx }).await?;
+
+        rx.await.context(Self::ERR)
+    }
+
     pub async fn create_object(
         &self,
         obj: Object,
@@ -183,6 +193,74 @@ impl Handle {
         rx.await.context(Self::ERR)
     }
 
+    /// Starts recording the world, so that it can be re-simulated later (see:
+    /// [`Handle::stop_recording()`]).
+    ///
+    /// If the world is already being recorded, the previous recording gets
+    /// discarded.
+    ///
+    /// Long recordings get split - the finished parts are stored next to the
+    /// world's file (see: [`Replay::paths()`]) or, if the world is not
+    /// persisted, discarded.
+    pub async fn start_recording(&self) -> Result<()> {
+        let (tx, rx) = oneshot::channel();
+
+        self.send(Request::StartRecording { tx }).await?;
+
+        rx.await.context(Self::ERR)
+    }
+
+    /// Stops recording the world and returns the recording, or `None` if the
+    /// world wasn't being recorded.
+    pub async fn stop_recording(&self) -> Result<Option<Replay>> {
+        let (tx, rx) = oneshot::channel();
+
+        self.send(Request::StopRecording { tx }).await?;
+
+        rx.await.context(Self::ERR)
+    }
+
+    /// Starts writing world's events matching given filter into a file next
+    /// to the world's file (`<id>.events`), see: [`EventLog`].
+    ///
+    /// Works only for worlds that are persisted.
+    ///
+    /// [`EventLog`]: crate::EventLog
+    pub async fn start_event_log(&self, filter: EventFilter) -> Result<()> {
+        let (tx, rx) = oneshot::channel();
+
+        self.send(Request::StartEventLog { filter, tx }).await?;
+
+        rx.await.context(Self::ERR)?
+    }
+
+    /// Creates a private copy of this world - including bots, together with
+    /// their memory - which then continues to be simulated on its own.
+    ///
+    /// The copy is never persisted and, being meant for experiments, allows
+    /// for [`Handle::rewind()`].
+    pub async fn fork(&self) -> Result<Handle> {
+        let (tx, rx) = oneshot::channel();
+
+        self.send(Request::Fork { tx }).await?;
+
+        rx.await.context(Self::ERR)?
+    }
+
+    /// Rewinds the world by given number of ticks, returning the number of
+    /// ticks actually rewound.
+    ///
+    /// Works only for worlds created with [`Config::checkpoints`] - the
+    /// history is limited to a couple of seconds, so asking for more simply
+    /// rewinds as far back as possible.
+    pub async fn rewind(&self, ticks: u64) -> Result<u64> {
+        let (tx, rx) = oneshot::channel();
+
+        self.send(Request::Rewind { ticks, tx }).await?;
+
+        rx.await.context(Self::ERR)?
+    }
+
     pub async fn overclock(&self, clock: Clock) -> Result<()> {
         let (tx, rx) = oneshot::channel();
 
@@ -207,7 +285,7 @@ pub struct HandleShared {
     pub tx: RequestTx,
     pub id: Id,
     pub name: String,
-    pub events: Option<broadcast::Sender<EventLetter>>,
+    pub events: Option<Arc<EventSubscribers>>,
     pub snapshots: watch::Sender<Arc<Snapshot>>,
 }
 
@@ -281,6 +359,13 @@ pub enum Request {
         tx: oneshot::Sender<()>,
     },
 
+    SetSpawnPoints {
+        points: Vec<IVec2>,
+
+        #[derivative(Debug = "ignore")]
+        tx: oneshot::Sender<()>,
+    },
+
     CreateObject {
         obj: Object,
         pos: Option<IVec2>,
@@ -296,6 +381,35 @@ pub enum Request {
         tx: oneshot::Sender<Option<Object>>,
     },
 
+    StartRecording {
+        #[derivative(Debug = "ignore")]
+        tx: oneshot::Sender<()>,
+    },
+
+    StopRecording {
+        #[derivative(Debug = "ignore")]
+        tx: oneshot::Sender<Option<Replay>>,
+    },
+
+    StartEventLog {
+        filter: EventFilter,
+
+        #[derivative(Debug = "ignore")]
+        tx: oneshot::Sender<Result<()>>,
+    },
+
+    Fork {
+        #[derivative(Debug = "ignore")]
+        tx: oneshot::Sender<Result<Handle>>,
+    },
+
+    Rewind {
+        ticks: u64,
+
+        #[derivative(Debug = "ignore")]
+        tx: oneshot::Sender<Result<u64>>,
+    },
+
     Overclock {
         clock: Clock,
 
@@ -304,15 +418,20 @@ pub enum Request {
     },
 }
 
-#[derive(Derivative)]
+#[derive(Clone, Derivative, Serialize, Deserialize)]
 #[derivative(Debug)]
 pub struct CreateBotRequest {
     #[derivative(Debug = "ignore")]
+    #[serde(
+        serialize_with = "serde_bytes::serialize",
+        deserialize_with = "deserialize_src"
+    )]
     pub src: Cow<'static, [u8]>,
     pub pos: Option<IVec2>,
     pub dir: Option<Dir>,
     pub instant: bool,
     pub oneshot: bool,
+    pub team: Option<u8>,
 }
 
 impl CreateBotRequest {
@@ -323,6 +442,7 @@ impl CreateBotRequest {
             dir: None,
             instant: false,
             oneshot: false,
+            team: None,
         }
     }
 
@@ -345,4 +465,22 @@ impl CreateBotRequest {
         self.oneshot = true;
         self
     }
+
+    /// Assigns bot to given team, which must be less than `u8::MAX`.
+    ///
+    /// If no team is given, the game mode gets to decide (see:
+    /// [`crate::GameMode::team_for()`]).
+    pub fn team(mut self, team: impl Into<Option<u8>>) -> Self {
+        self.team = team.into();
+        self
+    }
+}
+
+fn deserialize_src<'de, D>(
+    deserializer: D,
+) -> Result<Cow<'static, [u8]>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    ByteBuf::deserialize(deserializer).map(|src| Cow::Owned(src.into_vec()))
 }
@@ -1,10 +1,17 @@
-use crate::{bots, Clock, KillBot, Request, Shutdown, World};
+use crate::{
+    bots, replay, Clock, Container, Event, EventFilter, EventLog, KillBot,
+    Request, Shutdown, World,
+};
+use anyhow::{Context, Result};
 use itertools::Either;
 use std::ops::ControlFlow;
 use tokio::sync::mpsc::error::TryRecvError;
 use tracing::debug;
 
-pub fn run(world: &mut World) -> ControlFlow<Shutdown, ()> {
+pub fn run(
+    world: &mut World,
+    systems: &mut Container,
+) -> ControlFlow<Shutdown, ()> {
     loop {
         let request = match world.clock {
             Clock::Manual => {
@@ -14,107 +21,174 @@ pub fn run(world: &mut World) -> ControlFlow<Shutdown, ()> {
             _ => world.rx.try_recv(),
         };
 
-        if let Ok(request) = &request {
-            debug!(?request, "processing");
-        }
+        let request = match request {
+            Ok(request) => request,
 
-        match request {
-            Ok(Request::Tick { tx }) => {
-                assert!(world.tick.is_none());
+            Err(TryRecvError::Empty) => {
+                return ControlFlow::Continue(());
+            }
 
-                world.tick = Some(tx);
+            Err(TryRecvError::Disconnected) => {
+                return ControlFlow::Break(Shutdown { tx: None });
             }
+        };
 
-            Ok(Request::Pause { tx }) => {
-                world.paused = true;
+        debug!(?request, "processing");
 
-                _ = tx.send(());
-            }
+        if let Some(recorder) = &mut world.recorder {
+            recorder.record(&request);
+        }
 
-            Ok(Request::Resume { tx }) => {
-                world.paused = false;
+        if let Some(checkpoints) = &mut world.checkpoints {
+            checkpoints.record(&request);
+        }
 
-                _ = tx.send(());
-            }
+        process(world, systems, request)?;
 
-            Ok(Request::Shutdown { tx }) => {
-                return ControlFlow::Break(Shutdown { tx: Some(tx) });
-            }
+        if let Clock::Manual = world.clock {
+            return ControlFlow::Continue(());
+        }
+    }
+}
 
-            Ok(Request::CreateBot { req, tx }) => {
-                _ = tx.send(bots::create::run(world, req));
-            }
+pub fn process(
+    world: &mut World,
+    systems: &mut Container,
+    request: Request,
+) -> ControlFlow<Shutdown, ()> {
+    match request {
+        Request::Tick { tx } => {
+            assert!(world.tick.is_none());
 
-            Ok(Request::CreateBots { reqs, tx }) => {
-                let mut ids = Vec::new();
+            world.tick = Some(tx);
+        }
 
-                for req in reqs {
-                    ids.push(bots::create::run(world, req));
-                }
+        Request::Pause { tx } => {
+            world.paused = true;
 
-                _ = tx.send(ids);
-            }
+            _ = tx.send(());
+        }
 
-            Ok(Request::KillBot { id, reason, tx }) => {
-                bots::kill::run(
-                    world,
-                    KillBot {
-                        killed: Either::Left(id),
-                        reason,
-                        killer: None,
-                    },
-                );
-
-                _ = tx.send(());
-            }
+        Request::Resume { tx } => {
+            world.paused = false;
 
-            Ok(Request::DeleteBot { id, tx }) => {
-                world.bots.remove(id);
+            _ = tx.send(());
+        }
 
-                _ = tx.send(());
-            }
+        Request::Shutdown { tx } => {
+            return ControlFlow::Break(Shutdown { tx: Some(tx) });
+        }
 
-            Ok(Request::SetMap { map, tx }) => {
-                world.map = map;
+        Request::CreateBot { req, tx } => {
+            _ = tx.send(bots::create::run(world, req));
+        }
 
-                _ = tx.send(());
+        Request::CreateBots { reqs, tx } => {
+            let mut ids = Vec::new();
+
+            for req in reqs {
+                ids.push(bots::create::run(world, req));
             }
 
-            Ok(Request::SetSpawn { point, dir, tx }) => {
-                world.spawn = (point, dir);
+            _ = tx.send(ids);
+        }
 
-                _ = tx.send(());
-            }
+        Request::KillBot { id, reason, tx } => {
+            bots::kill::run(
+                world,
+                KillBot {
+                    killed: Either::Left(id),
+                    reason,
+                    killer: None,
+                },
+            );
+
+            _ = tx.send(());
+        }
 
-            Ok(Request::CreateObject { obj, pos, tx }) => {
-                let id = world.objects.create(&mut world.rng, obj, pos);
+        Request::DeleteBot { id, tx } => {
+            world.bots.remove(id);
 
-                _ = tx.send(id);
-            }
+            _ = tx.send(());
+        }
 
-            Ok(Request::DeleteObject { id, tx }) => {
-                let result = world.objects.remove(id);
+        Request::SetMap { map, tx } => {
+            world.map = map;
+            world.events.add(Event::MapChanged);
 
-                _ = tx.send(result);
-            }
+            _ = tx.send(());
+        }
 
-            Ok(Request::Overclock { clock, tx }) => {
-                world.clock = clock;
+        Request::SetSpawn { point, dir, tx } => {
+            world.spawn = (point, dir);
 
-                _ = tx.send(());
-            }
+            _ = tx.send(());
+        }
 
-            Err(TryRecvError::Empty) => {
-                return ControlFlow::Continue(());
-            }
+        Request::SetSpawnPoints { points, tx } => {
+            world.spawn_points = points;
 
-            Err(TryRecvError::Disconnected) => {
-                return ControlFlow::Break(Shutdown { tx: None });
+            _ = tx.send(());
+        }
+
+        Request::CreateObject { obj, pos, tx } => {
+            let id = world.objects.create(&mut world.rng, obj, pos);
+
+            world.events.add(Event::ObjectCreated { id, at: pos });
+
+            _ = tx.send(id);
+        }
+
+        Request::DeleteObject { id, tx } => {
+            let result = world.objects.remove(id);
+
+            if result.is_some() {
+                world.events.add(Event::ObjectDeleted { id });
             }
+
+            _ = tx.send(result);
         }
 
-        if let Clock::Manual = world.clock {
-            return ControlFlow::Continue(());
+        Request::Overclock { clock, tx } => {
+            world.clock = clock;
+
+            _ = tx.send(());
+        }
+
+        Request::StartRecording { tx } => {
+            world.recorder = Some(replay::Recorder::new(world));
+
+            _ = tx.send(());
+        }
+
+        Request::StopRecording { tx } => {
+            _ = tx.send(world.recorder.take().map(|rec| rec.finish()));
+        }
+
+        Request::StartEventLog { filter, tx } => {
+            _ = tx.send(start_event_log(world, filter));
+        }
+
+        Request::Fork { tx } => {
+            _ = tx.send(crate::fork(world));
+        }
+
+        Request::Rewind { ticks, tx } => {
+            _ = tx.send(replay::rewind(world, systems, ticks));
         }
     }
+
+    ControlFlow::Continue(())
+}
+
+fn start_event_log(world: &mut World, filter: EventFilter) -> Result<()> {
+    let path = world
+        .path
+        .as_ref()
+        .context("world is not persisted")?
+        .with_extension("events");
+
+    world.events.set_log(EventLog::open(path, filter)?);
+
+    Ok(())
 }
@@ -15,6 +15,7 @@ mod mode;
 mod object;
 mod objects;
 mod policy;
+mod replay;
 mod snapshots;
 mod stats;
 mod storage;
@@ -30,18 +31,31 @@ pub mod prelude {
     pub use crate::bot::BotId;
     pub use crate::clock::Clock;
     pub use crate::config::Config;
-    pub use crate::events::{Event, EventLetter, EventStream};
+    pub use crate::events::{
+        Event, EventFilter, EventKind, EventLetter, EventLog, EventLogEntry,
+        EventStream,
+    };
     pub use crate::handle::{CreateBotRequest, Handle, Request};
     pub use crate::map::{Map, MapBuilder, Tile, TileKind};
-    pub use crate::mode::{DeathmatchMode, Mode};
+    pub use crate::mode::{
+        CaptureTheFlagMode, DeathmatchMode, GameMode, GemCollectionMode,
+        KingOfTheHillMode, LastBotStandingMode, Mode, ModeBot, ModeContext,
+        ModeRequest, RacingMode, Round, WasmMode, Zone,
+    };
     pub use crate::object::{Object, ObjectId, ObjectKind};
-    pub use crate::policy::Policy;
+    pub use crate::policy::{Combat, Policy};
+    pub use crate::replay::{Replay, Replayer};
     pub use crate::snapshots::{
         Snapshot, SnapshotAliveBot, SnapshotAliveBots, SnapshotBot,
-        SnapshotBots, SnapshotDeadBot, SnapshotDeadBots, SnapshotObjects,
-        SnapshotQueuedBot, SnapshotQueuedBots, SnapshotStream,
+        SnapshotBotDelta, SnapshotBots, SnapshotDeadBot, SnapshotDeadBots,
+        SnapshotDelta, SnapshotDeltaStream, SnapshotLap, SnapshotObject,
+        SnapshotObjects, SnapshotQueuedBot, SnapshotQueuedBotDelta,
+        SnapshotQueuedBots, SnapshotStream, SnapshotTeam, SnapshotTeams,
+        SnapshotUpdate,
+    };
+    pub use crate::theme::{
+        ArenaTheme, CaveTheme, DungeonTheme, FileTheme, MazeTheme, Theme,
     };
-    pub use crate::theme::{ArenaTheme, DungeonTheme, Theme};
     pub use crate::utils::Dir;
 }
 
@@ -56,35 +70,30 @@ pub(crate) use self::mode::*;
 pub(crate) use self::object::*;
 pub(crate) use self::objects::*;
 pub(crate) use self::policy::*;
+pub(crate) use self::replay::*;
 pub(crate) use self::snapshots::*;
 pub(crate) use self::storage::*;
 pub(crate) use self::theme::*;
 pub(crate) use self::utils::*;
 use crate::Metronome;
-use anyhow::Result;
+use anyhow::{Context, Result};
 use glam::IVec2;
 use kartoffels_utils::Id;
-use rand::rngs::SmallRng;
 use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 use std::ops::ControlFlow;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::thread;
 use tokio::runtime::Handle as TokioHandle;
-use tokio::sync::{broadcast, mpsc, oneshot, watch};
+use tokio::sync::{mpsc, oneshot, watch};
 use tracing::{debug, info, info_span};
 
 pub fn create(config: Config) -> Handle {
-    assert!(
-        !(config.path.is_some() && config.seed.is_some()),
-        "setting both `config.path` and `config.seed` is not supported, because \
-         prng state is currently not persisted"
-    );
-
     let mut rng = config
         .seed
-        .map(SmallRng::from_seed)
-        .unwrap_or_else(SmallRng::from_entropy);
+        .map(ChaCha8Rng::from_seed)
+        .unwrap_or_else(ChaCha8Rng::from_entropy);
 
     let clock = config.clock;
     let id = rng.gen();
@@ -101,9 +110,11 @@ pub fn create(config: Config) -> Handle {
 
     let (handle, rx) = create_handle(id, name.clone(), config.events);
 
-    World {
+    let mut world = World {
         bots: Default::default(),
+        checkpoints: config.checkpoints.then(Default::default),
         clock,
+        convey: Default::default(),
         events: Events::new(handle.shared.events.clone()),
         map,
         metronome: clock.metronome(),
@@ -113,14 +124,19 @@ pub fn create(config: Config) -> Handle {
         path,
         paused: false,
         policy,
+        recorder: None,
         rng,
         rx,
         snapshots: handle.shared.snapshots.clone(),
         spawn: (None, None),
+        spawn_points: Default::default(),
         theme,
         tick: None,
-    }
-    .spawn(id);
+    };
+
+    theme::apply::run(&mut world);
+
+    world.spawn(id);
 
     handle
 }
@@ -129,37 +145,119 @@ pub fn resume(id: Id, path: &Path) -> Result<Handle> {
     let path = path.to_owned();
     let world = SerializedWorld::load(&path)?;
 
-    let bots = world.bots.into_owned();
-    let clock = Clock::default();
-    let map = world.map.into_owned();
-    let metronome = clock.metronome();
-    let mode = world.mode.into_owned();
-    let name = world.name.into_owned();
-    let policy = world.policy.into_owned();
-    let theme = world.theme.map(|theme| theme.into_owned());
+    let mut bots = world.bots.into_owned();
+
+    bots.alive.restore_layout(&world.layout);
 
+    let clock = world.clock;
+    let name = world.name.into_owned();
+    let objects = world.objects.map(|objects| objects.into_owned());
     let (handle, rx) = create_handle(id, name.clone(), false);
 
-    World {
+    let mut world = World {
         bots,
+        checkpoints: None,
         clock,
+        convey: world.convey.into_owned(),
         events: Events::new(handle.shared.events.clone()),
-        map,
-        metronome,
-        mode,
+        map: world.map.into_owned(),
+        metronome: clock.metronome(),
+        mode: world.mode.into_owned(),
         name,
-        objects: Default::default(),
+        objects: objects.clone().unwrap_or_default(),
         path: Some(path),
-        paused: false,
-        policy,
-        rng: SmallRng::from_entropy(),
+        paused: world.paused,
+        policy: world.policy.into_owned(),
+        recorder: None,
+        rng: world.rng.into_owned(),
         rx,
         snapshots: handle.shared.snapshots.clone(),
-        spawn: (None, None),
-        theme,
+        spawn: world.spawn,
+        spawn_points: world.spawn_points.into_owned(),
+        theme: world.theme.map(|theme| theme.into_owned()),
         tick: None,
+    };
+
+    // Worlds saved before objects got persisted need their theme's objects
+    // (and spawn points) to be re-created
+    if objects.is_none() {
+        theme::apply::run(&mut world);
     }
-    .spawn(id);
+
+    world.spawn(id);
+
+    Ok(handle)
+}
+
+/// Replaces game mode of the world stored in given file.
+///
+/// This operates directly on the file, so the world mustn't be running at the
+/// same time - otherwise the change would get overwritten on its next save.
+pub fn set_mode(path: &Path, mode: Mode) -> Result<()> {
+    SerializedWorld::update(path, |world| {
+        world.mode = mode.into();
+    })
+}
+
+/// Replaces theme and map of the world stored in given file.
+///
+/// Same as with [`set_mode()`], the world mustn't be running at the same time.
+pub fn set_theme(path: &Path, theme: Theme, map: Map) -> Result<()> {
+    SerializedWorld::update(path, |world| {
+        world.map = map.into();
+        world.objects = None;
+        world.theme = Some(theme.into());
+    })
+}
+
+/// Creates a private copy of given world, see: [`Handle::fork()`].
+fn fork(world: &World) -> Result<Handle> {
+    let forked: SerializedWorld =
+        ciborium::Value::serialized(&SerializedWorld::new(world))
+            .context("couldn't serialize world")?
+            .deserialized()
+            .context("couldn't deserialize world")?;
+
+    let mut bots = forked.bots.into_owned();
+
+    bots.alive.restore_layout(&forked.layout);
+
+    // Using world's rng here would affect the original simulation, which
+    // should remain oblivious to being forked
+    let id = rand::thread_rng().gen();
+    let name = forked.name.into_owned();
+
+    let (handle, rx) =
+        create_handle(id, name.clone(), world.events.is_streamed());
+
+    let world = World {
+        bots,
+        checkpoints: Some(Default::default()),
+        clock: forked.clock,
+        convey: forked.convey.into_owned(),
+        events: Events::new(handle.shared.events.clone()),
+        map: forked.map.into_owned(),
+        metronome: forked.clock.metronome(),
+        mode: forked.mode.into_owned(),
+        name,
+        objects: forked
+            .objects
+            .map(|objects| objects.into_owned())
+            .unwrap_or_default(),
+        path: None,
+        paused: false,
+        policy: forked.policy.into_owned(),
+        recorder: None,
+        rng: forked.rng.into_owned(),
+        rx,
+        snapshots: handle.shared.snapshots.clone(),
+        spawn: forked.spawn,
+        spawn_points: forked.spawn_points.into_owned(),
+        theme: forked.theme.map(|theme| theme.into_owned()),
+        tick: None,
+    };
+
+    world.spawn(id);
 
     Ok(handle)
 }
@@ -171,8 +269,7 @@ fn create_handle(
 ) -> (Handle, mpsc::Receiver<Request>) {
     let (tx, rx) = mpsc::channel(cfg::REQUEST_STREAM_CAPACITY);
 
-    let events =
-        events.then(|| broadcast::Sender::new(cfg::EVENT_STREAM_CAPACITY));
+    let events = events.then(Default::default);
 
     let handle = Handle {
         shared: Arc::new(HandleShared {
@@ -190,7 +287,9 @@ fn create_handle(
 
 struct World {
     bots: Bots,
+    checkpoints: Option<Checkpoints>,
     clock: Clock,
+    convey: ConveyTimer,
     events: Events,
     map: Map,
     metronome: Metronome,
@@ -200,20 +299,21 @@ struct World {
     path: Option<PathBuf>,
     paused: bool,
     policy: Policy,
-    rng: SmallRng,
+    recorder: Option<Recorder>,
+    rng: ChaCha8Rng,
     rx: RequestRx,
     snapshots: watch::Sender<Arc<Snapshot>>,
     spawn: (Option<IVec2>, Option<Dir>),
+    spawn_points: Vec<IVec2>,
     theme: Option<Theme>,
     tick: Option<oneshot::Sender<()>>,
 }
 
 impl World {
     fn spawn(mut self, id: Id) {
-        // We store bot indices into map's tile metadata and since those are u8,
-        // we can't have than 256 bots
-        assert!(self.policy.max_alive_bots <= 256);
-        assert!(self.policy.max_queued_bots <= 256);
+        // Snapshots store bot indices into map's tile metadata and since those
+        // are 24-bit, we can't have more than 2^24 bots
+        assert!(self.policy.max_alive_bots <= 1 << 24);
 
         let rt = TokioHandle::current();
         let span = info_span!("world", %id);
@@ -243,12 +343,12 @@ impl World {
     }
 
     fn tick(&mut self, systems: &mut Container) -> ControlFlow<Shutdown, ()> {
-        handle::process_requests::run(self)?;
+        handle::process_requests::run(self, systems)?;
+        replay::checkpoint(self);
+        replay::save::run(self, systems.get_mut());
+        replay::record::run(self);
 
-        if !self.paused {
-            bots::spawn::run(self);
-            bots::tick::run(self);
-        }
+        self.simulate(systems);
 
         snapshots::send::run(self, systems.get_mut());
         storage::save::run(self, systems.get_mut());
@@ -261,10 +361,24 @@ impl World {
         ControlFlow::Continue(())
     }
 
+    fn simulate(&mut self, systems: &mut Container) {
+        if !self.paused {
+            bots::spawn::run(self);
+            bots::tick::run(self);
+            bots::convey::run(self);
+            mode::tick::run(self);
+        }
+
+        mode::scores::run(self, systems.get_mut());
+    }
+
     fn shutdown(mut self, systems: &mut Container, shutdown: Shutdown) {
         debug!("shutting down");
 
         storage::save::run_now(&mut self, systems.get_mut(), true);
+        replay::save::run_now(&mut self, systems.get_mut());
+
+        self.events.close_log();
 
         if let Some(tx) = shutdown.tx {
             _ = tx.send(());
@@ -278,3 +392,36 @@ impl World {
 struct Shutdown {
     tx: Option<oneshot::Sender<()>>,
 }
+
+#[cfg(test)]
+impl World {
+    /// Creates a world for unit tests - the world isn't spawned on any thread
+    /// and its systems are meant to be driven by hand.
+    fn test(map: Map) -> Self {
+        let (handle, rx) = create_handle(Id::new(1), "test".into(), true);
+
+        Self {
+            bots: Default::default(),
+            checkpoints: None,
+            clock: Clock::Manual,
+            convey: Default::default(),
+            events: Events::new(handle.shared.events.clone()),
+            map,
+            metronome: Clock::Manual.metronome(),
+            mode: Default::default(),
+            name: "test".into(),
+            objects: Default::default(),
+            path: None,
+            paused: false,
+            policy: Default::default(),
+            recorder: None,
+            rng: ChaCha8Rng::from_seed(Default::default()),
+            rx,
+            snapshots: handle.shared.snapshots.clone(),
+            spawn: (None, None),
+            spawn_points: Default::default(),
+            theme: None,
+            tick: None,
+        }
+    }
+}
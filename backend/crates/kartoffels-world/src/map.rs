@@ -1,17 +1,29 @@
 mod builder;
 
 pub use self::builder::*;
-use ahash::HashMap;
+use crate::{Dir, Object, ObjectKind};
+use ahash::AHashMap;
+use anyhow::{anyhow, Result};
 use glam::{ivec2, uvec2, IVec2, UVec2};
 use rand::{Rng, RngCore};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
 use std::{cmp, fmt};
 
-#[derive(Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Default, Serialize, Deserialize)]
 pub struct Map {
     size: UVec2,
     tiles: Box<[Tile]>,
+
+    #[serde(skip, default = "Map::next_rev")]
+    rev: u64,
+
+    /// Teleporter pairs, built on the first call to
+    /// [`Self::teleporter_pair()`] since the map's last modification
+    #[serde(skip)]
+    teleporters: OnceLock<AHashMap<IVec2, IVec2>>,
 }
 
 impl Map {
@@ -19,37 +31,139 @@ impl Map {
         let tiles = vec![Tile::new(TileKind::VOID); (size.x * size.y) as usize]
             .into_boxed_slice();
 
-        Self { size, tiles }
+        Self {
+            size,
+            tiles,
+            rev: Self::next_rev(),
+            teleporters: Default::default(),
+        }
     }
 
-    pub fn parse(s: &str) -> (Self, Anchors) {
-        let lines: Vec<_> = s.split('\n').collect();
+    /// Parses a map drawn in ASCII, like:
+    ///
+    /// ```text
+    /// |-------|
+    /// |.a.=.>.|
+    /// |.&.*.&.|
+    /// |-------|
+    /// ```
+    ///
+    /// Besides the usual tiles (see: [`TileKind`]), the map can contain:
+    ///
+    /// - letters, which become anchors (and leave the tile void),
+    /// - objects (`=` for flags, `*` for gems), which get placed on the floor,
+    /// - conveyors pointing in any direction (`^`, `>`, `v`, `<`).
+    ///
+    /// Teleporters get paired in the reading order, i.e. the first teleporter
+    /// leads to the second one, the third one leads to the fourth one etc.
+    pub fn parse(s: &str) -> Result<(Self, Anchors)> {
+        let lines: Vec<_> = s
+            .split('\n')
+            .map(|line| line.strip_suffix('\r').unwrap_or(line))
+            .collect();
 
         let size = {
-            let width =
-                lines.iter().map(|line| line.len()).max().unwrap() as u32;
+            let width = lines
+                .iter()
+                .map(|line| line.chars().count())
+                .max()
+                .unwrap_or(0);
 
-            let height = lines.len() as u32;
-
-            uvec2(width, height)
+            uvec2(width as u32, lines.len() as u32)
         };
 
         let mut map = Self::new(size);
         let mut anchors = Anchors::default();
+        let mut teleporters = Vec::new();
 
         for (y, line) in lines.into_iter().enumerate() {
-            for (x, tile) in line.bytes().enumerate() {
+            for (x, ch) in line.chars().enumerate() {
                 let pos = ivec2(x as i32, y as i32);
 
-                if tile.is_ascii_alphabetic() {
-                    anchors.set(tile as char, pos);
-                } else {
-                    map.set(pos, tile);
+                let err = |msg: &str| {
+                    anyhow!("line {}, column {}: {msg}", y + 1, x + 1)
+                };
+
+                let Ok(ch) = u8::try_from(ch) else {
+                    return Err(err(&format!(
+                        "unknown tile `{}`",
+                        ch.escape_default()
+                    )));
+                };
+
+                match ch {
+                    TileKind::DOOR
+                    | TileKind::FLOOR
+                    | TileKind::VOID
+                    | TileKind::WALL_CRACKED
+                    | TileKind::WALL_H
+                    | TileKind::WALL_V
+                    | TileKind::WATER => {
+                        map.set(pos, ch);
+                    }
+
+                    b'^' | b'>' | b'v' | b'<' => {
+                        let dir = match ch {
+                            b'^' => Dir::N,
+                            b'>' => Dir::E,
+                            b'v' => Dir::S,
+                            _ => Dir::W,
+                        };
+
+                        map.set(
+                            pos,
+                            Tile {
+                                kind: TileKind::CONVEYOR,
+                                meta: [dir.into(), 0, 0],
+                            },
+                        );
+                    }
+
+                    TileKind::TELEPORTER => {
+                        let channel = u8::try_from(teleporters.len() / 2)
+                            .map_err(|_| err("too many teleporters"))?;
+
+                        map.set(
+                            pos,
+                            Tile {
+                                kind: TileKind::TELEPORTER,
+                                meta: [channel, 0, 0],
+                            },
+                        );
+
+                        teleporters.push((y, x));
+                    }
+
+                    ObjectKind::FLAG | ObjectKind::GEM => {
+                        anchors.objects.push((Object::new(ch), pos));
+                        map.set(pos, TileKind::FLOOR);
+                    }
+
+                    ch if ch.is_ascii_alphabetic() => {
+                        anchors.anchors.push((ch as char, pos));
+                    }
+
+                    _ => {
+                        return Err(err(&format!(
+                            "unknown tile `{}`",
+                            (ch as char).escape_default()
+                        )));
+                    }
                 }
             }
         }
 
-        (map, anchors)
+        if teleporters.len() % 2 == 1
+            && let Some((y, x)) = teleporters.last()
+        {
+            return Err(anyhow!(
+                "line {}, column {}: teleporter doesn't have a pair",
+                y + 1,
+                x + 1
+            ));
+        }
+
+        Ok((map, anchors))
     }
 
     pub fn get(&self, pos: IVec2) -> Tile {
@@ -63,6 +177,8 @@ impl Map {
     pub fn get_mut(&mut self, pos: IVec2) -> &mut Tile {
         let idx = self.pos_to_idx(pos).unwrap();
 
+        self.touch();
+
         &mut self.tiles[idx]
     }
 
@@ -71,9 +187,22 @@ impl Map {
 
         if let Some(idx) = self.pos_to_idx(pos) {
             self.tiles[idx] = tile;
+            self.touch();
         }
     }
 
+    /// Same as [`Self::set()`], but bumps the revision only once, which
+    /// makes it cheaper when changing lots of tiles at once.
+    pub fn set_many(&mut self, tiles: impl IntoIterator<Item = (IVec2, Tile)>) {
+        for (pos, tile) in tiles {
+            if let Some(idx) = self.pos_to_idx(pos) {
+                self.tiles[idx] = tile;
+            }
+        }
+
+        self.touch();
+    }
+
     pub fn set_if_void(&mut self, pos: IVec2, tile: impl Into<Tile>) {
         if self.get(pos).is_void() {
             self.set(pos, tile);
@@ -146,11 +275,13 @@ impl Map {
         for y in 0..self.size.y {
             for x in 0..self.size.x {
                 let pos = ivec2(x as i32, y as i32);
-                let tile = self.get_mut(pos);
+                let idx = self.pos_to_idx(pos).unwrap();
 
-                f(pos, tile);
+                f(pos, &mut self.tiles[idx]);
             }
         }
+
+        self.touch();
     }
 
     pub fn map(mut self, mut f: impl FnMut(IVec2, Tile) -> Tile) -> Self {
@@ -178,6 +309,62 @@ impl Map {
         self.size
     }
 
+    /// Returns position of the teleporter paired with the one at given
+    /// position.
+    ///
+    /// Teleporters are paired through the channel kept in their `meta[0]` -
+    /// if there are more than two teleporters on the same channel, the first
+    /// one (in the reading order) wins.
+    pub fn teleporter_pair(&self, pos: IVec2) -> Option<IVec2> {
+        self.teleporters
+            .get_or_init(|| {
+                let mut channels: AHashMap<u8, Vec<IVec2>> = AHashMap::new();
+
+                self.for_each(|pos, tile| {
+                    if tile.is_teleporter() {
+                        channels.entry(tile.meta[0]).or_default().push(pos);
+                    }
+                });
+
+                let mut pairs = AHashMap::new();
+
+                for tps in channels.into_values() {
+                    for &src in &tps {
+                        if let Some(&dst) = tps.iter().find(|&&dst| dst != src)
+                        {
+                            pairs.insert(src, dst);
+                        }
+                    }
+                }
+
+                pairs
+            })
+            .get(&pos)
+            .copied()
+    }
+
+    /// Returns revision of this map - a number that changes each time the map
+    /// gets modified, allowing to cheaply check whether the map has changed
+    /// since the last time someone's looked at it.
+    ///
+    /// Revisions are unique across all maps, so two maps with the same
+    /// revision are guaranteed to have the same contents (e.g. because one is
+    /// a clone of the other); the reverse doesn't hold.
+    pub fn rev(&self) -> u64 {
+        self.rev
+    }
+
+    fn touch(&mut self) {
+        self.rev = Self::next_rev();
+        self.teleporters = Default::default();
+    }
+
+    fn next_rev() -> u64 {
+        static REV: AtomicU64 = AtomicU64::new(1);
+
+        REV.fetch_add(1, Ordering::Relaxed)
+    }
+
     fn pos_to_idx(&self, pos: IVec2) -> Option<usize> {
         let size = self.size.as_ivec2();
 
@@ -189,6 +376,15 @@ impl Map {
     }
 }
 
+impl PartialEq for Map {
+    fn eq(&self, other: &Self) -> bool {
+        self.rev == other.rev
+            || (self.size == other.size && self.tiles == other.tiles)
+    }
+}
+
+impl Eq for Map {}
+
 impl fmt::Debug for Map {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("Map").field("size", &self.size).finish()
@@ -219,24 +415,37 @@ impl fmt::Display for Map {
     }
 }
 
+/// Anchors and objects found by [`Map::parse()`], in the reading order.
 #[derive(Clone, Debug, Default)]
 pub struct Anchors {
-    anchors: HashMap<char, IVec2>,
+    anchors: Vec<(char, IVec2)>,
+    objects: Vec<(Object, IVec2)>,
 }
 
 impl Anchors {
-    fn set(&mut self, id: char, pos: IVec2) {
-        self.anchors.insert(id, pos);
+    /// Returns position of given anchor - if the anchor appears many times,
+    /// the last occurrence wins.
+    pub fn get(&self, id: char) -> IVec2 {
+        self.anchors
+            .iter()
+            .rev()
+            .find(|(anchor, _)| *anchor == id)
+            .map(|(_, pos)| *pos)
+            .unwrap_or_else(|| panic!("missing anchor: {id}"))
     }
 
-    pub fn get(&self, id: char) -> IVec2 {
-        self.anchors[&id]
+    pub fn iter(&self) -> impl Iterator<Item = (char, IVec2)> + '_ {
+        self.anchors.iter().copied()
+    }
+
+    pub fn objects(&self) -> &[(Object, IVec2)] {
+        &self.objects
     }
 
     pub fn fill(&self, map: &mut Map, tile: impl Into<Tile>) {
         let tile = tile.into();
 
-        for pos in self.anchors.values() {
+        for (_, pos) in &self.anchors {
             map.set(*pos, tile);
         }
     }
@@ -249,6 +458,9 @@ pub struct Tile {
 }
 
 impl Tile {
+    /// Number of stabs it takes to break a cracked wall.
+    pub const CRACKED_WALL_DURABILITY: u8 = 3;
+
     pub fn new(kind: u8) -> Self {
         Self {
             kind,
@@ -265,12 +477,75 @@ impl Tile {
     }
 
     pub fn is_wall(&self) -> bool {
-        self.kind == TileKind::WALL_H || self.kind == TileKind::WALL_V
+        self.kind == TileKind::WALL_H
+            || self.kind == TileKind::WALL_V
+            || self.kind == TileKind::WALL_CRACKED
+    }
+
+    pub fn is_water(&self) -> bool {
+        self.kind == TileKind::WATER
+    }
+
+    /// Returns whether this tile is an open door.
+    ///
+    /// Doors keep their state in `meta[0]` - zero means closed, anything else
+    /// means open.
+    pub fn is_open_door(&self) -> bool {
+        self.kind == TileKind::DOOR && self.meta[0] != 0
+    }
+
+    pub fn is_teleporter(&self) -> bool {
+        self.kind == TileKind::TELEPORTER
+    }
+
+    /// Returns direction of this conveyor or `None` if this tile is not a
+    /// conveyor.
+    ///
+    /// Conveyors keep their direction in `meta[0]` (see: [`Dir`]'s `u8`
+    /// representation).
+    pub fn conveyor_dir(&self) -> Option<Dir> {
+        (self.kind == TileKind::CONVEYOR).then(|| Dir::from(self.meta[0]))
+    }
+
+    /// Returns whether bots (and objects) can enter this tile.
+    pub fn is_walkable(&self) -> bool {
+        self.is_floor()
+            || self.is_water()
+            || self.is_open_door()
+            || self.is_teleporter()
+            || self.conveyor_dir().is_some()
+    }
+
+    /// Creates a tile representing an alive bot, as seen on the snapshot's
+    /// map.
+    ///
+    /// Bots keep their index in `meta` (see: [`Self::bot_idx()`]), which
+    /// allows for up to 2^24 alive bots.
+    pub fn bot(idx: u32) -> Self {
+        let [b0, b1, b2, b3] = idx.to_le_bytes();
+
+        assert_eq!(b3, 0, "bot index out of range: {idx}");
+
+        Self {
+            kind: TileKind::BOT,
+            meta: [b0, b1, b2],
+        }
     }
 
     pub fn is_bot(&self) -> bool {
         self.kind == TileKind::BOT
     }
+
+    /// Returns index of the bot represented by this tile (see:
+    /// [`SnapshotAliveBots::get_by_idx()`]) or `None` if this tile is not a
+    /// bot.
+    ///
+    /// [`SnapshotAliveBots::get_by_idx()`]: crate::SnapshotAliveBots::get_by_idx
+    pub fn bot_idx(&self) -> Option<u32> {
+        self.is_bot().then(|| {
+            u32::from_le_bytes([self.meta[0], self.meta[1], self.meta[2], 0])
+        })
+    }
 }
 
 impl From<u8> for Tile {
@@ -313,9 +588,13 @@ pub struct TileKind;
 impl TileKind {
     pub const BOT: u8 = b'@';
     pub const BOT_CHEVRON: u8 = b'~';
+    pub const CONVEYOR: u8 = b'>';
     pub const DOOR: u8 = b'+';
     pub const FLOOR: u8 = b'.';
+    pub const TELEPORTER: u8 = b'&';
     pub const VOID: u8 = b' ';
+    pub const WALL_CRACKED: u8 = b'#';
     pub const WALL_H: u8 = b'-';
     pub const WALL_V: u8 = b'|';
+    pub const WATER: u8 = b'%';
 }
@@ -1,39 +1,374 @@
+mod capture_the_flag;
 mod deathmatch;
+mod gem_collection;
+mod king_of_the_hill;
+mod last_bot_standing;
+mod racing;
+mod systems;
+mod wasm;
 
+pub use self::capture_the_flag::*;
 pub use self::deathmatch::*;
-use crate::BotId;
+pub use self::gem_collection::*;
+pub use self::king_of_the_hill::*;
+pub use self::last_bot_standing::*;
+pub use self::racing::*;
+pub use self::systems::*;
+pub use self::wasm::*;
+use crate::{
+    AliveBot, BotId, Bots, Dir, Event, Events, Map, Object, ObjectId, Objects,
+};
 use ahash::AHashMap;
+use dyn_clone::DynClone;
+use glam::IVec2;
+use rand_chacha::ChaCha8Rng;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::{Deref, DerefMut};
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(tag = "type")]
-pub enum Mode {
-    #[serde(rename = "deathmatch")]
-    Deathmatch(DeathmatchMode),
-}
+/// Rules of the game - decides what bots score points for, what happens when
+/// they die etc.
+///
+/// Modes are persisted together with the rest of the world, tagged by their
+/// name - a custom mode gets registered by implementing this trait under
+/// `#[typetag::serde]`:
+///
+/// ```ignore
+/// #[typetag::serde(name = "tag")]
+/// impl GameMode for TagMode {
+///     fn scores(&self) -> &AHashMap<BotId, u32> {
+///         &self.scores
+///     }
+///
+///     fn on_bot_moved(
+///         &mut self,
+///         ctxt: &mut ModeContext,
+///         bot: ModeBot,
+///         from: IVec2,
+///     ) {
+///         /* ... */
+///     }
+/// }
+/// ```
+///
+/// All hooks are no-ops by default.
+#[typetag::serde(tag = "type")]
+pub trait GameMode: fmt::Debug + DynClone + Send {
+    /// Returns the scoreboard.
+    fn scores(&self) -> &AHashMap<BotId, u32>;
 
-impl Mode {
-    pub(crate) fn scores(&self) -> &AHashMap<BotId, u32> {
-        match self {
-            Mode::Deathmatch(this) => this.scores(),
-        }
+    /// Returns the team scoreboard, if the mode is team-based.
+    fn team_scores(&self) -> Option<&AHashMap<u8, u32>> {
+        None
+    }
+
+    /// Returns the area of map the mode wants to highlight (e.g. the hill in
+    /// king-of-the-hill).
+    fn zone(&self) -> Option<Zone> {
+        None
+    }
+
+    /// Returns positions of checkpoints, in order.
+    fn checkpoints(&self) -> &[IVec2] {
+        &[]
+    }
+
+    /// Returns the best lap times (in ticks), the fastest one first.
+    fn leaderboard(&self) -> Option<Vec<(BotId, u32)>> {
+        None
+    }
+
+    /// Returns history of rounds, the most recent round first.
+    fn rounds(&self) -> Option<Vec<Round>> {
+        None
+    }
+
+    /// Returns whether bots killed during the game should be benched until
+    /// the world gets reset (see: [`ModeRequest::ResetWorld`]) instead of
+    /// being requeued.
+    fn benches_bots(&self) -> bool {
+        false
+    }
+
+    /// Returns team for a newly uploaded bot (unless the bot's already got
+    /// one).
+    fn team_for(&mut self, _ctxt: &mut ModeContext) -> Option<u8> {
+        None
+    }
+
+    fn tick(&mut self, _ctxt: &mut ModeContext) -> Vec<ModeRequest> {
+        Vec::new()
+    }
+
+    fn on_bot_spawned(&mut self, _ctxt: &mut ModeContext, _bot: ModeBot) {
+        //
     }
 
-    pub(crate) fn on_bot_killed(
+    /// Called when a bot dies - note that at this point the killed bot is
+    /// already removed from the map.
+    fn on_bot_killed(
         &mut self,
-        killed_id: BotId,
-        killer_id: Option<BotId>,
+        _ctxt: &mut ModeContext,
+        _killed: ModeBot,
+        _killer: Option<BotId>,
     ) {
-        match self {
-            Mode::Deathmatch(this) => {
-                this.on_bot_killed(killed_id, killer_id);
-            }
-        }
+        //
+    }
+
+    fn on_bot_moved(
+        &mut self,
+        _ctxt: &mut ModeContext,
+        _bot: ModeBot,
+        _from: IVec2,
+    ) {
+        //
+    }
+
+    fn on_object_picked(
+        &mut self,
+        _ctxt: &mut ModeContext,
+        _bot: ModeBot,
+        _id: ObjectId,
+    ) {
+        //
+    }
+
+    fn on_object_dropped(
+        &mut self,
+        _ctxt: &mut ModeContext,
+        _bot: ModeBot,
+        _id: ObjectId,
+        _at: IVec2,
+    ) {
+        //
+    }
+}
+
+dyn_clone::clone_trait_object!(GameMode);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Mode(Box<dyn GameMode>);
+
+impl Mode {
+    pub fn new(mode: impl GameMode + 'static) -> Self {
+        Self(Box::new(mode))
     }
 }
 
 impl Default for Mode {
     fn default() -> Self {
-        Mode::Deathmatch(Default::default())
+        Self::new(DeathmatchMode::default())
+    }
+}
+
+impl<T> From<T> for Mode
+where
+    T: GameMode + 'static,
+{
+    fn from(mode: T) -> Self {
+        Self::new(mode)
+    }
+}
+
+impl Deref for Mode {
+    type Target = dyn GameMode;
+
+    fn deref(&self) -> &Self::Target {
+        &*self.0
+    }
+}
+
+impl DerefMut for Mode {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut *self.0
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ModeRequest {
+    /// Bot should get killed.
+    KillBot { id: BotId, reason: String },
+
+    /// Round is over - the map should be regenerated and all bots should get
+    /// queued again.
+    ResetWorld,
+}
+
+/// Access to the world, as seen by a game mode.
+pub struct ModeContext<'a> {
+    pub(crate) bots: &'a Bots,
+    pub(crate) events: &'a mut Events,
+    pub(crate) map: &'a Map,
+    pub(crate) objects: &'a mut Objects,
+    pub(crate) rng: &'a mut ChaCha8Rng,
+}
+
+impl ModeContext<'_> {
+    pub fn map(&self) -> &Map {
+        self.map
+    }
+
+    pub fn rng(&mut self) -> &mut ChaCha8Rng {
+        self.rng
+    }
+
+    /// Returns bots that are currently alive.
+    pub fn bots(&self) -> impl Iterator<Item = ModeBot> {
+        self.bots.alive.iter().map(ModeBot)
+    }
+
+    pub fn bot(&self, id: BotId) -> Option<ModeBot> {
+        self.bots.alive.get(id).map(ModeBot)
+    }
+
+    pub fn bot_at(&self, pos: IVec2) -> Option<ModeBot> {
+        self.bots
+            .alive
+            .lookup_at(pos)
+            .and_then(|id| self.bots.alive.get(id))
+            .map(ModeBot)
+    }
+
+    /// Returns whether there are any bots waiting to be spawned.
+    pub fn has_queued_bots(&self) -> bool {
+        !self.bots.queued.is_empty()
+    }
+
+    pub fn object(&self, id: ObjectId) -> Option<Object> {
+        self.objects.get(id)
+    }
+
+    pub fn object_at(&self, pos: IVec2) -> Option<ObjectId> {
+        self.objects.lookup_at(pos)
+    }
+
+    /// Returns objects lying on the map.
+    pub fn objects(
+        &self,
+    ) -> impl Iterator<Item = (ObjectId, Object, IVec2)> + '_ {
+        self.objects
+            .iter()
+            .filter_map(|obj| obj.pos.map(|pos| (obj.id, obj.obj, pos)))
+    }
+
+    pub fn create_object(&mut self, obj: Object, pos: IVec2) -> ObjectId {
+        let id = self.objects.create(self.rng, obj, Some(pos));
+
+        self.events.add(Event::ObjectCreated { id, at: Some(pos) });
+
+        id
+    }
+
+    /// Puts given object on the map - as compared to [`Self::create_object()`],
+    /// this is meant for objects that already exist, e.g. the ones taken out
+    /// of a killed bot's inventory.
+    pub fn place_object(&mut self, id: ObjectId, obj: Object, pos: IVec2) {
+        self.objects.add(id, obj, Some(pos));
+        self.events.add(Event::ObjectCreated { id, at: Some(pos) });
+    }
+
+    pub fn remove_object(&mut self, id: ObjectId) -> Option<Object> {
+        let obj = self.objects.remove(id)?;
+
+        self.events.add(Event::ObjectDeleted { id });
+
+        Some(obj)
+    }
+}
+
+/// Bot, as seen by a game mode.
+#[derive(Clone, Copy)]
+pub struct ModeBot<'a>(pub(crate) &'a AliveBot);
+
+impl ModeBot<'_> {
+    pub fn id(&self) -> BotId {
+        self.0.id
+    }
+
+    pub fn pos(&self) -> IVec2 {
+        self.0.pos
+    }
+
+    pub fn dir(&self) -> Dir {
+        self.0.dir
+    }
+
+    pub fn team(&self) -> Option<u8> {
+        self.0.team
+    }
+
+    /// Returns the number of ticks this bot has been alive for.
+    pub fn ticks(&self) -> u32 {
+        self.0.timer.ticks()
+    }
+
+    pub fn has_object(&self, id: ObjectId) -> bool {
+        self.0.inventory.has(id)
+    }
+
+    /// Returns objects carried by this bot.
+    pub fn objects(&self) -> impl Iterator<Item = (ObjectId, Object)> + '_ {
+        self.0.inventory.iter()
+    }
+}
+
+impl fmt::Debug for ModeBot<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ModeBot")
+            .field("id", &self.0.id)
+            .field("pos", &self.0.pos)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Clone, Debug, Default, Serialize, Deserialize)]
+    struct CustomMode {
+        scores: AHashMap<BotId, u32>,
+        moves: u32,
+    }
+
+    #[typetag::serde(name = "custom")]
+    impl GameMode for CustomMode {
+        fn scores(&self) -> &AHashMap<BotId, u32> {
+            &self.scores
+        }
+    }
+
+    #[test]
+    fn serde_builtin() {
+        let mode = Mode::default();
+        let actual = serde_json::to_value(&mode).unwrap();
+
+        let expected = json!({
+            "type": "deathmatch",
+            "scores": {},
+        });
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn serde_custom() {
+        let mode = Mode::new(CustomMode {
+            scores: Default::default(),
+            moves: 123,
+        });
+
+        let mut buffer = Vec::new();
+
+        ciborium::into_writer(&mode, &mut buffer).unwrap();
+
+        let mode: Mode = ciborium::from_reader(&buffer[..]).unwrap();
+
+        assert_eq!(
+            "Mode(CustomMode { scores: {}, moves: 123 })",
+            format!("{mode:?}")
+        );
     }
 }
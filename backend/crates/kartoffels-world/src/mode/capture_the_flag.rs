@@ -0,0 +1,369 @@
+use crate::{
+    BotId, GameMode, ModeBot, ModeContext, ModeRequest, Object, ObjectId,
+    ObjectKind,
+};
+use ahash::AHashMap;
+use glam::IVec2;
+use serde::{Deserialize, Serialize};
+
+/// Teams try to steal each other's flags and bring them back to their own
+/// bases.
+///
+/// Each team has a base with a flag (`=`) in it - a team scores a point when
+/// any of its bots drops the enemy flag on (or next to) its own base; the
+/// captured flag then returns to its base.
+///
+/// When a bot carrying a flag dies, the flag returns to its base as well.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CaptureTheFlagMode {
+    bases: Vec<CaptureTheFlagBase>,
+    scores: AHashMap<BotId, u32>,
+    team_scores: AHashMap<u8, u32>,
+}
+
+impl CaptureTheFlagMode {
+    /// Creates a game for given number of teams, with bases placed at random
+    /// positions once the map is ready.
+    pub fn new(teams: u8) -> Self {
+        Self::with_bases((0..teams).map(|_| None))
+    }
+
+    /// Creates a game with bases placed at given positions, one team per base
+    /// (`None` means the base should be placed randomly).
+    pub fn with_bases(
+        bases: impl IntoIterator<Item = impl Into<Option<IVec2>>>,
+    ) -> Self {
+        let bases: Vec<_> = bases
+            .into_iter()
+            .map(|pos| CaptureTheFlagBase {
+                pos: pos.into(),
+                flag: None,
+            })
+            .collect();
+
+        assert!(bases.len() <= u8::MAX as usize);
+
+        Self {
+            bases,
+            scores: Default::default(),
+            team_scores: Default::default(),
+        }
+    }
+
+    pub fn bases(&self) -> impl Iterator<Item = (u8, Option<IVec2>)> + '_ {
+        self.bases
+            .iter()
+            .enumerate()
+            .map(|(team, base)| (team as u8, base.pos))
+    }
+
+    fn tick_base(&mut self, ctxt: &mut ModeContext, team: u8) {
+        let mut base = self.bases[team as usize];
+
+        // If the map has changed under our feet, look for a new place
+        if let Some(pos) = base.pos
+            && !ctxt.map.get(pos).is_floor()
+        {
+            if let Some(flag) = base.flag
+                && ctxt.objects.lookup_at(pos) == Some(flag)
+            {
+                ctxt.remove_object(flag);
+                base.flag = None;
+            }
+
+            base.pos = None;
+        }
+
+        if base.pos.is_none() {
+            base.pos = self.sample_base_pos(ctxt);
+        }
+
+        // If the flag has disappeared (e.g. someone deleted it through the
+        // handle), spawn a new one
+        if let Some(flag) = base.flag
+            && ctxt.objects.get(flag).is_none()
+            && !ctxt.bots.alive.iter().any(|bot| bot.inventory.has(flag))
+        {
+            base.flag = None;
+        }
+
+        if base.flag.is_none()
+            && let Some(pos) = base.pos
+            && ctxt.bots.alive.lookup_at(pos).is_none()
+            && ctxt.objects.lookup_at(pos).is_none()
+        {
+            let flag = Object {
+                kind: ObjectKind::FLAG,
+                meta: [team + 1, 0, 0],
+            };
+
+            base.flag = Some(ctxt.create_object(flag, pos));
+        }
+
+        self.bases[team as usize] = base;
+    }
+
+    fn sample_base_pos(&self, ctxt: &mut ModeContext) -> Option<IVec2> {
+        let others: Vec<_> =
+            self.bases.iter().filter_map(|base| base.pos).collect();
+
+        // Out of a couple of random floor tiles, choose the one that's the
+        // farthest away from other bases
+        (0..256)
+            .map(|_| ctxt.map.sample_pos(ctxt.rng))
+            .filter(|pos| ctxt.map.get(*pos).is_floor())
+            .take(32)
+            .max_by_key(|pos| {
+                others
+                    .iter()
+                    .map(|other| pos.distance_squared(*other))
+                    .min()
+                    .unwrap_or_default()
+            })
+    }
+}
+
+#[typetag::serde(name = "capture-the-flag")]
+impl GameMode for CaptureTheFlagMode {
+    fn scores(&self) -> &AHashMap<BotId, u32> {
+        &self.scores
+    }
+
+    fn team_scores(&self) -> Option<&AHashMap<u8, u32>> {
+        Some(&self.team_scores)
+    }
+
+    fn team_for(&mut self, ctxt: &mut ModeContext) -> Option<u8> {
+        let mut sizes = vec![0; self.bases.len()];
+
+        let teams = ctxt
+            .bots
+            .alive
+            .iter()
+            .map(|bot| bot.team)
+            .chain(ctxt.bots.queued.iter().map(|bot| bot.team));
+
+        for team in teams.flatten() {
+            if let Some(size) = sizes.get_mut(team as usize) {
+                *size += 1;
+            }
+        }
+
+        sizes
+            .into_iter()
+            .enumerate()
+            .min_by_key(|(_, size)| *size)
+            .map(|(team, _)| team as u8)
+    }
+
+    fn tick(&mut self, ctxt: &mut ModeContext) -> Vec<ModeRequest> {
+        for team in 0..self.bases.len() {
+            self.tick_base(ctxt, team as u8);
+        }
+
+        Vec::new()
+    }
+
+    fn on_bot_killed(
+        &mut self,
+        _: &mut ModeContext,
+        killed: ModeBot,
+        killer: Option<BotId>,
+    ) {
+        self.scores.remove(&killed.id());
+
+        if let Some(killer) = killer {
+            *self.scores.entry(killer).or_default() += 1;
+        }
+
+        // Flags carried by the killed bot disappear together with its
+        // inventory - let's mark them as gone, so that they get respawned at
+        // their bases during the next tick
+        for base in &mut self.bases {
+            if let Some(flag) = base.flag
+                && killed.has_object(flag)
+            {
+                base.flag = None;
+            }
+        }
+    }
+
+    fn on_object_dropped(
+        &mut self,
+        ctxt: &mut ModeContext,
+        bot: ModeBot,
+        id: ObjectId,
+        at: IVec2,
+    ) {
+        let Some(bot_team) = bot.team() else {
+            return;
+        };
+
+        let Some(flag_team) =
+            self.bases.iter().position(|base| base.flag == Some(id))
+        else {
+            return;
+        };
+
+        if flag_team == bot_team as usize {
+            return;
+        }
+
+        let Some(base) = self.bases.get(bot_team as usize) else {
+            return;
+        };
+
+        let Some(base_pos) = base.pos else {
+            return;
+        };
+
+        let dist = (at - base_pos).abs();
+
+        if dist.x > 1 || dist.y > 1 {
+            return;
+        }
+
+        *self.scores.entry(bot.id()).or_default() += 1;
+        *self.team_scores.entry(bot_team).or_default() += 1;
+
+        ctxt.remove_object(id);
+        self.bases[flag_team].flag = None;
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+struct CaptureTheFlagBase {
+    pos: Option<IVec2>,
+    flag: Option<ObjectId>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bots, mode, AliveBot, BotAction, Dir, KillBot, Map, World};
+    use glam::ivec2;
+    use indoc::indoc;
+    use itertools::Either;
+
+    fn world() -> (World, BotId) {
+        let (map, _) = Map::parse(
+            indoc! {"
+                |.......|
+                |.......|
+            "}
+            .trim(),
+        )
+        .unwrap();
+
+        let mut world = World::test(map);
+        let id = BotId::new(1);
+
+        world.mode =
+            CaptureTheFlagMode::with_bases([ivec2(1, 0), ivec2(6, 0)]).into();
+
+        world.bots.alive.add(AliveBot {
+            id,
+            pos: ivec2(5, 0),
+            dir: Dir::E,
+            team: Some(0),
+            ..Default::default()
+        });
+
+        mode::tick::run(&mut world);
+
+        (world, id)
+    }
+
+    fn flag_at(world: &World, pos: IVec2) -> Option<ObjectId> {
+        world.objects.lookup_at(pos).filter(|id| {
+            world.objects.get(*id).unwrap().kind == ObjectKind::FLAG
+        })
+    }
+
+    #[test]
+    fn capture() {
+        let (mut world, id) = world();
+        let flag = flag_at(&world, ivec2(6, 0)).unwrap();
+
+        bots::tick::act(&mut world, id, BotAction::ArmPick { at: ivec2(6, 0) });
+
+        for _ in 0..3 {
+            bots::tick::act(
+                &mut world,
+                id,
+                BotAction::MotorMove {
+                    step: ivec2(-1, 0),
+                    len: 1,
+                },
+            );
+        }
+
+        // Dropping the flag away from our base doesn't score
+        bots::tick::act(
+            &mut world,
+            id,
+            BotAction::ArmDrop {
+                at: ivec2(3, 0),
+                idx: 0,
+            },
+        );
+
+        assert!(world.mode.team_scores().unwrap().is_empty());
+
+        bots::tick::act(&mut world, id, BotAction::ArmPick { at: ivec2(3, 0) });
+        world.bots.alive.get_mut(id).unwrap().dir = Dir::S;
+
+        bots::tick::act(
+            &mut world,
+            id,
+            BotAction::ArmDrop {
+                at: ivec2(2, 1),
+                idx: 0,
+            },
+        );
+
+        assert_eq!(Some(&1), world.mode.team_scores().unwrap().get(&0));
+        assert_eq!(Some(&1), world.mode.scores().get(&id));
+        assert!(world.objects.get(flag).is_none());
+
+        // ---
+
+        mode::tick::run(&mut world);
+
+        let new_flag = flag_at(&world, ivec2(6, 0)).unwrap();
+
+        assert_ne!(flag, new_flag);
+    }
+
+    #[test]
+    fn flag_returns_when_carrier_dies() {
+        let (mut world, id) = world();
+        let flag = flag_at(&world, ivec2(6, 0)).unwrap();
+
+        bots::tick::act(&mut world, id, BotAction::ArmPick { at: ivec2(6, 0) });
+
+        assert!(flag_at(&world, ivec2(6, 0)).is_none());
+
+        mode::tick::run(&mut world);
+
+        assert!(flag_at(&world, ivec2(6, 0)).is_none());
+
+        // ---
+
+        bots::kill::run(
+            &mut world,
+            KillBot {
+                killed: Either::Left(id),
+                reason: "killed manually".into(),
+                killer: None,
+            },
+        );
+
+        mode::tick::run(&mut world);
+
+        let new_flag = flag_at(&world, ivec2(6, 0)).unwrap();
+
+        assert_ne!(flag, new_flag);
+        assert!(world.mode.team_scores().unwrap().is_empty());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::BotId;
+use crate::{BotId, GameMode, ModeBot, ModeContext};
 use ahash::AHashMap;
 use serde::{Deserialize, Serialize};
 
@@ -7,20 +7,22 @@ pub struct DeathmatchMode {
     scores: AHashMap<BotId, u32>,
 }
 
-impl DeathmatchMode {
-    pub fn scores(&self) -> &AHashMap<BotId, u32> {
+#[typetag::serde(name = "deathmatch")]
+impl GameMode for DeathmatchMode {
+    fn scores(&self) -> &AHashMap<BotId, u32> {
         &self.scores
     }
 
-    pub fn on_bot_killed(
+    fn on_bot_killed(
         &mut self,
-        killed_id: BotId,
-        killer_id: Option<BotId>,
+        _: &mut ModeContext,
+        killed: ModeBot,
+        killer: Option<BotId>,
     ) {
-        self.scores.remove(&killed_id);
+        self.scores.remove(&killed.id());
 
-        if let Some(killer_id) = killer_id {
-            *self.scores.entry(killer_id).or_default() += 1;
+        if let Some(killer) = killer {
+            *self.scores.entry(killer).or_default() += 1;
         }
     }
 }
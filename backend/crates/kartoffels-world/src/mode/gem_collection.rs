@@ -0,0 +1,274 @@
+use crate::{
+    BotId, Clock, Dir, GameMode, ModeBot, ModeContext, ModeRequest, Object,
+    ObjectId, ObjectKind, Zone,
+};
+use ahash::AHashMap;
+use glam::IVec2;
+use serde::{Deserialize, Serialize};
+
+/// Bots collect gems scattered across the map and carry them back to the
+/// deposit tile.
+///
+/// Every `spawn_every` ticks a new gem appears on a random floor tile (unless
+/// there's already `max_gems` gems lying around); dropping a gem on the
+/// deposit tile awards a point to the bot that carried it there.
+///
+/// When a bot dies, the gems it was carrying fall on its tile (or nearby).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GemCollectionMode {
+    deposit: Option<IVec2>,
+    spawn_every: u32,
+    spawn_in: u32,
+    max_gems: u32,
+    scores: AHashMap<BotId, u32>,
+}
+
+impl GemCollectionMode {
+    /// Creates a game with given gem spawn rate (in ticks) and with given
+    /// limit of gems present on the map at once.
+    pub fn new(spawn_every: u32, max_gems: u32) -> Self {
+        Self {
+            deposit: None,
+            spawn_every,
+            spawn_in: 0,
+            max_gems,
+            scores: Default::default(),
+        }
+    }
+
+    /// Places the deposit at given position (by default it's placed at a
+    /// random position once the map is ready).
+    pub fn with_deposit(mut self, pos: IVec2) -> Self {
+        self.deposit = Some(pos);
+        self
+    }
+
+    pub fn deposit(&self) -> Option<IVec2> {
+        self.deposit
+    }
+
+    fn sample_free_pos(ctxt: &mut ModeContext) -> Option<IVec2> {
+        (0..256).map(|_| ctxt.map.sample_pos(ctxt.rng)).find(|pos| {
+            ctxt.map.get(*pos).is_floor()
+                && ctxt.bots.alive.lookup_at(*pos).is_none()
+                && ctxt.objects.lookup_at(*pos).is_none()
+        })
+    }
+}
+
+#[typetag::serde(name = "gem-collection")]
+impl GameMode for GemCollectionMode {
+    fn scores(&self) -> &AHashMap<BotId, u32> {
+        &self.scores
+    }
+
+    fn zone(&self) -> Option<Zone> {
+        self.deposit.map(|center| Zone { center, radius: 0 })
+    }
+
+    fn tick(&mut self, ctxt: &mut ModeContext) -> Vec<ModeRequest> {
+        // If the map has changed under our feet, look for a new place
+        if let Some(pos) = self.deposit
+            && !ctxt.map.get(pos).is_floor()
+        {
+            self.deposit = None;
+        }
+
+        if self.deposit.is_none() {
+            self.deposit = Self::sample_free_pos(ctxt);
+        }
+
+        if self.spawn_in > 0 {
+            self.spawn_in -= 1;
+            return Vec::new();
+        }
+
+        self.spawn_in = self.spawn_every;
+
+        let gems = ctxt
+            .objects
+            .iter()
+            .filter(|obj| obj.obj.kind == ObjectKind::GEM && obj.pos.is_some())
+            .count();
+
+        if gems >= self.max_gems as usize {
+            return Vec::new();
+        }
+
+        if let Some(pos) = Self::sample_free_pos(ctxt)
+            && Some(pos) != self.deposit
+        {
+            ctxt.create_object(Object::new(ObjectKind::GEM), pos);
+        }
+
+        Vec::new()
+    }
+
+    fn on_bot_killed(
+        &mut self,
+        ctxt: &mut ModeContext,
+        killed: ModeBot,
+        _: Option<BotId>,
+    ) {
+        let gems = killed
+            .objects()
+            .filter(|(_, obj)| obj.kind == ObjectKind::GEM);
+
+        let tiles: Vec<_> = [killed.pos()]
+            .into_iter()
+            .chain(Dir::all().map(|dir| killed.pos() + dir))
+            .filter(|pos| {
+                ctxt.map.get(*pos).is_floor()
+                    && ctxt.bots.alive.lookup_at(*pos).is_none()
+                    && ctxt.objects.lookup_at(*pos).is_none()
+            })
+            .collect();
+
+        // If there's no place left around the bot, the remaining gems are
+        // simply lost
+        for ((id, obj), pos) in gems.zip(tiles) {
+            ctxt.place_object(id, obj, pos);
+        }
+    }
+
+    fn on_object_dropped(
+        &mut self,
+        ctxt: &mut ModeContext,
+        bot: ModeBot,
+        id: ObjectId,
+        at: IVec2,
+    ) {
+        if Some(at) != self.deposit {
+            return;
+        }
+
+        if ctxt.objects.get(id).map(|obj| obj.kind) != Some(ObjectKind::GEM) {
+            return;
+        }
+
+        ctxt.remove_object(id);
+
+        let score = self.scores.entry(bot.id()).or_default();
+
+        *score = score.saturating_add(1);
+    }
+}
+
+impl Default for GemCollectionMode {
+    fn default() -> Self {
+        Self::new(Clock::HZ, 16)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bots, AliveBot, BotAction, Event, KillBot, Map, World};
+    use glam::ivec2;
+    use itertools::Either;
+
+    fn world() -> (World, BotId) {
+        let (map, _) = Map::parse("|.....|").unwrap();
+        let mut world = World::test(map);
+        let id = BotId::new(1);
+
+        world.mode = GemCollectionMode::new(0, 0)
+            .with_deposit(ivec2(1, 0))
+            .into();
+
+        world.bots.alive.add(AliveBot {
+            id,
+            pos: ivec2(3, 0),
+            dir: Dir::E,
+            ..Default::default()
+        });
+
+        (world, id)
+    }
+
+    fn add_gem(world: &mut World, pos: IVec2) -> ObjectId {
+        world.objects.create(
+            &mut world.rng,
+            Object::new(ObjectKind::GEM),
+            Some(pos),
+        )
+    }
+
+    #[test]
+    fn deposit() {
+        let (mut world, id) = world();
+        let gem1 = add_gem(&mut world, ivec2(4, 0));
+        let gem2 = add_gem(&mut world, ivec2(5, 0));
+
+        bots::tick::act(&mut world, id, BotAction::ArmPick { at: ivec2(4, 0) });
+
+        // Dropping the gem outside of the deposit doesn't score
+        bots::tick::act(
+            &mut world,
+            id,
+            BotAction::ArmDrop {
+                at: ivec2(2, 0),
+                idx: 0,
+            },
+        );
+
+        assert_eq!(None, world.mode.scores().get(&id));
+        assert_eq!(Some(gem1), world.objects.lookup_at(ivec2(2, 0)));
+
+        // ---
+
+        bots::tick::act(&mut world, id, BotAction::ArmPick { at: ivec2(2, 0) });
+        bots::tick::act(&mut world, id, BotAction::ArmPick { at: ivec2(5, 0) });
+
+        for idx in [1, 0] {
+            bots::tick::act(
+                &mut world,
+                id,
+                BotAction::ArmDrop {
+                    at: ivec2(1, 0),
+                    idx,
+                },
+            );
+        }
+
+        assert_eq!(Some(&2), world.mode.scores().get(&id));
+        assert!(world.objects.get(gem1).is_none());
+        assert!(world.objects.get(gem2).is_none());
+    }
+
+    #[test]
+    fn drop_on_death() {
+        let (mut world, id) = world();
+        let gem1 = add_gem(&mut world, ivec2(4, 0));
+        let gem2 = add_gem(&mut world, ivec2(5, 0));
+
+        bots::tick::act(&mut world, id, BotAction::ArmPick { at: ivec2(4, 0) });
+        bots::tick::act(&mut world, id, BotAction::ArmPick { at: ivec2(5, 0) });
+
+        bots::kill::run(
+            &mut world,
+            KillBot {
+                killed: Either::Left(id),
+                reason: "killed manually".into(),
+                killer: None,
+            },
+        );
+
+        let mut gems: Vec<_> =
+            world.objects.iter().map(|obj| (obj.id, obj.pos)).collect();
+
+        gems.sort_by_key(|(_, pos)| pos.map(|pos| pos.x));
+
+        // Gems fall on the bot's tile first, then around it
+        assert_eq!(
+            vec![(gem2, Some(ivec2(3, 0))), (gem1, Some(ivec2(4, 0)))],
+            gems
+        );
+
+        for (gem, pos) in gems {
+            let event = Event::ObjectCreated { id: gem, at: pos };
+
+            assert!(world.events.pending().contains(&event));
+        }
+    }
+}
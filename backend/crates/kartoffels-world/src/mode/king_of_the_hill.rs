@@ -0,0 +1,303 @@
+use crate::{BotId, GameMode, ModeBot, ModeContext, ModeRequest};
+use ahash::AHashMap;
+use glam::IVec2;
+use serde::{Deserialize, Serialize};
+use std::hash::Hash;
+
+/// Bots try to occupy a zone on the map - each tick, a point is awarded to
+/// the bot (or to the team) holding it.
+///
+/// A zone is held only when all bots inside it belong to the same team (or
+/// when there's just one, team-less bot inside it) - otherwise the zone is
+/// contested and nobody scores.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct KingOfTheHillMode {
+    zone: Option<Zone>,
+    radius: u8,
+    scores: AHashMap<BotId, u32>,
+    team_scores: AHashMap<u8, u32>,
+}
+
+impl KingOfTheHillMode {
+    /// Creates a game with zone of given radius, placed at a random position
+    /// once the map is ready.
+    pub fn new(radius: u8) -> Self {
+        Self {
+            zone: None,
+            radius,
+            scores: Default::default(),
+            team_scores: Default::default(),
+        }
+    }
+
+    /// Creates a game with zone placed at given position.
+    pub fn with_zone(center: IVec2, radius: u8) -> Self {
+        Self {
+            zone: Some(Zone { center, radius }),
+            ..Self::new(radius)
+        }
+    }
+
+    fn award<K>(scores: &mut AHashMap<K, u32>, key: K)
+    where
+        K: Eq + Hash,
+    {
+        let score = scores.entry(key).or_default();
+
+        *score = score.saturating_add(1);
+    }
+}
+
+#[typetag::serde(name = "king-of-the-hill")]
+impl GameMode for KingOfTheHillMode {
+    fn scores(&self) -> &AHashMap<BotId, u32> {
+        &self.scores
+    }
+
+    fn team_scores(&self) -> Option<&AHashMap<u8, u32>> {
+        Some(&self.team_scores)
+    }
+
+    fn zone(&self) -> Option<Zone> {
+        self.zone
+    }
+
+    fn tick(&mut self, ctxt: &mut ModeContext) -> Vec<ModeRequest> {
+        // If the map has changed under our feet, look for a new place
+        if let Some(zone) = self.zone
+            && !ctxt.map.get(zone.center).is_floor()
+        {
+            self.zone = None;
+        }
+
+        if self.zone.is_none() {
+            self.zone = (0..256)
+                .map(|_| ctxt.map.sample_pos(ctxt.rng))
+                .find(|pos| ctxt.map.get(*pos).is_floor())
+                .map(|center| Zone {
+                    center,
+                    radius: self.radius,
+                });
+        }
+
+        let Some(zone) = self.zone else {
+            return Vec::new();
+        };
+
+        let mut holders = ctxt
+            .bots
+            .alive
+            .iter()
+            .filter(|bot| zone.contains(bot.pos))
+            .map(|bot| (bot.id, bot.team));
+
+        let Some((first_id, first_team)) = holders.next() else {
+            return Vec::new();
+        };
+
+        match first_team {
+            Some(team) => {
+                let mut ids = vec![first_id];
+
+                for (id, other_team) in holders {
+                    if other_team != Some(team) {
+                        return Vec::new();
+                    }
+
+                    ids.push(id);
+                }
+
+                for id in ids {
+                    Self::award(&mut self.scores, id);
+                }
+
+                Self::award(&mut self.team_scores, team);
+            }
+
+            None => {
+                if holders.next().is_none() {
+                    Self::award(&mut self.scores, first_id);
+                }
+            }
+        }
+
+        Vec::new()
+    }
+
+    fn on_bot_killed(
+        &mut self,
+        _: &mut ModeContext,
+        killed: ModeBot,
+        _: Option<BotId>,
+    ) {
+        self.scores.remove(&killed.id());
+    }
+}
+
+/// Square area of the map, spanning `radius` tiles in each direction from
+/// its center.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Zone {
+    pub center: IVec2,
+    pub radius: u8,
+}
+
+impl Zone {
+    pub fn contains(&self, pos: IVec2) -> bool {
+        let dist = (pos - self.center).abs();
+        let radius = self.radius as i32;
+
+        dist.x <= radius && dist.y <= radius
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bots, AliveBot, KillBot, Map, World};
+    use glam::ivec2;
+    use indoc::indoc;
+    use itertools::Either;
+
+    fn world() -> World {
+        let (map, _) = Map::parse(
+            indoc! {"
+                |.......|
+                |.......|
+                |.......|
+            "}
+            .trim(),
+        )
+        .unwrap();
+
+        let mut world = World::test(map);
+
+        world.mode = KingOfTheHillMode::with_zone(ivec2(3, 1), 1).into();
+        world
+    }
+
+    fn add_bot(world: &mut World, id: u64, pos: IVec2, team: Option<u8>) {
+        world.bots.alive.add(AliveBot {
+            id: BotId::new(id),
+            pos,
+            team,
+            ..Default::default()
+        });
+    }
+
+    fn tick(world: &mut World) {
+        let requests = world.mode.tick(&mut ModeContext {
+            bots: &world.bots,
+            events: &mut world.events,
+            map: &world.map,
+            objects: &mut world.objects,
+            rng: &mut world.rng,
+        });
+
+        assert!(requests.is_empty());
+    }
+
+    fn score(world: &World, id: u64) -> Option<u32> {
+        world.mode.scores().get(&BotId::new(id)).copied()
+    }
+
+    fn team_score(world: &World, team: u8) -> Option<u32> {
+        world.mode.team_scores().unwrap().get(&team).copied()
+    }
+
+    #[test]
+    fn single_holder() {
+        let mut world = world();
+
+        add_bot(&mut world, 1, ivec2(2, 0), None);
+        add_bot(&mut world, 2, ivec2(6, 1), None);
+
+        tick(&mut world);
+        tick(&mut world);
+
+        assert_eq!(Some(2), score(&world, 1));
+        assert_eq!(None, score(&world, 2));
+        assert!(world.mode.team_scores().unwrap().is_empty());
+    }
+
+    #[test]
+    fn team_holder() {
+        let mut world = world();
+
+        add_bot(&mut world, 1, ivec2(2, 1), Some(0));
+        add_bot(&mut world, 2, ivec2(4, 2), Some(0));
+        add_bot(&mut world, 3, ivec2(0, 0), Some(1));
+
+        tick(&mut world);
+
+        assert_eq!(Some(1), score(&world, 1));
+        assert_eq!(Some(1), score(&world, 2));
+        assert_eq!(None, score(&world, 3));
+        assert_eq!(Some(1), team_score(&world, 0));
+        assert_eq!(None, team_score(&world, 1));
+    }
+
+    #[test]
+    fn contested() {
+        for teams in [
+            [None, None],
+            [Some(0), Some(1)],
+            [Some(0), None],
+            [None, Some(0)],
+        ] {
+            let mut world = world();
+
+            add_bot(&mut world, 1, ivec2(2, 1), teams[0]);
+            add_bot(&mut world, 2, ivec2(4, 1), teams[1]);
+
+            tick(&mut world);
+
+            assert!(world.mode.scores().is_empty(), "teams: {teams:?}");
+
+            assert!(
+                world.mode.team_scores().unwrap().is_empty(),
+                "teams: {teams:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn score_dropped_on_death() {
+        let mut world = world();
+
+        add_bot(&mut world, 1, ivec2(3, 1), Some(0));
+
+        tick(&mut world);
+
+        assert_eq!(Some(1), score(&world, 1));
+        assert_eq!(Some(1), team_score(&world, 0));
+
+        bots::kill::run(
+            &mut world,
+            KillBot {
+                killed: Either::Left(BotId::new(1)),
+                reason: "killed manually".into(),
+                killer: None,
+            },
+        );
+
+        assert_eq!(None, score(&world, 1));
+
+        // Team keeps the points its members have earned
+        assert_eq!(Some(1), team_score(&world, 0));
+    }
+
+    #[test]
+    fn zone_contains() {
+        let zone = Zone {
+            center: ivec2(10, 10),
+            radius: 2,
+        };
+
+        assert!(zone.contains(ivec2(10, 10)));
+        assert!(zone.contains(ivec2(8, 12)));
+        assert!(zone.contains(ivec2(12, 8)));
+        assert!(!zone.contains(ivec2(13, 10)));
+        assert!(!zone.contains(ivec2(10, 7)));
+    }
+}
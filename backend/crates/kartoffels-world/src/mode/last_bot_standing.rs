@@ -0,0 +1,118 @@
+use crate::{BotId, GameMode, ModeBot, ModeContext, ModeRequest};
+use ahash::AHashMap;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Round-based deathmatch without respawning - the last bot (or team)
+/// standing wins the round.
+///
+/// Bots killed during a round are benched until the round is over; once
+/// there's just one bot (or team) left, the result gets recorded, the map
+/// gets regenerated from world's theme and all of the participating bots get
+/// queued again.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct LastBotStandingMode {
+    scores: AHashMap<BotId, u32>,
+    started: bool,
+    rounds: VecDeque<Round>,
+}
+
+impl LastBotStandingMode {
+    const MAX_ROUNDS: usize = 128;
+}
+
+#[typetag::serde(name = "last-bot-standing")]
+impl GameMode for LastBotStandingMode {
+    fn scores(&self) -> &AHashMap<BotId, u32> {
+        &self.scores
+    }
+
+    fn rounds(&self) -> Option<Vec<Round>> {
+        Some(self.rounds.iter().cloned().collect())
+    }
+
+    fn benches_bots(&self) -> bool {
+        true
+    }
+
+    fn tick(&mut self, ctxt: &mut ModeContext) -> Vec<ModeRequest> {
+        let mut bots = ctxt.bots.alive.iter();
+        let first = bots.next();
+
+        let winner = match first {
+            Some(first) => {
+                let is_single_side = bots
+                    .all(|bot| bot.team.is_some() && bot.team == first.team);
+
+                if !is_single_side {
+                    self.started = true;
+                    return Vec::new();
+                }
+
+                Some(first)
+            }
+
+            None => None,
+        };
+
+        // The round has to actually begin (i.e. there has to be at least two
+        // sides fighting) and everybody waiting in the queue must get a chance
+        // to participate before we can declare anyone as a winner
+        if !self.started || !ctxt.bots.queued.is_empty() {
+            return Vec::new();
+        }
+
+        let nth = self.rounds.front().map_or(1, |round| round.nth + 1);
+
+        let round = Round {
+            nth,
+            winner: winner
+                .filter(|_| ctxt.bots.alive.count() == 1)
+                .map(|bot| bot.id),
+            team: winner.and_then(|bot| bot.team),
+            bots: (ctxt.bots.benched.len() + ctxt.bots.alive.count()) as u32,
+            ended_at: Utc::now(),
+        };
+
+        if self.rounds.len() >= Self::MAX_ROUNDS {
+            self.rounds.pop_back();
+        }
+
+        self.rounds.push_front(round);
+        self.scores.clear();
+        self.started = false;
+
+        vec![ModeRequest::ResetWorld]
+    }
+
+    fn on_bot_killed(
+        &mut self,
+        _: &mut ModeContext,
+        killed: ModeBot,
+        killer: Option<BotId>,
+    ) {
+        self.scores.remove(&killed.id());
+
+        if let Some(killer) = killer {
+            *self.scores.entry(killer).or_default() += 1;
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Round {
+    pub nth: u32,
+
+    /// Bot that won the round; `None` if the round ended with a draw or was
+    /// won by a team with more than one bot alive.
+    pub winner: Option<BotId>,
+
+    /// Team that won the round, if the winner had one.
+    pub team: Option<u8>,
+
+    /// Number of bots that participated in the round.
+    pub bots: u32,
+
+    pub ended_at: DateTime<Utc>,
+}
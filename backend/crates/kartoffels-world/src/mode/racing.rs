@@ -0,0 +1,335 @@
+use crate::{BotId, GameMode, ModeBot, ModeContext, ModeRequest};
+use ahash::AHashMap;
+use anyhow::{anyhow, Result};
+use glam::IVec2;
+use serde::{Deserialize, Serialize};
+
+/// Time trial - bots drive through checkpoints, in order, and compete for the
+/// best lap time.
+///
+/// A lap starts when bot enters the first checkpoint and ends when, after
+/// visiting all of the other checkpoints in order, the bot enters the first
+/// checkpoint again. Lap times are measured in bot ticks (see: `BotTimer`).
+///
+/// Bot's score is the number of laps it has completed.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RacingMode {
+    checkpoints: Vec<IVec2>,
+    len: u8,
+    progress: AHashMap<BotId, RacingProgress>,
+    scores: AHashMap<BotId, u32>,
+    best_laps: AHashMap<BotId, u32>,
+}
+
+impl RacingMode {
+    const MAX_LEADERBOARD_ENTRIES: usize = 128;
+
+    /// Creates a race with given number of checkpoints, placed at random
+    /// positions once the map is ready.
+    ///
+    /// A lap needs at least two checkpoints, otherwise this function returns
+    /// an error.
+    pub fn new(checkpoints: u8) -> Result<Self> {
+        if checkpoints < 2 {
+            return Err(anyhow!(
+                "race needs at least 2 checkpoints, got {checkpoints}"
+            ));
+        }
+
+        Ok(Self {
+            checkpoints: Default::default(),
+            len: checkpoints,
+            progress: Default::default(),
+            scores: Default::default(),
+            best_laps: Default::default(),
+        })
+    }
+
+    /// Creates a race with checkpoints placed at given positions.
+    ///
+    /// Same as with [`Self::new()`], there must be at least two checkpoints -
+    /// and, since checkpoints are counted using `u8`, at most 255 of them.
+    pub fn with_checkpoints(checkpoints: Vec<IVec2>) -> Result<Self> {
+        let len = u8::try_from(checkpoints.len()).map_err(|_| {
+            anyhow!(
+                "race can have at most {} checkpoints, got {}",
+                u8::MAX,
+                checkpoints.len()
+            )
+        })?;
+
+        Ok(Self {
+            checkpoints,
+            ..Self::new(len)?
+        })
+    }
+
+    fn sorted_laps(&self) -> Vec<(BotId, u32)> {
+        let mut entries: Vec<_> = self
+            .best_laps
+            .iter()
+            .map(|(id, ticks)| (*id, *ticks))
+            .collect();
+
+        entries.sort_unstable_by_key(|(id, ticks)| (*ticks, *id));
+        entries
+    }
+
+    fn sample_checkpoints(ctxt: &mut ModeContext, len: u8) -> Vec<IVec2> {
+        let mut checkpoints: Vec<IVec2> = Vec::new();
+
+        for _ in 0..len {
+            // Out of a couple of random floor tiles, choose the one that's the
+            // farthest away from other checkpoints
+            let pos = (0..256)
+                .map(|_| ctxt.map.sample_pos(ctxt.rng))
+                .filter(|pos| {
+                    ctxt.map.get(*pos).is_floor() && !checkpoints.contains(pos)
+                })
+                .take(32)
+                .max_by_key(|pos| {
+                    checkpoints
+                        .iter()
+                        .map(|other| pos.distance_squared(*other))
+                        .min()
+                        .unwrap_or_default()
+                });
+
+            let Some(pos) = pos else {
+                return Default::default();
+            };
+
+            checkpoints.push(pos);
+        }
+
+        checkpoints
+    }
+}
+
+#[typetag::serde(name = "racing")]
+impl GameMode for RacingMode {
+    fn scores(&self) -> &AHashMap<BotId, u32> {
+        &self.scores
+    }
+
+    fn checkpoints(&self) -> &[IVec2] {
+        &self.checkpoints
+    }
+
+    fn leaderboard(&self) -> Option<Vec<(BotId, u32)>> {
+        Some(self.sorted_laps())
+    }
+
+    fn tick(&mut self, ctxt: &mut ModeContext) -> Vec<ModeRequest> {
+        // If the map has changed under our feet, place checkpoints again
+        if self
+            .checkpoints
+            .iter()
+            .any(|pos| !ctxt.map.get(*pos).is_floor())
+        {
+            self.checkpoints.clear();
+            self.progress.clear();
+        }
+
+        if self.checkpoints.is_empty() {
+            self.checkpoints = Self::sample_checkpoints(ctxt, self.len);
+
+            if self.checkpoints.is_empty() {
+                return Vec::new();
+            }
+        }
+
+        for bot in ctxt.bots.alive.iter() {
+            let progress = self.progress.entry(bot.id).or_default();
+
+            let Some(lap_started_at) = progress.lap_started_at else {
+                if bot.pos == self.checkpoints[0] {
+                    progress.lap_started_at = Some(bot.timer.ticks());
+                    progress.next = 1;
+                }
+
+                continue;
+            };
+
+            if bot.pos != self.checkpoints[progress.next] {
+                continue;
+            }
+
+            if progress.next > 0 {
+                progress.next = (progress.next + 1) % self.checkpoints.len();
+                continue;
+            }
+
+            // Lap completed!
+            let time = bot.timer.ticks() - lap_started_at;
+
+            *self.scores.entry(bot.id).or_default() += 1;
+
+            self.best_laps
+                .entry(bot.id)
+                .and_modify(|best| *best = (*best).min(time))
+                .or_insert(time);
+
+            progress.lap_started_at = Some(bot.timer.ticks());
+            progress.next = 1;
+        }
+
+        if self.best_laps.len() > Self::MAX_LEADERBOARD_ENTRIES {
+            let slowest = self.sorted_laps().last().map(|(id, _)| *id);
+
+            if let Some(slowest) = slowest {
+                self.best_laps.remove(&slowest);
+            }
+        }
+
+        Vec::new()
+    }
+
+    fn on_bot_killed(
+        &mut self,
+        _: &mut ModeContext,
+        killed: ModeBot,
+        _: Option<BotId>,
+    ) {
+        self.progress.remove(&killed.id());
+        self.scores.remove(&killed.id());
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+struct RacingProgress {
+    /// Index of the checkpoint the bot has to visit next
+    next: usize,
+
+    /// Bot's tick at which its current lap has started; `None` if the bot
+    /// hasn't entered the first checkpoint yet
+    lap_started_at: Option<u32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bots, AliveBot, BotAction, Dir, Map, World};
+    use glam::ivec2;
+
+    struct Race {
+        world: World,
+        mode: RacingMode,
+        id: BotId,
+    }
+
+    impl Race {
+        fn new() -> Self {
+            let (map, _) = Map::parse("|......|").unwrap();
+            let mut world = World::test(map);
+            let id = BotId::new(1);
+
+            world.bots.alive.add(AliveBot {
+                id,
+                pos: ivec2(1, 0),
+                dir: Dir::E,
+                ..Default::default()
+            });
+
+            let mode = RacingMode::with_checkpoints(vec![
+                ivec2(2, 0),
+                ivec2(4, 0),
+                ivec2(6, 0),
+            ])
+            .unwrap();
+
+            Self { world, mode, id }
+        }
+
+        /// Moves the bot by `dx` tiles, spending `ticks` ticks on each one.
+        fn drive(&mut self, dx: i32, ticks: u32) {
+            for _ in 0..dx.abs() {
+                let bot = self.world.bots.alive.get_mut(self.id).unwrap();
+
+                for _ in 0..ticks {
+                    bot.timer.tick();
+                }
+
+                bots::tick::act(
+                    &mut self.world,
+                    self.id,
+                    BotAction::MotorMove {
+                        step: ivec2(dx.signum(), 0),
+                        len: 1,
+                    },
+                );
+
+                self.mode.tick(&mut ModeContext {
+                    bots: &self.world.bots,
+                    events: &mut self.world.events,
+                    map: &self.world.map,
+                    objects: &mut self.world.objects,
+                    rng: &mut self.world.rng,
+                });
+            }
+        }
+
+        fn progress(&self) -> (usize, Option<u32>) {
+            let progress = &self.mode.progress[&self.id];
+
+            (progress.next, progress.lap_started_at)
+        }
+    }
+
+    #[test]
+    fn laps() {
+        let mut race = Race::new();
+
+        race.drive(1, 10);
+
+        assert_eq!((1, Some(10)), race.progress());
+
+        race.drive(2, 10);
+
+        assert_eq!((2, Some(10)), race.progress());
+
+        // Going back doesn't count, checkpoints must be visited in order
+        race.drive(-2, 10);
+        race.drive(2, 10);
+
+        assert_eq!((2, Some(10)), race.progress());
+
+        race.drive(2, 10);
+
+        assert_eq!((0, Some(10)), race.progress());
+        assert!(race.mode.scores.is_empty());
+
+        race.drive(-4, 10);
+
+        assert_eq!((1, Some(130)), race.progress());
+        assert_eq!(Some(&1), race.mode.scores.get(&race.id));
+        assert_eq!(Some(vec![(race.id, 120)]), race.mode.leaderboard());
+
+        // ---
+
+        race.drive(4, 1);
+        race.drive(-4, 1);
+
+        assert_eq!((1, Some(138)), race.progress());
+        assert_eq!(Some(&2), race.mode.scores.get(&race.id));
+        assert_eq!(Some(vec![(race.id, 8)]), race.mode.leaderboard());
+
+        // ---
+
+        race.drive(4, 10);
+        race.drive(-4, 10);
+
+        assert_eq!(Some(&3), race.mode.scores.get(&race.id));
+        assert_eq!(Some(vec![(race.id, 8)]), race.mode.leaderboard());
+    }
+
+    #[test]
+    fn err_too_few_checkpoints() {
+        let err = RacingMode::with_checkpoints(vec![ivec2(1, 1)])
+            .unwrap_err()
+            .to_string();
+
+        assert_eq!("race needs at least 2 checkpoints, got 1", err);
+        assert!(RacingMode::new(0).is_err());
+    }
+}
@@ -0,0 +1,3 @@
+pub mod reset;
+pub mod scores;
+pub mod tick;
@@ -0,0 +1,110 @@
+use crate::{bots, theme, Event, KillBot, World};
+use itertools::Either;
+use tracing::{info, warn};
+
+pub fn run(world: &mut World) {
+    info!("resetting world");
+
+    // Kill bots that have survived the round - they get benched together with
+    // the rest of participants
+    let ids: Vec<_> = world.bots.alive.iter().map(|bot| bot.id).collect();
+
+    for id in ids {
+        bots::kill::run(
+            world,
+            KillBot {
+                killed: Either::Left(id),
+                reason: "round over".into(),
+                killer: None,
+            },
+        );
+    }
+
+    let objects: Vec<_> = world
+        .objects
+        .iter()
+        .filter(|obj| obj.pos.is_some())
+        .map(|obj| obj.id)
+        .collect();
+
+    for id in objects {
+        world.objects.remove(id);
+        world.events.add(Event::ObjectDeleted { id });
+    }
+
+    if let Some(theme) = &world.theme {
+        match theme.create_map(&mut world.rng) {
+            Ok(map) => {
+                world.map = map;
+                world.events.add(Event::MapChanged);
+
+                theme::apply::run(world);
+            }
+
+            Err(err) => {
+                warn!("couldn't regenerate map: {err:?}");
+            }
+        }
+    }
+
+    for mut bot in std::mem::take(&mut world.bots.benched) {
+        // If there's no place in the queue, the bot remains benched (and
+        // visible as dead) until the next reset
+        if world.bots.queued.len() >= world.policy.max_queued_bots {
+            world.bots.benched.push(bot);
+            continue;
+        }
+
+        world.bots.dead.remove(bot.id);
+
+        bot.requeued = true;
+        bot.events.add("requeued for the next round");
+
+        world.bots.queued.push(bot);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AliveBot, BotId, LastBotStandingMode, Map, World};
+    use glam::{ivec2, uvec2};
+
+    #[test]
+    fn full_queue() {
+        let mut world = World::test(Map::new(uvec2(8, 1)));
+
+        world.mode = LastBotStandingMode::default().into();
+        world.policy.max_queued_bots = 2;
+
+        for id in 1..=3 {
+            world.bots.alive.add(AliveBot {
+                id: BotId::new(id),
+                pos: ivec2(id as i32, 0),
+                ..Default::default()
+            });
+        }
+
+        super::run(&mut world);
+
+        assert_eq!(2, world.bots.queued.len());
+        assert!(world.bots.queued.contains(BotId::new(1)));
+        assert!(world.bots.queued.contains(BotId::new(2)));
+        assert!(!world.bots.dead.contains(BotId::new(1)));
+        assert!(!world.bots.dead.contains(BotId::new(2)));
+
+        assert_eq!(1, world.bots.benched.len());
+        assert_eq!(BotId::new(3), world.bots.benched[0].id);
+        assert!(world.bots.dead.contains(BotId::new(3)));
+
+        // ---
+
+        world.bots.queued.pop();
+        world.bots.queued.pop();
+
+        super::run(&mut world);
+
+        assert!(world.bots.benched.is_empty());
+        assert!(world.bots.queued.contains(BotId::new(3)));
+        assert!(!world.bots.dead.contains(BotId::new(3)));
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{BotId, Event, World};
+use ahash::AHashMap;
+
+/// Announces changes to the scores, as reported by the game mode.
+///
+/// Game modes update their scores in many different places (when a bot gets
+/// killed, when it picks an object etc.), so instead of tracking each of
+/// them, we simply compare the scores with what we've seen the last time.
+#[derive(Debug, Default)]
+pub struct State {
+    scores: AHashMap<BotId, u32>,
+}
+
+pub fn run(world: &mut World, state: &mut State) {
+    if !world.events.is_enabled() {
+        return;
+    }
+
+    let scores = world.mode.scores();
+
+    for (&id, &score) in scores {
+        if state.scores.get(&id) != Some(&score) {
+            world.events.add(Event::ScoreChanged { id, score });
+        }
+    }
+
+    for &id in state.scores.keys() {
+        if !scores.contains_key(&id) {
+            world.events.add(Event::ScoreChanged { id, score: 0 });
+        }
+    }
+
+    if state.scores != *scores {
+        state.scores.clone_from(scores);
+    }
+}
@@ -0,0 +1,33 @@
+use crate::{bots, KillBot, ModeContext, ModeRequest, World};
+use itertools::Either;
+
+pub fn run(world: &mut World) {
+    for _ in 0..world.clock.steps() {
+        let requests = world.mode.tick(&mut ModeContext {
+            bots: &world.bots,
+            events: &mut world.events,
+            map: &world.map,
+            objects: &mut world.objects,
+            rng: &mut world.rng,
+        });
+
+        for request in requests {
+            match request {
+                ModeRequest::KillBot { id, reason } => {
+                    bots::kill::run(
+                        world,
+                        KillBot {
+                            killed: Either::Left(id),
+                            reason,
+                            killer: None,
+                        },
+                    );
+                }
+
+                ModeRequest::ResetWorld => {
+                    super::reset::run(world);
+                }
+            }
+        }
+    }
+}
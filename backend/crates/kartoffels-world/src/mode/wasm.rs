@@ -0,0 +1,897 @@
+use crate::{
+    BotId, GameMode, Map, ModeBot, ModeContext, ModeRequest, Object, ObjectId,
+};
+use ahash::AHashMap;
+use anyhow::{anyhow, Context, Result};
+use glam::{ivec2, IVec2};
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use tracing::{debug, warn};
+use wasmi::core::{Pages, F32, F64};
+use wasmi::{
+    Caller, Config, Engine, Extern, Instance, Linker, Module, Mutability,
+    Store, StoreLimits, StoreLimitsBuilder, Val, WasmParams,
+};
+
+/// Game mode implemented by a WebAssembly module.
+///
+/// The module is run by an interpreter, sandboxed from the rest of the
+/// system: it doesn't have access to network, files etc., it just gets
+/// notified about things happening in the world through functions it exports:
+///
+/// - `init()`,
+/// - `tick()`,
+/// - `on_bot_spawned(bot: i64)`,
+/// - `on_bot_killed(bot: i64, killer: i64)`,
+/// - `on_bot_moved(bot: i64, x: i32, y: i32)`,
+/// - `on_object_picked(bot: i64, obj: i64)`,
+/// - `on_object_dropped(bot: i64, obj: i64, x: i32, y: i32)`,
+///
+/// ... and issues commands through functions it imports from the
+/// `kartoffels` namespace:
+///
+/// - `rand() -> i32`,
+/// - `map_width() -> i32`,
+/// - `map_height() -> i32`,
+/// - `tile_at(x: i32, y: i32) -> i32`,
+/// - `bot_at(x: i32, y: i32) -> i64`,
+/// - `object_at(x: i32, y: i32) -> i64`,
+/// - `create_object(kind: i32, x: i32, y: i32)`,
+/// - `remove_object(obj: i64)`,
+/// - `kill_bot(bot: i64)`,
+/// - `set_score(bot: i64, score: i32)`,
+/// - `reset_world()`,
+/// - `log(ptr: i32, len: i32)`.
+///
+/// All exports are optional; ids are passed as numbers, with zero meaning
+/// "none".
+///
+/// Functions that look at the world (`tile_at()`, `bot_at()` etc.) see it as
+/// of the latest `tick()` - e.g. `on_bot_moved()` gets the bot's new position
+/// through its parameters, but `bot_at()` still reports the bot at its old
+/// place until the next tick.
+///
+/// Module's state is persisted (and carried over when the world gets cloned,
+/// e.g. for a fork or a replay) through its exported `memory` and exported
+/// mutable globals - anything else, like non-exported globals, gets reset to
+/// its initial value, so modules shouldn't keep state there between calls.
+///
+/// Module can use up to [`WasmMode::MAX_MEMORY`] bytes of memory - if it
+/// tries to grow past that or runs out of fuel, it traps and gets disabled.
+#[derive(Serialize, Deserialize)]
+pub struct WasmMode {
+    #[serde(with = "serde_bytes")]
+    code: Vec<u8>,
+    scores: AHashMap<BotId, u32>,
+
+    #[serde(default)]
+    runtime: WasmRuntimeState,
+
+    #[serde(skip)]
+    requests: Vec<ModeRequest>,
+}
+
+impl WasmMode {
+    /// Maximum number of instructions a single call into the module can
+    /// execute.
+    const FUEL: u64 = 1_000_000;
+
+    /// Maximum size of module's memory, in bytes.
+    pub const MAX_MEMORY: usize = 1024 * 1024;
+
+    pub fn new(code: Vec<u8>) -> Result<Self> {
+        WasmRuntime::new(&code)?;
+
+        Ok(Self {
+            code,
+            scores: Default::default(),
+            runtime: Default::default(),
+            requests: Default::default(),
+        })
+    }
+
+    fn call<P>(&mut self, ctxt: &mut ModeContext, name: &str, params: P)
+    where
+        P: WasmParams,
+    {
+        self.instantiate(ctxt);
+        self.invoke(ctxt, name, params);
+    }
+
+    fn instantiate(&mut self, ctxt: &mut ModeContext) {
+        let WasmRuntimeState::Pending(image) = &mut self.runtime else {
+            return;
+        };
+
+        let image = image.take();
+
+        let runtime = WasmRuntime::new(&self.code).and_then(|mut runtime| {
+            if let Some(image) = &image {
+                runtime.restore(image).context("couldn't restore module")?;
+            }
+
+            Ok(runtime)
+        });
+
+        match runtime {
+            Ok(mut runtime) => {
+                runtime.sync(ctxt);
+
+                self.runtime = WasmRuntimeState::Ready(Box::new(runtime));
+
+                if image.is_none() {
+                    self.invoke(ctxt, "init", ());
+                }
+            }
+
+            Err(err) => {
+                warn!("couldn't instantiate module: {err:?}");
+
+                self.runtime = WasmRuntimeState::Failed;
+            }
+        }
+    }
+
+    fn invoke<P>(&mut self, ctxt: &mut ModeContext, name: &str, params: P)
+    where
+        P: WasmParams,
+    {
+        let WasmRuntimeState::Ready(runtime) = &mut self.runtime else {
+            return;
+        };
+
+        match runtime.call(ctxt, name, params) {
+            Ok(cmds) => {
+                self.apply(ctxt, cmds);
+            }
+
+            Err(err) => {
+                warn!("module crashed in `{name}()`, disabling it: {err}");
+
+                self.runtime = WasmRuntimeState::Failed;
+            }
+        }
+    }
+
+    fn apply(&mut self, ctxt: &mut ModeContext, cmds: Vec<WasmCmd>) {
+        for cmd in cmds {
+            match cmd {
+                WasmCmd::CreateObject { obj, pos } => {
+                    if ctxt.map.get(pos).is_floor()
+                        && ctxt.bot_at(pos).is_none()
+                        && ctxt.object_at(pos).is_none()
+                    {
+                        ctxt.create_object(obj, pos);
+                    }
+                }
+
+                WasmCmd::RemoveObject { id } => {
+                    ctxt.remove_object(id);
+                }
+
+                WasmCmd::KillBot { id } => {
+                    self.requests.push(ModeRequest::KillBot {
+                        id,
+                        reason: "killed by the rules".into(),
+                    });
+                }
+
+                WasmCmd::SetScore { id, score } => {
+                    self.scores.insert(id, score);
+                }
+
+                WasmCmd::ResetWorld => {
+                    self.requests.push(ModeRequest::ResetWorld);
+                }
+            }
+        }
+    }
+}
+
+#[typetag::serde(name = "wasm")]
+impl GameMode for WasmMode {
+    fn scores(&self) -> &AHashMap<BotId, u32> {
+        &self.scores
+    }
+
+    fn tick(&mut self, ctxt: &mut ModeContext) -> Vec<ModeRequest> {
+        self.instantiate(ctxt);
+
+        if let WasmRuntimeState::Ready(runtime) = &mut self.runtime {
+            runtime.sync(ctxt);
+        }
+
+        self.invoke(ctxt, "tick", ());
+
+        std::mem::take(&mut self.requests)
+    }
+
+    fn on_bot_spawned(&mut self, ctxt: &mut ModeContext, bot: ModeBot) {
+        self.call(ctxt, "on_bot_spawned", (bot_id(bot.id()),));
+    }
+
+    fn on_bot_killed(
+        &mut self,
+        ctxt: &mut ModeContext,
+        killed: ModeBot,
+        killer: Option<BotId>,
+    ) {
+        self.call(
+            ctxt,
+            "on_bot_killed",
+            (bot_id(killed.id()), killer.map_or(0, bot_id)),
+        );
+    }
+
+    fn on_bot_moved(&mut self, ctxt: &mut ModeContext, bot: ModeBot, _: IVec2) {
+        self.call(
+            ctxt,
+            "on_bot_moved",
+            (bot_id(bot.id()), bot.pos().x, bot.pos().y),
+        );
+    }
+
+    fn on_object_picked(
+        &mut self,
+        ctxt: &mut ModeContext,
+        bot: ModeBot,
+        id: ObjectId,
+    ) {
+        self.call(ctxt, "on_object_picked", (bot_id(bot.id()), object_id(id)));
+    }
+
+    fn on_object_dropped(
+        &mut self,
+        ctxt: &mut ModeContext,
+        bot: ModeBot,
+        id: ObjectId,
+        at: IVec2,
+    ) {
+        self.call(
+            ctxt,
+            "on_object_dropped",
+            (bot_id(bot.id()), object_id(id), at.x, at.y),
+        );
+    }
+}
+
+impl Clone for WasmMode {
+    fn clone(&self) -> Self {
+        Self {
+            code: self.code.clone(),
+            scores: self.scores.clone(),
+            runtime: self.runtime.clone(),
+            requests: self.requests.clone(),
+        }
+    }
+}
+
+impl fmt::Debug for WasmMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("WasmMode")
+            .field("code", &format_args!("[{} bytes]", self.code.len()))
+            .field("scores", &self.scores)
+            .finish()
+    }
+}
+
+enum WasmRuntimeState {
+    /// Module hasn't been instantiated yet - if there's an image, the module
+    /// gets restored from it, otherwise its `init()` gets called.
+    Pending(Option<WasmImage>),
+
+    Ready(Box<WasmRuntime>),
+
+    /// Module couldn't be instantiated or it has crashed.
+    Failed,
+}
+
+impl Default for WasmRuntimeState {
+    fn default() -> Self {
+        Self::Pending(None)
+    }
+}
+
+impl Clone for WasmRuntimeState {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Pending(image) => Self::Pending(image.clone()),
+            Self::Ready(runtime) => Self::Pending(Some(runtime.image())),
+            Self::Failed => Self::Failed,
+        }
+    }
+}
+
+impl Serialize for WasmRuntimeState {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let repr = match self {
+            Self::Pending(None) => SerializedWasmRuntimeState::Pending,
+            Self::Pending(Some(image)) => {
+                SerializedWasmRuntimeState::Running(image.clone())
+            }
+            Self::Ready(runtime) => {
+                SerializedWasmRuntimeState::Running(runtime.image())
+            }
+            Self::Failed => SerializedWasmRuntimeState::Failed,
+        };
+
+        repr.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for WasmRuntimeState {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(
+            match SerializedWasmRuntimeState::deserialize(deserializer)? {
+                SerializedWasmRuntimeState::Pending => Self::Pending(None),
+                SerializedWasmRuntimeState::Running(image) => {
+                    Self::Pending(Some(image))
+                }
+                SerializedWasmRuntimeState::Failed => Self::Failed,
+            },
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum SerializedWasmRuntimeState {
+    Pending,
+    Running(WasmImage),
+    Failed,
+}
+
+/// Module's state - its memory and exported mutable globals.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct WasmImage {
+    #[serde(with = "serde_bytes")]
+    memory: Vec<u8>,
+    globals: Vec<(String, WasmGlobal)>,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum WasmGlobal {
+    I32(i32),
+    I64(i64),
+    F32(u32),
+    F64(u64),
+}
+
+impl WasmGlobal {
+    fn new(val: Val) -> Option<Self> {
+        match val {
+            Val::I32(val) => Some(Self::I32(val)),
+            Val::I64(val) => Some(Self::I64(val)),
+            Val::F32(val) => Some(Self::F32(val.to_bits())),
+            Val::F64(val) => Some(Self::F64(val.to_bits())),
+            Val::FuncRef(_) | Val::ExternRef(_) => None,
+        }
+    }
+
+    fn into_val(self) -> Val {
+        match self {
+            Self::I32(val) => Val::I32(val),
+            Self::I64(val) => Val::I64(val),
+            Self::F32(val) => Val::F32(F32::from_bits(val)),
+            Self::F64(val) => Val::F64(F64::from_bits(val)),
+        }
+    }
+}
+
+struct WasmRuntime {
+    store: Store<WasmHost>,
+    instance: Instance,
+}
+
+impl WasmRuntime {
+    fn new(code: &[u8]) -> Result<Self> {
+        let engine = Engine::new(Config::default().consume_fuel(true));
+        let module =
+            Module::new(&engine, code).context("couldn't parse module")?;
+
+        let mut linker = Linker::new(&engine);
+
+        Self::link(&mut linker)?;
+
+        let host = WasmHost::new(&module);
+        let mut store = Store::new(&engine, host);
+
+        store.limiter(|host| &mut host.limits);
+
+        // Unwrap-safety: We've just enabled fuel metering
+        store.set_fuel(WasmMode::FUEL).unwrap();
+
+        let instance = linker
+            .instantiate(&mut store, &module)
+            .context("couldn't link module")?
+            .start(&mut store)
+            .context("couldn't start module")?;
+
+        Ok(Self { store, instance })
+    }
+
+    fn link(linker: &mut Linker<WasmHost>) -> Result<()> {
+        linker.func_wrap(
+            "kartoffels",
+            "rand",
+            |mut caller: Caller<WasmHost>| -> i32 {
+                caller.data_mut().rng.gen()
+            },
+        )?;
+
+        linker.func_wrap(
+            "kartoffels",
+            "map_width",
+            |caller: Caller<WasmHost>| -> i32 {
+                caller.data().map().size().x as i32
+            },
+        )?;
+
+        linker.func_wrap(
+            "kartoffels",
+            "map_height",
+            |caller: Caller<WasmHost>| -> i32 {
+                caller.data().map().size().y as i32
+            },
+        )?;
+
+        linker.func_wrap(
+            "kartoffels",
+            "tile_at",
+            |caller: Caller<WasmHost>, x: i32, y: i32| -> i32 {
+                caller.data().map().get(ivec2(x, y)).kind as i32
+            },
+        )?;
+
+        linker.func_wrap(
+            "kartoffels",
+            "bot_at",
+            |caller: Caller<WasmHost>, x: i32, y: i32| -> i64 {
+                caller
+                    .data()
+                    .bots
+                    .as_ref()
+                    .and_then(|bots| bots.get(&ivec2(x, y)))
+                    .map_or(0, |id| bot_id(*id))
+            },
+        )?;
+
+        linker.func_wrap(
+            "kartoffels",
+            "object_at",
+            |caller: Caller<WasmHost>, x: i32, y: i32| -> i64 {
+                caller
+                    .data()
+                    .objects
+                    .as_ref()
+                    .and_then(|objects| objects.get(&ivec2(x, y)))
+                    .map_or(0, |id| object_id(*id))
+            },
+        )?;
+
+        linker.func_wrap(
+            "kartoffels",
+            "create_object",
+            |mut caller: Caller<WasmHost>, kind: i32, x: i32, y: i32| {
+                caller.data_mut().cmds.push(WasmCmd::CreateObject {
+                    obj: Object::new(kind as u8),
+                    pos: ivec2(x, y),
+                });
+            },
+        )?;
+
+        linker.func_wrap(
+            "kartoffels",
+            "remove_object",
+            |mut caller: Caller<WasmHost>, id: i64| {
+                if let Some(id) = ObjectId::try_new(id as u64) {
+                    caller.data_mut().cmds.push(WasmCmd::RemoveObject { id });
+                }
+            },
+        )?;
+
+        linker.func_wrap(
+            "kartoffels",
+            "kill_bot",
+            |mut caller: Caller<WasmHost>, id: i64| {
+                if let Some(id) = BotId::try_new(id as u64) {
+                    caller.data_mut().cmds.push(WasmCmd::KillBot { id });
+                }
+            },
+        )?;
+
+        linker.func_wrap(
+            "kartoffels",
+            "set_score",
+            |mut caller: Caller<WasmHost>, id: i64, score: i32| {
+                if let Some(id) = BotId::try_new(id as u64) {
+                    caller.data_mut().cmds.push(WasmCmd::SetScore {
+                        id,
+                        score: score.max(0) as u32,
+                    });
+                }
+            },
+        )?;
+
+        linker.func_wrap(
+            "kartoffels",
+            "reset_world",
+            |mut caller: Caller<WasmHost>| {
+                caller.data_mut().cmds.push(WasmCmd::ResetWorld);
+            },
+        )?;
+
+        linker.func_wrap(
+            "kartoffels",
+            "log",
+            |caller: Caller<WasmHost>, ptr: i32, len: i32| {
+                let Some(memory) =
+                    caller.get_export("memory").and_then(Extern::into_memory)
+                else {
+                    return;
+                };
+
+                let mut msg = vec![0; len.clamp(0, 1024) as usize];
+
+                if memory.read(&caller, ptr as usize, &mut msg).is_ok() {
+                    debug!("module says: {}", String::from_utf8_lossy(&msg));
+                }
+            },
+        )?;
+
+        Ok(())
+    }
+
+    fn call<P>(
+        &mut self,
+        ctxt: &mut ModeContext,
+        name: &str,
+        params: P,
+    ) -> Result<Vec<WasmCmd>>
+    where
+        P: WasmParams,
+    {
+        let Ok(func) = self.instance.get_typed_func::<P, ()>(&self.store, name)
+        else {
+            return Ok(Default::default());
+        };
+
+        self.store.data_mut().rng = SmallRng::seed_from_u64(ctxt.rng.gen());
+
+        // Unwrap-safety: Fuel metering is enabled in `Self::new()`
+        self.store.set_fuel(WasmMode::FUEL).unwrap();
+
+        func.call(&mut self.store, params)?;
+
+        Ok(std::mem::take(&mut self.store.data_mut().cmds))
+    }
+
+    /// Refreshes the world as seen by the module - this is done once a tick
+    /// instead of before each call, since hooks such as `on_bot_moved()` can
+    /// get called for every bot.
+    fn sync(&mut self, ctxt: &ModeContext) {
+        self.store.data_mut().sync(ctxt);
+    }
+
+    fn image(&self) -> WasmImage {
+        let memory = self
+            .instance
+            .get_memory(&self.store, "memory")
+            .map(|memory| memory.data(&self.store).to_vec())
+            .unwrap_or_default();
+
+        let globals = self
+            .instance
+            .exports(&self.store)
+            .filter_map(|export| {
+                let name = export.name().to_owned();
+                let global = export.into_global()?;
+
+                if global.ty(&self.store).mutability() != Mutability::Var {
+                    return None;
+                }
+
+                Some((name, WasmGlobal::new(global.get(&self.store))?))
+            })
+            .collect();
+
+        WasmImage { memory, globals }
+    }
+
+    fn restore(&mut self, image: &WasmImage) -> Result<()> {
+        if let Some(memory) = self.instance.get_memory(&self.store, "memory") {
+            let curr = memory.data(&self.store).len();
+
+            if image.memory.len() > curr {
+                let pages = (image.memory.len() - curr).div_ceil(PAGE_SIZE);
+
+                let pages = u32::try_from(pages)
+                    .ok()
+                    .and_then(Pages::new)
+                    .context("image is too large")?;
+
+                memory
+                    .grow(&mut self.store, pages)
+                    .map_err(|err| anyhow!("couldn't grow memory: {err}"))?;
+            }
+
+            memory
+                .write(&mut self.store, 0, &image.memory)
+                .map_err(|err| anyhow!("couldn't write memory: {err}"))?;
+        }
+
+        for (name, global) in &image.globals {
+            let global_ref = self
+                .instance
+                .get_global(&self.store, name)
+                .ok_or_else(|| anyhow!("unknown global: {name}"))?;
+
+            global_ref
+                .set(&mut self.store, global.into_val())
+                .map_err(|err| anyhow!("couldn't set `{name}`: {err}"))?;
+        }
+
+        Ok(())
+    }
+}
+
+const PAGE_SIZE: usize = 64 * 1024;
+
+/// State visible to the module - since the module outlives `ModeContext`,
+/// this is a copy of the world, refreshed on each tick.
+///
+/// Only the parts module can actually look at (judging by its imports) are
+/// copied, and the map is copied only when it changes.
+struct WasmHost {
+    map: Option<Map>,
+    bots: Option<AHashMap<IVec2, BotId>>,
+    objects: Option<AHashMap<IVec2, ObjectId>>,
+    rng: SmallRng,
+    cmds: Vec<WasmCmd>,
+    limits: StoreLimits,
+}
+
+impl WasmHost {
+    fn new(module: &Module) -> Self {
+        let imports: Vec<_> = module
+            .imports()
+            .filter(|import| import.module() == "kartoffels")
+            .map(|import| import.name())
+            .collect();
+
+        let imports = |names: &[&str]| {
+            imports.iter().any(|import| names.contains(import))
+        };
+
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(WasmMode::MAX_MEMORY)
+            .memories(1)
+            .tables(1)
+            .instances(1)
+            .trap_on_grow_failure(true)
+            .build();
+
+        Self {
+            map: imports(&["map_width", "map_height", "tile_at"])
+                .then(Default::default),
+            bots: imports(&["bot_at"]).then(Default::default),
+            objects: imports(&["object_at"]).then(Default::default),
+            rng: SmallRng::seed_from_u64(0),
+            cmds: Default::default(),
+            limits,
+        }
+    }
+
+    fn sync(&mut self, ctxt: &ModeContext) {
+        if let Some(map) = &mut self.map
+            && map.rev() != ctxt.map.rev()
+        {
+            *map = ctxt.map.clone();
+        }
+
+        if let Some(bots) = &mut self.bots {
+            bots.clear();
+            bots.extend(ctxt.bots().map(|bot| (bot.pos(), bot.id())));
+        }
+
+        if let Some(objects) = &mut self.objects {
+            objects.clear();
+            objects.extend(ctxt.objects().map(|(id, _, pos)| (pos, id)));
+        }
+    }
+
+    fn map(&self) -> &Map {
+        // Unwrap-safety: Map is synced if module imports any function that
+        // uses it
+        self.map.as_ref().unwrap()
+    }
+}
+
+#[derive(Debug)]
+enum WasmCmd {
+    CreateObject { obj: Object, pos: IVec2 },
+    RemoveObject { id: ObjectId },
+    KillBot { id: BotId },
+    SetScore { id: BotId, score: u32 },
+    ResetWorld,
+}
+
+fn bot_id(id: BotId) -> i64 {
+    id.get().get() as i64
+}
+
+fn object_id(id: ObjectId) -> i64 {
+    id.get().get() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bots, mode, AliveBot, BotAction, World};
+
+    fn tick(world: &mut World, mode: &mut WasmMode) {
+        mode.tick(&mut ModeContext {
+            bots: &world.bots,
+            events: &mut world.events,
+            map: &world.map,
+            objects: &mut world.objects,
+            rng: &mut world.rng,
+        });
+    }
+
+    fn score(mode: &WasmMode) -> Option<u32> {
+        mode.scores.get(&BotId::new(1)).copied()
+    }
+
+    #[test]
+    fn persistence() {
+        let code = wat::parse_str(
+            r#"
+            (module
+              (import "kartoffels" "set_score" (func $set_score (param i64 i32)))
+
+              (memory (export "memory") 1)
+              (global $ticks (export "ticks") (mut i32) (i32.const 0))
+
+              (func (export "init")
+                (i32.store (i32.const 0) (i32.const 100)))
+
+              (func (export "tick")
+                (global.set $ticks (i32.add (global.get $ticks) (i32.const 1)))
+                (i32.store (i32.const 0) (i32.add (i32.load (i32.const 0)) (i32.const 2)))
+                (call $set_score
+                  (i64.const 1)
+                  (i32.add (global.get $ticks) (i32.load (i32.const 0))))))
+            "#,
+        )
+        .unwrap();
+
+        let mut world = World::test(Map::parse("|...|").unwrap().0);
+        let mut mode = WasmMode::new(code).unwrap();
+
+        tick(&mut world, &mut mode);
+        tick(&mut world, &mut mode);
+
+        assert_eq!(Some(106), score(&mode));
+
+        // ---
+
+        let mut cloned = mode.clone();
+
+        tick(&mut world, &mut cloned);
+
+        assert_eq!(Some(109), score(&cloned));
+
+        // ---
+
+        let mut buf = Vec::new();
+
+        ciborium::into_writer(&mode, &mut buf).unwrap();
+
+        let mut loaded: WasmMode = ciborium::from_reader(&buf[..]).unwrap();
+
+        tick(&mut world, &mut loaded);
+
+        assert_eq!(Some(109), score(&loaded));
+    }
+
+    #[test]
+    fn sync() {
+        let code = wat::parse_str(
+            r#"
+            (module
+              (import "kartoffels" "bot_at" (func $bot_at (param i32 i32) (result i64)))
+              (import "kartoffels" "set_score" (func $set_score (param i64 i32)))
+
+              (func (export "tick")
+                (call $set_score (call $bot_at (i32.const 2) (i32.const 0)) (i32.const 1)))
+
+              (func (export "on_bot_moved") (param i64 i32 i32)
+                (call $set_score (call $bot_at (local.get 1) (local.get 2)) (i32.const 2))))
+            "#,
+        )
+        .unwrap();
+
+        let mut world = World::test(Map::parse("|...|").unwrap().0);
+        let mut mode = WasmMode::new(code).unwrap();
+
+        world.bots.alive.add(AliveBot {
+            id: BotId::new(1),
+            pos: ivec2(1, 0),
+            ..Default::default()
+        });
+
+        tick(&mut world, &mut mode);
+
+        assert_eq!(None, score(&mode));
+
+        // ---
+
+        world.mode = mode.into();
+
+        bots::tick::act(
+            &mut world,
+            BotId::new(1),
+            BotAction::MotorMove {
+                step: ivec2(1, 0),
+                len: 1,
+            },
+        );
+
+        // Module sees the world as of the latest tick, so it doesn't find the
+        // bot at its new position yet
+        assert_eq!(None, world.mode.scores().get(&BotId::new(1)));
+
+        mode::tick::run(&mut world);
+
+        assert_eq!(Some(&1), world.mode.scores().get(&BotId::new(1)));
+    }
+
+    #[test]
+    fn memory_limit() {
+        let code = wat::parse_str(
+            r#"
+            (module
+              (import "kartoffels" "set_score" (func $set_score (param i64 i32)))
+
+              (memory 1)
+
+              (func (export "tick")
+                (drop (memory.grow (i32.const 8)))
+                (call $set_score (i64.const 1) (memory.size))))
+            "#,
+        )
+        .unwrap();
+
+        let mut world = World::test(Map::parse("|...|").unwrap().0);
+        let mut mode = WasmMode::new(code).unwrap();
+
+        tick(&mut world, &mut mode);
+
+        assert_eq!(Some(9), score(&mode));
+
+        // Growing past the limit traps and disables the module
+        tick(&mut world, &mut mode);
+        tick(&mut world, &mut mode);
+
+        assert_eq!(Some(9), score(&mode));
+        assert!(matches!(mode.runtime, WasmRuntimeState::Failed));
+    }
+
+    #[test]
+    fn err_memory_limit() {
+        let code = wat::parse_str("(module (memory 17))").unwrap();
+        let err = WasmMode::new(code).unwrap_err();
+
+        assert!(format!("{err:?}").starts_with("couldn't link module"));
+    }
+}
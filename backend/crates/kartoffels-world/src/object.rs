@@ -84,6 +84,14 @@ impl ObjectId {
     pub const fn new(id: u64) -> Self {
         Self(Id::new(id))
     }
+
+    pub fn try_new(id: u64) -> Option<Self> {
+        Id::try_new(id).map(Self)
+    }
+
+    pub fn get(&self) -> Id {
+        self.0
+    }
 }
 
 impl fmt::Display for ObjectId {
@@ -2,6 +2,7 @@ use crate::{Object, ObjectId};
 use ahash::AHashMap;
 use glam::IVec2;
 use rand::{Rng, RngCore};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct Objects {
@@ -77,7 +78,31 @@ impl Objects {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+impl Serialize for Objects {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for Objects {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut this = Self::default();
+
+        for entry in Vec::<ObjectEntry>::deserialize(deserializer)? {
+            this.add(entry.id, entry.obj, entry.pos);
+        }
+
+        Ok(this)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct ObjectEntry {
     pub id: ObjectId,
     pub obj: Object,
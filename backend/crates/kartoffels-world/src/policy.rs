@@ -3,6 +3,23 @@ use serde::{Deserialize, Serialize};
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct Policy {
     pub auto_respawn: bool,
+    pub combat: Combat,
     pub max_alive_bots: usize,
     pub max_queued_bots: usize,
 }
+
+/// Decides what happens when a bot gets stabbed.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub enum Combat {
+    /// Stabbed bot dies immediately.
+    #[default]
+    #[serde(rename = "instant-kill")]
+    InstantKill,
+
+    /// Stabbed bot loses some hit points and dies only after running out of
+    /// them.
+    #[serde(rename = "hp")]
+    Hp,
+}
@@ -0,0 +1,251 @@
+mod checkpoints;
+mod recorder;
+mod replayer;
+mod systems;
+
+pub use self::checkpoints::*;
+pub use self::recorder::*;
+pub use self::replayer::*;
+pub use self::systems::*;
+use crate::{
+    storage, BotId, Clock, CreateBotRequest, Dir, Map, Object, ObjectId,
+    Request, Tile,
+};
+use anyhow::{Context, Result};
+use glam::IVec2;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::{fs, mem};
+use tokio::sync::oneshot;
+
+/// Recording of a world, allowing to re-simulate it tick-by-tick (see:
+/// [`Handle::start_recording()`] and [`Replayer`]).
+///
+/// Recordings are meant for debugging - they are not migrated between
+/// versions, so a recording created by an older version of the game might not
+/// load.
+///
+/// [`Handle::start_recording()`]: crate::Handle::start_recording
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Replay {
+    /// World at the moment the recording got started, in the same format as
+    /// the `*.world` files
+    #[serde(with = "serde_bytes")]
+    world: Vec<u8>,
+
+    /// Requests processed by the world, in the order of their arrival
+    entries: Vec<ReplayEntry>,
+
+    /// Number of ticks recorded
+    ticks: u64,
+}
+
+impl Replay {
+    /// How many files to keep per world, see: [`Self::paths()`].
+    pub const MAX_FILES: usize = 4;
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let buffer = fs::read(path)
+            .with_context(|| format!("couldn't read {}", path.display()))?;
+
+        ciborium::from_reader(&*buffer)
+            .with_context(|| format!("couldn't load {}", path.display()))
+    }
+
+    pub fn store(&self, path: &Path) -> Result<()> {
+        let mut buffer = Vec::new();
+
+        ciborium::into_writer(self, &mut buffer)
+            .context("couldn't serialize replay")?;
+
+        storage::write_atomically(path, &buffer)
+    }
+
+    /// Returns paths of all files that make up recording of given world, from
+    /// the newest to the oldest one - note that not all of them have to exist.
+    ///
+    /// Long recordings get split into many files, so that recording a
+    /// long-running world doesn't eat all the memory.
+    pub fn paths(world: &Path) -> Vec<PathBuf> {
+        let path = world.with_extension("replay");
+        let mut paths = vec![path.clone()];
+
+        for idx in 1..Self::MAX_FILES {
+            let mut rotated = path.as_os_str().to_owned();
+
+            rotated.push(format!(".{idx}"));
+            paths.push(rotated.into());
+        }
+
+        paths
+    }
+
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct ReplayEntry {
+    tick: u64,
+    request: ReplayRequest,
+}
+
+/// Serializable counterpart of [`Request`], covering requests that affect the
+/// simulation.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type")]
+enum ReplayRequest {
+    #[serde(rename = "pause")]
+    Pause,
+
+    #[serde(rename = "resume")]
+    Resume,
+
+    #[serde(rename = "create-bot")]
+    CreateBot { req: CreateBotRequest },
+
+    #[serde(rename = "create-bots")]
+    CreateBots { reqs: Vec<CreateBotRequest> },
+
+    #[serde(rename = "kill-bot")]
+    KillBot { id: BotId, reason: String },
+
+    #[serde(rename = "delete-bot")]
+    DeleteBot { id: BotId },
+
+    #[serde(rename = "set-map")]
+    SetMap { map: Map },
+
+    #[serde(rename = "set-spawn")]
+    SetSpawn {
+        point: Option<IVec2>,
+        dir: Option<Dir>,
+    },
+
+    #[serde(rename = "set-spawn-points")]
+    SetSpawnPoints { points: Vec<IVec2> },
+
+    #[serde(rename = "create-object")]
+    CreateObject { obj: Object, pos: Option<IVec2> },
+
+    #[serde(rename = "delete-object")]
+    DeleteObject { id: ObjectId },
+
+    #[serde(rename = "overclock")]
+    Overclock { clock: Clock },
+}
+
+impl ReplayRequest {
+    fn new(request: &Request) -> Option<Self> {
+        let this = match request {
+            Request::Pause { .. } => Self::Pause,
+            Request::Resume { .. } => Self::Resume,
+
+            Request::CreateBot { req, .. } => {
+                Self::CreateBot { req: req.clone() }
+            }
+
+            Request::CreateBots { reqs, .. } => {
+                Self::CreateBots { reqs: reqs.clone() }
+            }
+
+            Request::KillBot { id, reason, .. } => Self::KillBot {
+                id: *id,
+                reason: reason.clone(),
+            },
+
+            Request::DeleteBot { id, .. } => Self::DeleteBot { id: *id },
+            Request::SetMap { map, .. } => Self::SetMap { map: map.clone() },
+
+            Request::SetSpawn { point, dir, .. } => Self::SetSpawn {
+                point: *point,
+                dir: *dir,
+            },
+
+            Request::SetSpawnPoints { points, .. } => Self::SetSpawnPoints {
+                points: points.clone(),
+            },
+
+            Request::CreateObject { obj, pos, .. } => Self::CreateObject {
+                obj: *obj,
+                pos: *pos,
+            },
+
+            Request::DeleteObject { id, .. } => Self::DeleteObject { id: *id },
+            Request::Overclock { clock, .. } => {
+                Self::Overclock { clock: *clock }
+            }
+
+            // Those don't affect the simulation (ticks get recorded
+            // separately)
+            Request::Tick { .. }
+            | Request::Shutdown { .. }
+            | Request::StartRecording { .. }
+            | Request::StopRecording { .. }
+            | Request::StartEventLog { .. }
+            | Request::Fork { .. }
+            | Request::Rewind { .. } => {
+                return None;
+            }
+        };
+
+        Some(this)
+    }
+
+    /// Returns the approximate number of bytes occupied by this request.
+    fn size(&self) -> usize {
+        let payload = match self {
+            Self::CreateBot { req } => req.src.len(),
+            Self::CreateBots { reqs } => {
+                reqs.iter().map(|req| req.src.len()).sum()
+            }
+            Self::SetMap { map } => {
+                map.size().element_product() as usize * mem::size_of::<Tile>()
+            }
+            _ => 0,
+        };
+
+        mem::size_of::<ReplayEntry>() + payload
+    }
+
+    fn into_request(self) -> Request {
+        match self {
+            Self::Pause => Request::Pause { tx: tx() },
+            Self::Resume => Request::Resume { tx: tx() },
+            Self::CreateBot { req } => Request::CreateBot { req, tx: tx() },
+            Self::CreateBots { reqs } => Request::CreateBots { reqs, tx: tx() },
+
+            Self::KillBot { id, reason } => Request::KillBot {
+                id,
+                reason,
+                tx: tx(),
+            },
+
+            Self::DeleteBot { id } => Request::DeleteBot { id, tx: tx() },
+            Self::SetMap { map } => Request::SetMap { map, tx: tx() },
+
+            Self::SetSpawn { point, dir } => Request::SetSpawn {
+                point,
+                dir,
+                tx: tx(),
+            },
+
+            Self::SetSpawnPoints { points } => {
+                Request::SetSpawnPoints { points, tx: tx() }
+            }
+
+            Self::CreateObject { obj, pos } => {
+                Request::CreateObject { obj, pos, tx: tx() }
+            }
+
+            Self::DeleteObject { id } => Request::DeleteObject { id, tx: tx() },
+            Self::Overclock { clock } => Request::Overclock { clock, tx: tx() },
+        }
+    }
+}
+
+/// Creates a sender whose response nobody's waiting for.
+fn tx<T>() -> oneshot::Sender<T> {
+    oneshot::channel().0
+}
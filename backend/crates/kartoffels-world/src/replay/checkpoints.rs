@@ -0,0 +1,128 @@
+use super::{Recorder, Replayer};
+use crate::{snapshots, Container, Request, World};
+use anyhow::{Context, Result};
+use std::collections::VecDeque;
+
+/// Ring buffer of recordings started periodically, allowing to rewind the
+/// world (see: [`Handle::rewind()`]).
+///
+/// Each checkpoint contains the entire world, including bots' memory, so this
+/// is rather memory-hungry and meant only for small, private worlds.
+///
+/// [`Handle::rewind()`]: crate::Handle::rewind
+#[derive(Debug, Default)]
+pub struct Checkpoints {
+    entries: VecDeque<Recorder>,
+}
+
+impl Checkpoints {
+    /// How often to create a checkpoint, in ticks (about two seconds on the
+    /// default clock).
+    const PERIOD: u64 = 512;
+
+    /// How many checkpoints to keep.
+    const CAPACITY: usize = 8;
+
+    pub fn record(&mut self, request: &Request) {
+        for entry in &mut self.entries {
+            entry.record(request);
+        }
+    }
+
+    pub fn tick(&mut self) {
+        for entry in &mut self.entries {
+            entry.tick();
+        }
+    }
+
+    fn needs_checkpoint(&self) -> bool {
+        self.entries
+            .back()
+            .is_none_or(|entry| entry.ticks() >= Self::PERIOD)
+    }
+
+    fn push(&mut self, entry: Recorder) {
+        if self.entries.len() >= Self::CAPACITY {
+            self.entries.pop_front();
+        }
+
+        self.entries.push_back(entry);
+    }
+}
+
+/// Creates a new checkpoint, if it's about time.
+pub fn checkpoint(world: &mut World) {
+    let Some(checkpoints) = &world.checkpoints else {
+        return;
+    };
+
+    if !checkpoints.needs_checkpoint() {
+        return;
+    }
+
+    let checkpoint = Recorder::new(world);
+
+    // Unwrap-safety: We've just checked that checkpoints are enabled
+    world.checkpoints.as_mut().unwrap().push(checkpoint);
+}
+
+/// Rewinds the world by given number of ticks, returning the number of ticks
+/// actually rewound (which can be smaller if the history is not long enough).
+///
+/// Rewinding restores the newest checkpoint that's old enough and then
+/// re-simulates it forward, so the outcome is exactly the same as if the
+/// world was at that moment in time.
+pub fn rewind(
+    world: &mut World,
+    systems: &mut Container,
+    ticks: u64,
+) -> Result<u64> {
+    let checkpoints = world
+        .checkpoints
+        .as_mut()
+        .context("world doesn't support rewinding")?;
+
+    let checkpoint = checkpoints
+        .entries
+        .iter()
+        .rfind(|entry| entry.ticks() >= ticks)
+        .or_else(|| checkpoints.entries.front())
+        .context("there's nothing to rewind yet")?;
+
+    let ticks = ticks.min(checkpoint.ticks());
+    let mut replayer = Replayer::new(checkpoint.clone().finish())?;
+
+    for _ in 0..(checkpoint.ticks() - ticks) {
+        replayer.tick();
+    }
+
+    checkpoints.entries.retain_mut(|entry| entry.rewind(ticks));
+
+    if let Some(recorder) = &mut world.recorder
+        && !recorder.rewind(ticks)
+    {
+        world.recorder = None;
+    }
+
+    let past = replayer.into_world();
+
+    world.bots = past.bots;
+    world.clock = past.clock;
+    world.convey = past.convey;
+    world.map = past.map;
+    world.metronome = past.metronome;
+    world.mode = past.mode;
+    world.objects = past.objects;
+    world.paused = past.paused;
+    world.policy = past.policy;
+    world.rng = past.rng;
+    world.spawn = past.spawn;
+    world.spawn_points = past.spawn_points;
+    world.theme = past.theme;
+
+    // Send the snapshot right away, so that the rewind is visible even if the
+    // world is paused
+    snapshots::send::run_now(world, systems.get_mut());
+
+    Ok(ticks)
+}
@@ -0,0 +1,115 @@
+use super::{Replay, ReplayEntry, ReplayRequest};
+use crate::{Request, SerializedWorld, World};
+
+#[derive(Clone, Debug)]
+pub struct Recorder {
+    replay: Replay,
+
+    /// Approximate number of bytes occupied by the recording
+    size: usize,
+}
+
+impl Recorder {
+    /// Maximum number of ticks in a single recording - once it's reached,
+    /// the recording gets split (see: [`super::save::run()`]).
+    pub const MAX_TICKS: u64 = 256 * 1024;
+
+    /// Maximum size of a single recording, see: [`Self::MAX_TICKS`].
+    pub const MAX_SIZE: usize = 32 * 1024 * 1024;
+
+    pub fn new(world: &World) -> Self {
+        let mut buffer = Vec::new();
+
+        SerializedWorld::new(world)
+            .write(&mut buffer)
+            .expect("couldn't serialize the world");
+
+        Self {
+            size: buffer.len(),
+            replay: Replay {
+                world: buffer,
+                entries: Default::default(),
+                ticks: 0,
+            },
+        }
+    }
+
+    pub fn record(&mut self, request: &Request) {
+        if let Some(request) = ReplayRequest::new(request) {
+            self.size += request.size();
+            self.replay.entries.push(ReplayEntry {
+                tick: self.replay.ticks,
+                request,
+            });
+        }
+    }
+
+    pub fn tick(&mut self) {
+        self.replay.ticks += 1;
+    }
+
+    /// Returns the number of ticks recorded so far.
+    pub fn ticks(&self) -> u64 {
+        self.replay.ticks
+    }
+
+    /// Returns whether the recording has grown too large and should be
+    /// split.
+    pub fn is_full(&self) -> bool {
+        self.replay.ticks >= Self::MAX_TICKS || self.size >= Self::MAX_SIZE
+    }
+
+    /// Discards the last `ticks` ticks of the recording, returning `false` if
+    /// the recording was started later than that.
+    pub fn rewind(&mut self, ticks: u64) -> bool {
+        let Some(remaining) = self.replay.ticks.checked_sub(ticks) else {
+            return false;
+        };
+
+        self.replay.entries.retain(|entry| entry.tick < remaining);
+        self.replay.ticks = remaining;
+
+        true
+    }
+
+    pub fn finish(self) -> Replay {
+        self.replay
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CreateBotRequest, Map};
+    use tokio::sync::oneshot;
+
+    #[test]
+    fn is_full() {
+        let world = World::test(Map::default());
+
+        // ---
+
+        let mut target = Recorder::new(&world);
+
+        for _ in 1..Recorder::MAX_TICKS {
+            target.tick();
+        }
+
+        assert!(!target.is_full());
+
+        target.tick();
+
+        assert!(target.is_full());
+
+        // ---
+
+        let mut target = Recorder::new(&world);
+
+        target.record(&Request::CreateBot {
+            req: CreateBotRequest::new(vec![0; Recorder::MAX_SIZE]),
+            tx: oneshot::channel().0,
+        });
+
+        assert!(target.is_full());
+    }
+}
@@ -0,0 +1,108 @@
+use super::{Replay, ReplayEntry};
+use crate::{
+    handle, snapshots, Container, Events, SerializedWorld, Snapshot, World,
+};
+use anyhow::{Context, Result};
+use std::collections::VecDeque;
+use std::io::Cursor;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+/// Re-simulates a recorded world, tick-by-tick.
+///
+/// The simulation is bit-for-bit the same as the recorded one, as long as
+/// the game mode persists all of its state.
+pub struct Replayer {
+    world: World,
+    systems: Container,
+    entries: VecDeque<ReplayEntry>,
+    elapsed: u64,
+    ticks: u64,
+}
+
+impl Replayer {
+    pub fn new(replay: Replay) -> Result<Self> {
+        let world = SerializedWorld::read(Cursor::new(&replay.world))
+            .context("couldn't load world")?;
+
+        let mut bots = world.bots.into_owned();
+
+        bots.alive.restore_layout(&world.layout);
+
+        let world = World {
+            bots,
+            checkpoints: None,
+            clock: world.clock,
+            convey: world.convey.into_owned(),
+            events: Events::new(None),
+            map: world.map.into_owned(),
+            metronome: world.clock.metronome(),
+            mode: world.mode.into_owned(),
+            name: world.name.into_owned(),
+            objects: world
+                .objects
+                .map(|objects| objects.into_owned())
+                .unwrap_or_default(),
+            path: None,
+            paused: world.paused,
+            policy: world.policy.into_owned(),
+            recorder: None,
+            rng: world.rng.into_owned(),
+            rx: mpsc::channel(1).1,
+            snapshots: Default::default(),
+            spawn: world.spawn,
+            spawn_points: world.spawn_points.into_owned(),
+            theme: world.theme.map(|theme| theme.into_owned()),
+            tick: None,
+        };
+
+        Ok(Self {
+            world,
+            systems: Default::default(),
+            entries: replay.entries.into(),
+            elapsed: 0,
+            ticks: replay.ticks,
+        })
+    }
+
+    /// Simulates the next recorded tick, returns `false` if the recording has
+    /// already finished.
+    pub fn tick(&mut self) -> bool {
+        while let Some(entry) = self.entries.front()
+            && entry.tick == self.elapsed
+        {
+            // Unwrap-safety: We've just checked that there's an entry
+            let entry = self.entries.pop_front().unwrap();
+
+            _ = handle::process_requests::process(
+                &mut self.world,
+                &mut self.systems,
+                entry.request.into_request(),
+            );
+        }
+
+        if self.elapsed >= self.ticks {
+            return false;
+        }
+
+        self.world.simulate(&mut self.systems);
+        self.elapsed += 1;
+
+        true
+    }
+
+    /// Returns the number of ticks simulated so far.
+    pub fn elapsed(&self) -> u64 {
+        self.elapsed
+    }
+
+    pub(crate) fn into_world(self) -> World {
+        self.world
+    }
+
+    pub fn snapshot(&mut self) -> Arc<Snapshot> {
+        snapshots::send::run_now(&mut self.world, self.systems.get_mut());
+
+        self.world.snapshots.borrow().clone()
+    }
+}
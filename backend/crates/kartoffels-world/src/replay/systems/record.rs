@@ -0,0 +1,11 @@
+use crate::World;
+
+pub fn run(world: &mut World) {
+    if let Some(recorder) = &mut world.recorder {
+        recorder.tick();
+    }
+
+    if let Some(checkpoints) = &mut world.checkpoints {
+        checkpoints.tick();
+    }
+}
@@ -0,0 +1,102 @@
+use crate::{Recorder, Replay, World};
+use anyhow::{Context, Result};
+use std::path::PathBuf;
+use std::{fs, mem};
+use tokio::runtime;
+use tokio::task::{self, JoinHandle};
+use tracing::{debug, info, warn, Span};
+
+#[derive(Default)]
+pub struct State {
+    task: Option<JoinHandle<()>>,
+}
+
+/// Splits the recording once it grows too large - the finished part gets
+/// stored next to the world's file (or discarded, if the world isn't
+/// persisted), so that recording a long-running world doesn't eat all the
+/// memory.
+pub fn run(world: &mut World, state: &mut State) {
+    if !world.recorder.as_ref().is_some_and(Recorder::is_full) {
+        return;
+    }
+
+    debug!("splitting recording");
+
+    let next = Recorder::new(world);
+
+    // Unwrap-safety: We've just checked that the recorder is present
+    let prev = mem::replace(world.recorder.as_mut().unwrap(), next);
+
+    if let Some(path) = &world.path {
+        wait(state);
+
+        let path = path.clone();
+        let replay = prev.finish();
+
+        let span = Span::current();
+
+        state.task = Some(task::spawn_blocking(move || {
+            let _span = span.entered();
+
+            report(store(path, replay));
+        }));
+    }
+}
+
+/// Stores the recording (if any) next to the world's file, so that it can be
+/// inspected after the world's been shut down.
+pub fn run_now(world: &mut World, state: &mut State) {
+    wait(state);
+
+    let Some(path) = &world.path else {
+        return;
+    };
+
+    let Some(recorder) = world.recorder.take() else {
+        return;
+    };
+
+    report(store(path.clone(), recorder.finish()));
+}
+
+/// Waits for the previous recording to get stored, so that the files don't
+/// get rotated from under it.
+fn wait(state: &mut State) {
+    if let Some(task) = state.task.take() {
+        _ = runtime::Handle::current().block_on(task);
+    }
+}
+
+/// Stores the recording as the newest file, moving older files back (and
+/// removing the oldest one, if there's too many of them).
+fn store(world: PathBuf, replay: Replay) -> Result<PathBuf> {
+    let paths = Replay::paths(&world);
+
+    for (src, dst) in paths.iter().zip(&paths[1..]).rev() {
+        if src.exists() {
+            fs::rename(src, dst).with_context(|| {
+                format!(
+                    "couldn't rename {} to {}",
+                    src.display(),
+                    dst.display()
+                )
+            })?;
+        }
+    }
+
+    replay.store(&paths[0])?;
+
+    Ok(paths[0].clone())
+}
+
+fn report(result: Result<PathBuf>) {
+    match result {
+        Ok(path) => {
+            info!("recording saved: {}", path.display());
+        }
+
+        Err(err) => {
+            warn!("couldn't save recording: {:?}", err);
+        }
+    }
+}
@@ -1,26 +1,45 @@
+mod delta;
 mod stream;
 mod systems;
 
+pub use self::delta::*;
 pub use self::stream::*;
 pub use self::systems::*;
-use crate::{BotEvent, BotId, Clock, Dir, Map, Object, ObjectId};
+use crate::{
+    BotEvent, BotId, Clock, Dir, Map, Object, ObjectId, Round, Tile, Zone,
+};
 use ahash::AHashMap;
 use glam::IVec2;
 use itertools::Itertools;
 use prettytable::{row, Table};
+use serde::Serialize;
 use std::cmp::Reverse;
 use std::collections::VecDeque;
 use std::fmt;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
-#[derive(Debug, Default, PartialEq, Eq)]
+#[derive(Debug, Default)]
 pub struct Snapshot {
-    raw_map: Map,
-    map: Map,
+    raw_map: Arc<Map>,
+
+    /// Tiles overlaid on top of `raw_map` (bots, objects etc.)
+    overlay: Vec<(IVec2, Tile)>,
+
+    /// Rendered map, built on the first call to [`Self::map()`] - most
+    /// snapshots are never looked at as a whole (e.g. the ones that get
+    /// consumed as deltas), so there's no point in paying for it upfront
+    map: OnceLock<Map>,
+
     bots: SnapshotBots,
+    teams: SnapshotTeams,
     objects: SnapshotObjects,
+    zone: Option<Zone>,
+    rounds: Option<Vec<Round>>,
+    checkpoints: Vec<IVec2>,
+    leaderboard: Option<Vec<SnapshotLap>>,
     clock: Clock,
     version: u64,
+    delta: Option<Arc<SnapshotDelta>>,
 }
 
 impl Snapshot {
@@ -28,18 +47,52 @@ impl Snapshot {
         &self.raw_map
     }
 
+    /// Returns the rendered map, i.e. the raw map with bots and objects
+    /// overlaid on top of it.
     pub fn map(&self) -> &Map {
-        &self.map
+        self.map.get_or_init(|| {
+            let mut map = (*self.raw_map).clone();
+
+            map.set_many(self.overlay.iter().copied());
+            map
+        })
     }
 
     pub fn bots(&self) -> &SnapshotBots {
         &self.bots
     }
 
+    pub fn teams(&self) -> &SnapshotTeams {
+        &self.teams
+    }
+
     pub fn objects(&self) -> &SnapshotObjects {
         &self.objects
     }
 
+    /// Returns the zone highlighted by the current game mode (e.g. the hill
+    /// in king-of-the-hill), if any.
+    pub fn zone(&self) -> Option<Zone> {
+        self.zone
+    }
+
+    /// Returns history of rounds (the most recent round first), if the
+    /// current game mode is round-based.
+    pub fn rounds(&self) -> Option<&[Round]> {
+        self.rounds.as_deref()
+    }
+
+    /// Returns checkpoints (in order), if the current game mode has any.
+    pub fn checkpoints(&self) -> &[IVec2] {
+        &self.checkpoints
+    }
+
+    /// Returns the best lap times, the fastest one first, if the current game
+    /// mode is a race.
+    pub fn leaderboard(&self) -> Option<&[SnapshotLap]> {
+        self.leaderboard.as_deref()
+    }
+
     pub fn clock(&self) -> Clock {
         self.clock
     }
@@ -47,6 +100,12 @@ impl Snapshot {
     pub fn version(&self) -> u64 {
         self.version
     }
+
+    /// Returns the difference between the previous snapshot and this one -
+    /// `None` only for the empty snapshot the world starts with.
+    pub fn delta(&self) -> Option<&Arc<SnapshotDelta>> {
+        self.delta.as_ref()
+    }
 }
 
 impl fmt::Display for Snapshot {
@@ -54,7 +113,7 @@ impl fmt::Display for Snapshot {
         writeln!(f, "# map")?;
         writeln!(f)?;
         writeln!(f, "```")?;
-        writeln!(f, "{}", self.map)?;
+        writeln!(f, "{}", self.map())?;
         writeln!(f, "```")?;
 
         if !self.bots.is_empty() {
@@ -64,6 +123,13 @@ impl fmt::Display for Snapshot {
             writeln!(f, "{}", self.bots)?;
         }
 
+        if !self.teams.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "# teams")?;
+            writeln!(f)?;
+            writeln!(f, "{}", self.teams)?;
+        }
+
         Ok(())
     }
 }
@@ -135,8 +201,8 @@ pub enum SnapshotBot<'a> {
 #[derive(Debug, Default, PartialEq, Eq)]
 pub struct SnapshotAliveBots {
     entries: Vec<SnapshotAliveBot>,
-    id_to_idx: AHashMap<BotId, u8>,
-    idx_by_scores: Vec<u8>,
+    id_to_idx: AHashMap<BotId, u32>,
+    idx_by_scores: Vec<u32>,
 }
 
 impl SnapshotAliveBots {
@@ -144,7 +210,7 @@ impl SnapshotAliveBots {
         self.get_by_idx(*self.id_to_idx.get(&id)?)
     }
 
-    pub fn get_by_idx(&self, idx: u8) -> Option<&SnapshotAliveBot> {
+    pub fn get_by_idx(&self, idx: u32) -> Option<&SnapshotAliveBot> {
         self.entries.get(idx as usize)
     }
 
@@ -210,10 +276,12 @@ pub struct SnapshotAliveBot {
     pub age: u32,
     pub dir: Dir,
     pub events: Arc<VecDeque<Arc<BotEvent>>>,
+    pub hp: u32,
     pub id: BotId,
     pub pos: IVec2,
     pub score: u32,
     pub serial: Arc<VecDeque<u32>>,
+    pub team: Option<u8>,
 }
 
 impl SnapshotAliveBot {
@@ -273,11 +341,64 @@ impl SnapshotQueuedBots {
 #[derive(Debug, PartialEq, Eq)]
 pub struct SnapshotQueuedBot {
     pub events: Arc<VecDeque<Arc<BotEvent>>>,
-    pub place: u8,
+    pub place: u32,
     pub requeued: bool,
     pub serial: Arc<VecDeque<u32>>,
 }
 
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SnapshotTeams {
+    entries: Vec<SnapshotTeam>,
+}
+
+impl SnapshotTeams {
+    pub fn get(&self, id: u8) -> Option<&SnapshotTeam> {
+        self.entries.iter().find(|team| team.id == id)
+    }
+
+    /// Returns teams sorted by their scores, best team first.
+    pub fn iter(&self) -> impl Iterator<Item = &SnapshotTeam> + '_ {
+        self.entries.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl fmt::Display for SnapshotTeams {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut table = Table::init(vec![]);
+
+        table.set_titles(row!["id", "bots", "score"]);
+
+        for team in self.iter() {
+            table.add_row(row![team.id, team.bots, team.score]);
+        }
+
+        write!(f, "{table}")
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct SnapshotTeam {
+    pub id: u8,
+    pub bots: usize,
+    pub score: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct SnapshotLap {
+    pub id: BotId,
+    pub ticks: u32,
+}
+
+impl SnapshotLap {
+    pub fn seconds(&self) -> f32 {
+        self.ticks as f32 / (Clock::HZ as f32)
+    }
+}
+
 #[derive(Debug, Default, PartialEq, Eq)]
 pub struct SnapshotObjects {
     objects: Vec<SnapshotObject>,
@@ -293,7 +414,7 @@ impl SnapshotObjects {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
 pub struct SnapshotObject {
     pub id: ObjectId,
     pub obj: Object,
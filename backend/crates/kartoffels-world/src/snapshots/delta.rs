@@ -0,0 +1,318 @@
+use super::{
+    Snapshot, SnapshotAliveBot, SnapshotLap, SnapshotObject, SnapshotQueuedBot,
+    SnapshotTeam,
+};
+use crate::{BotId, Clock, Dir, Map, ObjectId, Round, Tile, Zone};
+use ahash::{AHashMap, AHashSet};
+use glam::{IVec2, UVec2};
+use serde::Serialize;
+use std::sync::Arc;
+
+/// Difference between two snapshots, allowing to keep track of the world
+/// without going through the entire snapshot each time (see:
+/// [`Snapshot::delta()`] and [`crate::SnapshotStream::into_deltas()`]).
+///
+/// Deltas are built once, by the world, as it sends the snapshots - so the
+/// cost doesn't grow with the number of subscribers.
+///
+/// Fields that describe the game mode (zone, rounds etc.) are `Some` only if
+/// they've changed - e.g. `zone: Some(None)` means the zone has disappeared.
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+pub struct SnapshotDelta {
+    /// Version of the snapshot this delta applies to
+    pub base: u64,
+
+    /// Version of the snapshot this delta produces
+    pub version: u64,
+
+    /// New size of the map, if it's changed - in this case `tiles` contain
+    /// the entire map
+    pub size: Option<UVec2>,
+
+    /// Tiles of the rendered map (see: [`Snapshot::map()`]) that have changed
+    pub tiles: Vec<(IVec2, Tile)>,
+
+    /// Alive bots that have appeared or changed
+    pub bots: Vec<SnapshotBotDelta>,
+
+    /// Alive bots that have disappeared (got killed, deleted etc.)
+    pub bots_removed: Vec<BotId>,
+
+    /// Objects that have appeared or changed
+    pub objects: Vec<SnapshotObject>,
+
+    /// Objects that have disappeared
+    pub objects_removed: Vec<ObjectId>,
+
+    /// Queued bots that have appeared or changed their place in the queue
+    pub queued: Vec<SnapshotQueuedBotDelta>,
+
+    /// Queued bots that have left the queue (got spawned, deleted etc.)
+    pub queued_removed: Vec<BotId>,
+
+    /// Bots that have died
+    pub dead: Vec<BotId>,
+
+    /// Dead bots that have been forgotten or brought back to life
+    pub dead_removed: Vec<BotId>,
+
+    /// Teams, sorted by their scores (see: [`Snapshot::teams()`])
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub teams: Option<Vec<SnapshotTeam>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zone: Option<Option<Zone>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rounds: Option<Option<Vec<Round>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checkpoints: Option<Vec<IVec2>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub leaderboard: Option<Option<Vec<SnapshotLap>>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clock: Option<Clock>,
+}
+
+impl SnapshotDelta {
+    pub(crate) fn new(prev: &Snapshot, curr: &Snapshot) -> Self {
+        let mut this = Self {
+            base: prev.version,
+            version: curr.version,
+            ..Default::default()
+        };
+
+        this.diff_map(prev, curr);
+        this.diff_bots(prev, curr);
+        this.diff_objects(prev, curr);
+        this.diff_queued(prev, curr);
+        this.diff_dead(prev, curr);
+        this.diff_mode(prev, curr);
+        this
+    }
+
+    /// Finds tiles of the rendered map that have changed.
+    ///
+    /// Rendered maps are built lazily (see: [`Snapshot::map()`]), so instead
+    /// of comparing them, we compare the overlays (bots, objects etc.) and -
+    /// only if the map itself has changed, which happens rarely - the raw
+    /// maps.
+    fn diff_map(&mut self, prev: &Snapshot, curr: &Snapshot) {
+        if prev.raw_map.size() != curr.raw_map.size() {
+            self.size = Some(curr.raw_map.size());
+
+            curr.map().for_each(|pos, tile| {
+                self.tiles.push((pos, tile));
+            });
+
+            return;
+        }
+
+        // Later overlay entries take precedence, same as when rendering
+        let prev_overlay: AHashMap<_, _> =
+            prev.overlay.iter().copied().collect();
+
+        let curr_overlay: AHashMap<_, _> =
+            curr.overlay.iter().copied().collect();
+
+        let mut positions: AHashSet<_> = prev_overlay
+            .keys()
+            .chain(curr_overlay.keys())
+            .copied()
+            .filter(|pos| curr.raw_map.contains(*pos))
+            .collect();
+
+        if !Arc::ptr_eq(&prev.raw_map, &curr.raw_map) {
+            curr.raw_map.for_each(|pos, tile| {
+                if prev.raw_map.get(pos) != tile {
+                    positions.insert(pos);
+                }
+            });
+        }
+
+        for pos in positions {
+            let prev_tile = prev_overlay
+                .get(&pos)
+                .copied()
+                .unwrap_or_else(|| prev.raw_map.get(pos));
+
+            let curr_tile = curr_overlay
+                .get(&pos)
+                .copied()
+                .unwrap_or_else(|| curr.raw_map.get(pos));
+
+            if prev_tile != curr_tile {
+                self.tiles.push((pos, curr_tile));
+            }
+        }
+
+        self.tiles.sort_unstable_by_key(|(pos, _)| (pos.y, pos.x));
+    }
+
+    fn diff_bots(&mut self, prev: &Snapshot, curr: &Snapshot) {
+        let prev_bots = prev.bots.alive();
+        let curr_bots = curr.bots.alive();
+
+        for (idx, bot) in curr_bots.iter().enumerate() {
+            let bot = SnapshotBotDelta::new(idx as u32, bot);
+
+            let prev_bot = prev_bots.id_to_idx.get(&bot.id).and_then(|&idx| {
+                Some(SnapshotBotDelta::new(idx, prev_bots.get_by_idx(idx)?))
+            });
+
+            if prev_bot != Some(bot) {
+                self.bots.push(bot);
+            }
+        }
+
+        for bot in prev_bots.iter() {
+            if !curr_bots.has(bot.id) {
+                self.bots_removed.push(bot.id);
+            }
+        }
+    }
+
+    fn diff_objects(&mut self, prev: &Snapshot, curr: &Snapshot) {
+        let mut prev_objs: AHashMap<_, _> =
+            prev.objects.iter().map(|obj| (obj.id, obj)).collect();
+
+        for obj in curr.objects.iter() {
+            if prev_objs.remove(&obj.id) != Some(obj) {
+                self.objects.push(obj.clone());
+            }
+        }
+
+        self.objects_removed.extend(prev_objs.into_keys());
+    }
+
+    fn diff_queued(&mut self, prev: &Snapshot, curr: &Snapshot) {
+        let prev_bots = &prev.bots.queued.entries;
+        let curr_bots = &curr.bots.queued.entries;
+
+        for (&id, bot) in curr_bots {
+            let bot = SnapshotQueuedBotDelta::new(id, bot);
+
+            let prev_bot = prev_bots
+                .get(&id)
+                .map(|bot| SnapshotQueuedBotDelta::new(id, bot));
+
+            if prev_bot != Some(bot) {
+                self.queued.push(bot);
+            }
+        }
+
+        for &id in prev_bots.keys() {
+            if !curr_bots.contains_key(&id) {
+                self.queued_removed.push(id);
+            }
+        }
+    }
+
+    fn diff_dead(&mut self, prev: &Snapshot, curr: &Snapshot) {
+        let prev_bots = &prev.bots.dead;
+        let curr_bots = &curr.bots.dead;
+
+        self.dead
+            .extend(curr_bots.ids().filter(|id| !prev_bots.has(*id)));
+
+        self.dead_removed
+            .extend(prev_bots.ids().filter(|id| !curr_bots.has(*id)));
+    }
+
+    fn diff_mode(&mut self, prev: &Snapshot, curr: &Snapshot) {
+        fn diff<T>(prev: &T, curr: &T) -> Option<T>
+        where
+            T: Clone + PartialEq,
+        {
+            (prev != curr).then(|| curr.clone())
+        }
+
+        self.teams = diff(&prev.teams.entries, &curr.teams.entries);
+        self.zone = diff(&prev.zone, &curr.zone);
+        self.rounds = diff(&prev.rounds, &curr.rounds);
+        self.checkpoints = diff(&prev.checkpoints, &curr.checkpoints);
+        self.leaderboard = diff(&prev.leaderboard, &curr.leaderboard);
+        self.clock = diff(&prev.clock, &curr.clock);
+    }
+
+    /// Applies this delta to given map, which should be the rendered map of
+    /// the base snapshot (or the result of applying previous deltas).
+    pub fn apply_to_map(&self, map: &mut Map) {
+        if let Some(size) = self.size {
+            *map = Map::new(size);
+        }
+
+        for (pos, tile) in &self.tiles {
+            map.set(*pos, *tile);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size.is_none()
+            && self.tiles.is_empty()
+            && self.bots.is_empty()
+            && self.bots_removed.is_empty()
+            && self.objects.is_empty()
+            && self.objects_removed.is_empty()
+            && self.queued.is_empty()
+            && self.queued_removed.is_empty()
+            && self.dead.is_empty()
+            && self.dead_removed.is_empty()
+            && self.teams.is_none()
+            && self.zone.is_none()
+            && self.rounds.is_none()
+            && self.checkpoints.is_none()
+            && self.leaderboard.is_none()
+            && self.clock.is_none()
+    }
+}
+
+/// Alive bot, as seen by [`SnapshotDelta`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct SnapshotBotDelta {
+    pub id: BotId,
+
+    /// Index of the bot, as used by tiles in the rendered map (see:
+    /// [`Tile::bot_idx()`])
+    pub idx: u32,
+
+    pub pos: IVec2,
+    pub dir: Dir,
+    pub hp: u32,
+    pub score: u32,
+    pub team: Option<u8>,
+}
+
+impl SnapshotBotDelta {
+    fn new(idx: u32, bot: &SnapshotAliveBot) -> Self {
+        Self {
+            id: bot.id,
+            idx,
+            pos: bot.pos,
+            dir: bot.dir,
+            hp: bot.hp,
+            score: bot.score,
+            team: bot.team,
+        }
+    }
+}
+
+/// Queued bot, as seen by [`SnapshotDelta`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct SnapshotQueuedBotDelta {
+    pub id: BotId,
+    pub place: u32,
+    pub requeued: bool,
+}
+
+impl SnapshotQueuedBotDelta {
+    fn new(id: BotId, bot: &SnapshotQueuedBot) -> Self {
+        Self {
+            id,
+            place: bot.place,
+            requeued: bot.requeued,
+        }
+    }
+}
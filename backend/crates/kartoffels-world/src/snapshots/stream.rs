@@ -1,4 +1,4 @@
-use super::Snapshot;
+use super::{Snapshot, SnapshotDelta};
 use crate::Handle;
 use anyhow::{Context, Result};
 use std::sync::Arc;
@@ -21,4 +21,50 @@ impl SnapshotStream {
     pub async fn next(&mut self) -> Result<Arc<Snapshot>> {
         self.stream.next().await.context(Handle::ERR)
     }
+
+    /// Turns this stream into one that yields deltas instead of complete
+    /// snapshots, see: [`SnapshotDeltaStream`].
+    pub fn into_deltas(self) -> SnapshotDeltaStream {
+        SnapshotDeltaStream {
+            stream: self,
+            version: None,
+        }
+    }
+}
+
+/// Stream of snapshot deltas.
+///
+/// The stream starts with a complete snapshot, after which it yields deltas
+/// built by the world (see: [`Snapshot::delta()`]).
+///
+/// Snapshots are sent through a channel that keeps only the latest one, so a
+/// subscriber that's too slow can miss some of them - in that case the delta
+/// wouldn't line up, so the stream yields a complete snapshot again.
+#[derive(Debug)]
+pub struct SnapshotDeltaStream {
+    stream: SnapshotStream,
+    version: Option<u64>,
+}
+
+impl SnapshotDeltaStream {
+    pub async fn next(&mut self) -> Result<SnapshotUpdate> {
+        let snapshot = self.stream.next().await?;
+        let version = self.version.replace(snapshot.version);
+
+        let update = match snapshot.delta() {
+            Some(delta) if Some(delta.base) == version => {
+                SnapshotUpdate::Delta(delta.clone())
+            }
+
+            _ => SnapshotUpdate::Full(snapshot),
+        };
+
+        Ok(update)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum SnapshotUpdate {
+    Full(Arc<Snapshot>),
+    Delta(Arc<SnapshotDelta>),
 }
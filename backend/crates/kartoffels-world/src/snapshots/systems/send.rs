@@ -1,15 +1,18 @@
 use crate::{
     Clock, Map, Snapshot, SnapshotAliveBot, SnapshotAliveBots, SnapshotBots,
-    SnapshotDeadBot, SnapshotDeadBots, SnapshotObject, SnapshotObjects,
-    SnapshotQueuedBot, SnapshotQueuedBots, Tile, TileKind, World,
+    SnapshotDeadBot, SnapshotDeadBots, SnapshotDelta, SnapshotLap,
+    SnapshotObject, SnapshotObjects, SnapshotQueuedBot, SnapshotQueuedBots,
+    SnapshotTeam, SnapshotTeams, Tile, TileKind, World,
 };
 use ahash::AHashMap;
+use glam::IVec2;
 use std::cmp::Reverse;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 pub struct State {
     next_run_at: Instant,
+    raw_map: Arc<Map>,
     version: u64,
 }
 
@@ -17,6 +20,7 @@ impl Default for State {
     fn default() -> Self {
         Self {
             next_run_at: Instant::now(),
+            raw_map: Default::default(),
             version: 0,
         }
     }
@@ -27,24 +31,50 @@ pub fn run(world: &mut World, state: &mut State) {
         return;
     }
 
+    run_now(world, state);
+}
+
+pub fn run_now(world: &mut World, state: &mut State) {
     state.version += 1;
 
-    let snapshot = {
+    // Map rarely changes, so instead of cloning it for each snapshot, let's
+    // share it between snapshots until it does
+    if state.raw_map.rev() != world.map.rev() {
+        state.raw_map = Arc::new(world.map.clone());
+    }
+
+    let mut snapshot = {
         let bots = prepare_bots(world);
-        let map = prepare_map(&bots, world);
+        let overlay = prepare_overlay(world);
+        let teams = prepare_teams(&bots, world);
         let objects = prepare_objects(world);
 
-        Arc::new(Snapshot {
-            raw_map: world.map.clone(),
-            map,
+        Snapshot {
+            raw_map: state.raw_map.clone(),
+            overlay,
+            map: Default::default(),
             bots,
+            teams,
             objects,
+            zone: world.mode.zone(),
+            rounds: world.mode.rounds(),
+            checkpoints: world.mode.checkpoints().to_vec(),
+            leaderboard: prepare_leaderboard(world),
             clock: world.clock,
             version: state.version,
-        })
+            delta: None,
+        }
     };
 
-    world.snapshots.send_replace(snapshot);
+    // Build the delta here, once, instead of letting each subscriber diff the
+    // snapshots on its own - the previous snapshot is the one still lying in
+    // the channel
+    snapshot.delta = Some(Arc::new(SnapshotDelta::new(
+        &world.snapshots.borrow(),
+        &snapshot,
+    )));
+
+    world.snapshots.send_replace(Arc::new(snapshot));
     world.events.send(state.version);
 
     state.next_run_at = match world.clock {
@@ -72,21 +102,23 @@ fn prepare_alive_bots(world: &mut World) -> SnapshotAliveBots {
             age: bot.timer.ticks(),
             dir: bot.dir,
             events: bot.events.snapshot(),
+            hp: bot.health.hp(),
             id: bot.id,
             pos: bot.pos,
             score: scores.get(&bot.id).copied().unwrap_or_default(),
             serial: bot.serial.snapshot(),
+            team: bot.team,
         })
         .collect();
 
     let id_to_idx: AHashMap<_, _> = entries
         .iter()
         .enumerate()
-        .map(|(idx, bot)| (bot.id, idx as u8))
+        .map(|(idx, bot)| (bot.id, idx as u32))
         .collect();
 
     let idx_by_scores = {
-        let mut idx: Vec<_> = (0..(entries.len() as u8)).collect();
+        let mut idx: Vec<_> = (0..(entries.len() as u32)).collect();
 
         idx.sort_unstable_by_key(|idx| {
             let bot = &entries[*idx as usize];
@@ -142,36 +174,91 @@ fn prepare_queued_bots(world: &mut World) -> SnapshotQueuedBots {
     SnapshotQueuedBots { entries }
 }
 
-fn prepare_map(bots: &SnapshotBots, world: &World) -> Map {
-    let mut map = world.map.clone();
+/// Prepares tiles to be overlaid on top of the map (bots, objects etc.).
+///
+/// The map itself gets shared between snapshots and rendered only if someone
+/// asks for it (see: [`Snapshot::map()`]), so the cost here depends on the
+/// number of bots and objects, not on the map size.
+fn prepare_overlay(world: &World) -> Vec<(IVec2, Tile)> {
+    let mut overlay = Vec::new();
 
-    for (idx, bot) in bots.alive().iter().enumerate() {
-        let tile = Tile {
-            kind: TileKind::BOT,
-            meta: [idx as u8, 0, 0],
-        };
+    for (idx, bot) in world.bots.alive.iter().enumerate() {
+        overlay.push((bot.pos, Tile::bot(idx as u32)));
 
         let chevron_pos = bot.pos + bot.dir;
 
-        let chevron_tile = Tile {
-            kind: TileKind::BOT_CHEVRON,
-            meta: [idx as u8, u8::from(bot.dir), 0],
-        };
-
-        map.set(bot.pos, tile);
+        if world.bots.alive.lookup_at(chevron_pos).is_none() {
+            let chevron_tile = Tile {
+                kind: TileKind::BOT_CHEVRON,
+                meta: [0, u8::from(bot.dir), 0],
+            };
 
-        if !map.get(chevron_pos).is_bot() {
-            map.set(chevron_pos, chevron_tile);
+            overlay.push((chevron_pos, chevron_tile));
         }
     }
 
     for obj in world.objects.iter() {
         if let Some(pos) = obj.pos {
-            map.set(pos, obj.obj.kind);
+            overlay.push((pos, Tile::new(obj.obj.kind)));
+        }
+    }
+
+    overlay
+}
+
+fn prepare_teams(bots: &SnapshotBots, world: &World) -> SnapshotTeams {
+    let mut teams = AHashMap::<u8, SnapshotTeam>::new();
+    let team_scores = world.mode.team_scores();
+
+    for bot in bots.alive().iter() {
+        let Some(id) = bot.team else {
+            continue;
+        };
+
+        let team = teams.entry(id).or_insert(SnapshotTeam {
+            id,
+            bots: 0,
+            score: 0,
+        });
+
+        team.bots += 1;
+
+        if team_scores.is_none() {
+            team.score += bot.score;
+        }
+    }
+
+    // If the mode keeps track of teams on its own, prefer its scores (they
+    // survive the bots being killed) and include teams without any bots
+    if let Some(team_scores) = team_scores {
+        for (&id, &score) in team_scores {
+            teams
+                .entry(id)
+                .or_insert(SnapshotTeam {
+                    id,
+                    bots: 0,
+                    score: 0,
+                })
+                .score = score;
         }
     }
 
-    map
+    let mut entries: Vec<_> = teams.into_values().collect();
+
+    entries.sort_unstable_by_key(|team| (Reverse(team.score), team.id));
+
+    SnapshotTeams { entries }
+}
+
+fn prepare_leaderboard(world: &World) -> Option<Vec<SnapshotLap>> {
+    let entries = world
+        .mode
+        .leaderboard()?
+        .into_iter()
+        .map(|(id, ticks)| SnapshotLap { id, ticks })
+        .collect();
+
+    Some(entries)
 }
 
 fn prepare_objects(world: &World) -> SnapshotObjects {
@@ -187,3 +274,55 @@ fn prepare_objects(world: &World) -> SnapshotObjects {
 
     SnapshotObjects { objects }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AliveBot, BotId, Dir, Object, ObjectId, ObjectKind};
+    use glam::ivec2;
+
+    #[test]
+    fn map() {
+        let (map, _) = Map::parse("|.....|").unwrap();
+        let mut world = World::test(map);
+        let mut state = State::default();
+        let id = BotId::new(1);
+
+        world.bots.alive.add(AliveBot {
+            id,
+            pos: ivec2(1, 0),
+            dir: Dir::E,
+            ..Default::default()
+        });
+
+        world.objects.add(
+            ObjectId::new(1),
+            Object::new(ObjectKind::GEM),
+            Some(ivec2(4, 0)),
+        );
+
+        run_now(&mut world, &mut state);
+
+        assert_eq!("|@~.*.|", world.snapshots.borrow().map().to_string());
+
+        // ---
+
+        let mut bot = world.bots.alive.take(0).unwrap();
+
+        bot.pos = ivec2(3, 0);
+        world.bots.alive.insert(0, id, ivec2(1, 0), Some(bot));
+        world.objects.remove(ObjectId::new(1));
+
+        run_now(&mut world, &mut state);
+
+        assert_eq!("|..@~.|", world.snapshots.borrow().map().to_string());
+
+        // ---
+
+        world.map.set(ivec2(1, 0), TileKind::WATER);
+
+        run_now(&mut world, &mut state);
+
+        assert_eq!("|%.@~.|", world.snapshots.borrow().map().to_string());
+    }
+}
@@ -4,39 +4,88 @@ mod systems;
 
 use self::header::*;
 pub use self::systems::*;
-use crate::{Bots, Map, Metronome, Mode, Policy, Theme};
+use crate::{
+    Bots, Clock, ConveyTimer, Dir, Map, Metronome, Mode, Objects, Policy,
+    Theme, World,
+};
 use anyhow::{Context, Result};
+use glam::IVec2;
 use maybe_owned::MaybeOwned;
+use rand_chacha::ChaCha8Rng;
 use serde::{Deserialize, Serialize};
 use std::fs::{self, File};
 use std::future::Future;
-use std::io::{BufReader, Cursor};
-use std::path::Path;
+use std::io::{BufReader, Cursor, Read, Write};
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use tokio::task;
 
+/// World, as stored in `*.world` files.
+///
+/// This contains everything needed to continue the simulation exactly where
+/// it's left off - resuming a saved world yields the same outcome as if the
+/// world has never been stopped.
 #[derive(Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
 pub struct SerializedWorld<'a> {
     pub bots: MaybeOwned<'a, Bots>,
+    pub clock: Clock,
+    pub convey: MaybeOwned<'a, ConveyTimer>,
+
+    /// Layout of alive bots, see: [`crate::AliveBots::layout()`]
+    pub layout: Vec<bool>,
+
     pub map: MaybeOwned<'a, Map>,
     pub mode: MaybeOwned<'a, Mode>,
     pub name: MaybeOwned<'a, String>,
+
+    /// Objects lying on the map or carried by bots - `None` for worlds saved
+    /// before objects got persisted, in which case the theme's objects get
+    /// re-created on resume (see: [`crate::theme::apply::run()`])
+    pub objects: Option<MaybeOwned<'a, Objects>>,
+
+    pub paused: bool,
     pub policy: MaybeOwned<'a, Policy>,
+    pub rng: MaybeOwned<'a, ChaCha8Rng>,
+    pub spawn: (Option<IVec2>, Option<Dir>),
+    pub spawn_points: MaybeOwned<'a, Vec<IVec2>>,
     pub theme: Option<MaybeOwned<'a, Theme>>,
 }
 
-impl SerializedWorld<'_> {
+impl<'a> SerializedWorld<'a> {
+    pub fn new(world: &'a World) -> Self {
+        Self {
+            bots: MaybeOwned::Borrowed(&world.bots),
+            clock: world.clock,
+            convey: MaybeOwned::Borrowed(&world.convey),
+            layout: world.bots.alive.layout(),
+            map: MaybeOwned::Borrowed(&world.map),
+            mode: MaybeOwned::Borrowed(&world.mode),
+            name: MaybeOwned::Borrowed(&world.name),
+            objects: Some(MaybeOwned::Borrowed(&world.objects)),
+            paused: world.paused,
+            policy: MaybeOwned::Borrowed(&world.policy),
+            rng: MaybeOwned::Borrowed(&world.rng),
+            spawn: world.spawn,
+            spawn_points: MaybeOwned::Borrowed(&world.spawn_points),
+            theme: world.theme.as_ref().map(MaybeOwned::Borrowed),
+        }
+    }
+
     pub fn load(path: &Path) -> Result<Self> {
-        let mut file = File::open(path)?;
-        let mut file = BufReader::new(&mut file);
+        let file = File::open(path)?;
+
+        Self::read(BufReader::new(file))
+    }
 
-        let header = Header::read(&mut file)
+    pub fn read(mut reader: impl Read) -> Result<Self> {
+        let header = Header::read(&mut reader)
             .context("couldn't read header")?
             .validated()
             .context("couldn't validate header")?;
 
-        let this =
-            ciborium::from_reader(&mut file).context("couldn't read state")?;
+        let this = ciborium::from_reader(&mut reader)
+            .context("couldn't read state")?;
 
         let this =
             migrations::run(header.version(), migrations::version(), this)
@@ -54,6 +103,17 @@ impl SerializedWorld<'_> {
         Ok(this)
     }
 
+    pub fn write(&self, mut writer: impl Write) -> Result<()> {
+        Header::default()
+            .write(&mut writer)
+            .context("couldn't write header")?;
+
+        ciborium::into_writer(self, &mut writer)
+            .context("couldn't write state")?;
+
+        Ok(())
+    }
+
     pub fn store(
         self,
         path: &Path,
@@ -65,39 +125,66 @@ impl SerializedWorld<'_> {
         let (buffer, tt_ser) = Metronome::try_measure(|| {
             let mut buffer = Vec::new();
 
-            Header::default()
-                .write(&mut buffer)
-                .context("couldn't write header")?;
-
-            ciborium::into_writer(&self, &mut buffer)
-                .context("couldn't write state")?;
+            self.write(&mut buffer)?;
 
             Ok(buffer)
         })?;
 
         let path = path.to_owned();
-        let path_new = path.with_extension("world.new");
 
         let task = task::spawn_blocking(move || {
-            let (_, tt_io) = Metronome::try_measure(|| {
-                fs::write(&path_new, &buffer).with_context(|| {
-                    format!("couldn't write: {}", path_new.display())
-                })?;
-
-                fs::rename(&path_new, &path).with_context(|| {
-                    format!(
-                        "couldn't rename {} to {}",
-                        path_new.display(),
-                        path.display()
-                    )
-                })?;
-
-                Ok(())
-            })?;
+            let (_, tt_io) =
+                Metronome::try_measure(|| write_atomically(&path, &buffer))?;
 
             Ok((tt_ser, tt_io))
         });
 
         Ok(async move { task.await.context("task crashed")? })
     }
+
+    /// Loads world from given file, migrating it to the current version, lets
+    /// `f` modify it and then stores it back.
+    ///
+    /// This is meant for tools that operate on worlds offline - the world
+    /// mustn't be running at the same time, since the changes would get
+    /// overwritten on its next save.
+    pub fn update(
+        path: &Path,
+        f: impl FnOnce(&mut SerializedWorld<'static>),
+    ) -> Result<()> {
+        let mut this = SerializedWorld::load(path)
+            .with_context(|| format!("couldn't load {}", path.display()))?;
+
+        f(&mut this);
+
+        let mut buffer = Vec::new();
+
+        this.write(&mut buffer)?;
+
+        write_atomically(path, &buffer)
+    }
+}
+
+/// Writes given buffer into a temporary file and then renames it, so that a
+/// crash in the middle of writing doesn't leave a half-written world behind.
+pub fn write_atomically(path: &Path, buffer: &[u8]) -> Result<()> {
+    let path_new = {
+        let mut path = path.as_os_str().to_owned();
+
+        path.push(".new");
+        PathBuf::from(path)
+    };
+
+    fs::write(&path_new, buffer)
+        .with_context(|| format!("couldn't write: {}", path_new.display()))?;
+
+    fs::rename(&path_new, path).with_context(|| {
+        format!(
+            "couldn't rename {} to {}",
+            path_new.display(),
+            path.display()
+        )
+    })?;
+
+    Ok(())
 }
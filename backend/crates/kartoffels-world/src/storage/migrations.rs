@@ -10,6 +10,12 @@ mod v10;
 mod v11;
 mod v12;
 mod v13;
+mod v14;
+mod v15;
+mod v16;
+mod v17;
+mod v18;
+mod v19;
 
 use anyhow::Result;
 use ciborium::Value;
@@ -28,6 +34,12 @@ static MIGRATIONS: &[fn(&mut Value)] = &[
     v11::run,
     v12::run,
     v13::run,
+    v14::run,
+    v15::run,
+    v16::run,
+    v17::run,
+    v18::run,
+    v19::run,
 ];
 
 pub fn run(old: u32, new: u32, mut world: Value) -> Result<Value> {
@@ -0,0 +1,53 @@
+use ciborium::Value;
+use kartoffels_utils::{CborMapExt, CborValueExt};
+
+pub fn run(world: &mut Value) {
+    for motor in world.query_mut("/bots/alive/*/motor") {
+        motor
+            .as_map_mut()
+            .unwrap()
+            .add_entry("status", Value::Text("idle".into()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::migrations;
+    use indoc::indoc;
+
+    #[test]
+    fn test() {
+        let given = indoc! {r#"
+          {
+            "bots": {
+              "alive": [
+                {
+                  "id": "1234-1234-1234-1234",
+                  "motor": {
+                    "cooldown": 123
+                  }
+                }
+              ]
+            }
+          }
+        "#};
+
+        let expected = indoc! {r#"
+          {
+            "bots": {
+              "alive": [
+                {
+                  "id": "1234-1234-1234-1234",
+                  "motor": {
+                    "cooldown": 123,
+                    "status": "idle"
+                  }
+                }
+              ]
+            }
+          }
+        "#};
+
+        migrations::tests::run(14, given, expected);
+    }
+}
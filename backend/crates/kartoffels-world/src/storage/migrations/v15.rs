@@ -0,0 +1,69 @@
+use ciborium::Value;
+use kartoffels_utils::{CborMapExt, CborValueExt};
+
+pub fn run(world: &mut Value) {
+    for policy in world.query_mut("/policy") {
+        policy
+            .as_map_mut()
+            .unwrap()
+            .add_entry("combat", Value::Text("instant-kill".into()));
+    }
+
+    for bot in world.query_mut("/bots/alive/*") {
+        bot.as_map_mut().unwrap().add_entry(
+            "health",
+            Value::Map(
+                Vec::default().with_entry("hp", Value::Integer(100.into())),
+            ),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::migrations;
+    use indoc::indoc;
+
+    #[test]
+    fn test() {
+        let given = indoc! {r#"
+          {
+            "bots": {
+              "alive": [
+                {
+                  "id": "1234-1234-1234-1234"
+                }
+              ]
+            },
+            "policy": {
+              "auto_respawn": true,
+              "max_alive_bots": 16,
+              "max_queued_bots": 16
+            }
+          }
+        "#};
+
+        let expected = indoc! {r#"
+          {
+            "bots": {
+              "alive": [
+                {
+                  "id": "1234-1234-1234-1234",
+                  "health": {
+                    "hp": 100
+                  }
+                }
+              ]
+            },
+            "policy": {
+              "auto_respawn": true,
+              "max_alive_bots": 16,
+              "max_queued_bots": 16,
+              "combat": "instant-kill"
+            }
+          }
+        "#};
+
+        migrations::tests::run(15, given, expected);
+    }
+}
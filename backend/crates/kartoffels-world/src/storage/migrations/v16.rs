@@ -0,0 +1,69 @@
+use ciborium::Value;
+use kartoffels_utils::{CborMapExt, CborValueExt};
+
+pub fn run(world: &mut Value) {
+    for bot in world.query_mut("/bots/{alive,queued}/*") {
+        bot.as_map_mut().unwrap().add_entry("team", Value::Null);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::migrations;
+    use indoc::indoc;
+
+    #[test]
+    fn test() {
+        let given = indoc! {r#"
+          {
+            "bots": {
+              "alive": [
+                {
+                  "id": "1234-1234-1234-1234"
+                }
+              ],
+
+              "dead": [
+                {
+                  "id": "4321-4321-4321-4321"
+                }
+              ],
+
+              "queued": [
+                {
+                  "id": "5678-5678-5678-5678"
+                }
+              ]
+            }
+          }
+        "#};
+
+        let expected = indoc! {r#"
+          {
+            "bots": {
+              "alive": [
+                {
+                  "id": "1234-1234-1234-1234",
+                  "team": null
+                }
+              ],
+
+              "dead": [
+                {
+                  "id": "4321-4321-4321-4321"
+                }
+              ],
+
+              "queued": [
+                {
+                  "id": "5678-5678-5678-5678",
+                  "team": null
+                }
+              ]
+            }
+          }
+        "#};
+
+        migrations::tests::run(16, given, expected);
+    }
+}
@@ -0,0 +1,42 @@
+use ciborium::Value;
+use kartoffels_utils::{CborMapExt, CborValueExt};
+
+pub fn run(world: &mut Value) {
+    for bots in world.query_mut("/bots") {
+        bots.as_map_mut()
+            .unwrap()
+            .add_entry("benched", Value::Array(Default::default()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::migrations;
+    use indoc::indoc;
+
+    #[test]
+    fn test() {
+        let given = indoc! {r#"
+          {
+            "bots": {
+              "alive": [],
+              "dead": [],
+              "queued": []
+            }
+          }
+        "#};
+
+        let expected = indoc! {r#"
+          {
+            "bots": {
+              "alive": [],
+              "dead": [],
+              "queued": [],
+              "benched": []
+            }
+          }
+        "#};
+
+        migrations::tests::run(17, given, expected);
+    }
+}
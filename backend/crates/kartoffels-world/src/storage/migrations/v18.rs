@@ -0,0 +1,43 @@
+use ciborium::Value;
+use kartoffels_utils::CborMapExt;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+/// Worlds saved before prng got persisted used to get reseeded on each
+/// resume, so there's no state to recover - let's pick a fresh seed.
+pub fn run(world: &mut Value) {
+    let rng = Value::serialized(&ChaCha8Rng::from_entropy()).unwrap();
+
+    world.as_map_mut().unwrap().add_entry("rng", rng);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::migrations;
+    use kartoffels_utils::json_to_cbor;
+
+    #[test]
+    fn test() {
+        let given = json_to_cbor(serde_json::json!({
+            "bots": {
+                "alive": [],
+                "dead": [],
+                "queued": [],
+                "benched": []
+            }
+        }));
+
+        let actual = migrations::run(17, 18, given).unwrap();
+
+        let rng = actual
+            .as_map()
+            .unwrap()
+            .iter()
+            .find(|(key, _)| key.as_text() == Some("rng"))
+            .map(|(_, val)| val)
+            .unwrap();
+
+        rng.deserialized::<ChaCha8Rng>().unwrap();
+    }
+}
@@ -0,0 +1,59 @@
+use ciborium::Value;
+use kartoffels_utils::CborMapExt;
+
+/// Worlds saved before the simulation state got persisted used to be resumed
+/// with fresh objects, conveyors etc. - since there's no state to recover,
+/// let's fill in the defaults the world would've been resumed with.
+pub fn run(world: &mut Value) {
+    world
+        .as_map_mut()
+        .unwrap()
+        .add_entry("clock", Value::Text("normal".into()))
+        .add_entry(
+            "convey",
+            Value::Map(vec![(
+                Value::Text("ticks".into()),
+                Value::Integer(0.into()),
+            )]),
+        )
+        .add_entry("layout", Value::Array(Default::default()))
+        .add_entry("objects", Value::Null)
+        .add_entry("paused", Value::Bool(false))
+        .add_entry("spawn", Value::Array(vec![Value::Null, Value::Null]))
+        .add_entry("spawn-points", Value::Array(Default::default()));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::storage::migrations;
+    use indoc::indoc;
+
+    #[test]
+    fn test() {
+        let given = indoc! {r#"
+          {
+            "name": "world"
+          }
+        "#};
+
+        let expected = indoc! {r#"
+          {
+            "name": "world",
+            "clock": "normal",
+            "convey": {
+              "ticks": 0
+            },
+            "layout": [],
+            "objects": null,
+            "paused": false,
+            "spawn": [
+              null,
+              null
+            ],
+            "spawn-points": []
+          }
+        "#};
+
+        migrations::tests::run(19, given, expected);
+    }
+}
@@ -1,7 +1,6 @@
 use crate::{SerializedWorld, World};
 use anyhow::{Context, Result};
 use futures_util::FutureExt;
-use maybe_owned::MaybeOwned;
 use std::future::Future;
 use std::time::{Duration, Instant};
 use tokio::{runtime, task};
@@ -51,16 +50,9 @@ pub fn run_now(world: &mut World, state: &mut State, wait: bool) {
         }
     }
 
-    let world = SerializedWorld {
-        bots: MaybeOwned::Borrowed(&world.bots),
-        map: MaybeOwned::Borrowed(&world.map),
-        mode: MaybeOwned::Borrowed(&world.mode),
-        name: MaybeOwned::Borrowed(&world.name),
-        policy: MaybeOwned::Borrowed(&world.policy),
-        theme: world.theme.as_ref().map(MaybeOwned::Borrowed),
-    };
-
-    let task = world.store(path).expect("couldn't save the world");
+    let task = SerializedWorld::new(world)
+        .store(path)
+        .expect("couldn't save the world");
 
     let task = task::spawn(
         async move {
@@ -1,12 +1,22 @@
 mod arena;
+mod cave;
 mod dungeon;
+mod file;
+mod maze;
+mod systems;
 
 pub use self::arena::*;
+pub use self::cave::*;
 pub use self::dungeon::*;
-use crate::Map;
-use anyhow::Result;
+pub use self::file::*;
+pub use self::maze::*;
+pub use self::systems::*;
+use crate::{Dir, Map, Object, Tile, TileKind};
+use anyhow::{Context, Result};
+use glam::{ivec2, IVec2};
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(tag = "type")]
@@ -14,15 +24,131 @@ pub enum Theme {
     #[serde(rename = "arena")]
     Arena(ArenaTheme),
 
+    #[serde(rename = "cave")]
+    Cave(CaveTheme),
+
     #[serde(rename = "dungeon")]
     Dungeon(DungeonTheme),
+
+    #[serde(rename = "file")]
+    File(FileTheme),
+
+    #[serde(rename = "maze")]
+    Maze(MazeTheme),
 }
 
 impl Theme {
     pub fn create_map(&self, rng: &mut impl RngCore) -> Result<Map> {
         match self {
             Theme::Arena(this) => Ok(this.create_map()),
+            Theme::Cave(this) => this.create_map(rng),
             Theme::Dungeon(this) => this.create_map(rng),
+            Theme::File(this) => this.create_map(),
+            Theme::Maze(this) => this.create_map(rng),
         }
     }
+
+    /// Returns places where bots should get spawned; empty if bots can get
+    /// spawned anywhere.
+    pub fn spawn_points(&self) -> Vec<IVec2> {
+        match self {
+            Theme::File(this) => this.spawn_points(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Returns objects that should be placed on a freshly created map.
+    pub fn objects(&self) -> Vec<(Object, IVec2)> {
+        match self {
+            Theme::File(this) => this.objects(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Picks a random floor tile and removes all floors that aren't reachable from
+/// it, together with walls that don't surround any floor anymore.
+fn remove_unreachable_tiles(
+    rng: &mut impl RngCore,
+    map: &mut Map,
+) -> Result<()> {
+    const NOT_VISITED: u8 = 0;
+    const VISITED: u8 = 1;
+
+    let room_pos = (0..1024)
+        .find_map(|_| {
+            let pos = map.sample_pos(rng);
+
+            if map.get(pos).is_floor() {
+                Some(pos)
+            } else {
+                None
+            }
+        })
+        .context("couldn't find any room")?;
+
+    map.set(
+        room_pos,
+        Tile {
+            kind: TileKind::FLOOR,
+            meta: [VISITED, 0, 0],
+        },
+    );
+
+    let mut stack = VecDeque::from_iter([room_pos]);
+
+    while let Some(pos) = stack.pop_front() {
+        for dir in Dir::all() {
+            let pos = pos + dir;
+            let tile = map.get(pos);
+
+            if tile.is_floor() && tile.meta[0] == NOT_VISITED {
+                map.set(
+                    pos,
+                    Tile {
+                        kind: TileKind::FLOOR,
+                        meta: [VISITED, 0, 0],
+                    },
+                );
+
+                stack.push_back(pos);
+            }
+        }
+    }
+
+    // ---
+
+    map.for_each_mut(|_, tile| {
+        if tile.is_floor() && tile.meta[0] == NOT_VISITED {
+            *tile = TileKind::VOID.into();
+        }
+    });
+
+    for y in 0..map.size().y {
+        for x in 0..map.size().x {
+            let pos = ivec2(x as i32, y as i32);
+            let tile = map.get(pos);
+
+            if tile.is_wall() {
+                let mut has_floor_nearby = false;
+
+                for dy in -1..=1 {
+                    for dx in -1..=1 {
+                        has_floor_nearby |=
+                            map.get(pos + ivec2(dx, dy)).is_floor();
+                    }
+                }
+
+                if !has_floor_nearby {
+                    map.set(pos, TileKind::VOID);
+                }
+            }
+        }
+    }
+
+    map.for_each_mut(|_, tile| {
+        tile.meta[0] = 0;
+    });
+
+    Ok(())
 }
@@ -0,0 +1,199 @@
+use crate::{Map, TileKind};
+use anyhow::{anyhow, Context, Result};
+use glam::{ivec2, IVec2, UVec2};
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CaveTheme {
+    size: UVec2,
+    fill_ratio: f32,
+    smoothing_passes: u32,
+}
+
+impl CaveTheme {
+    /// Creates a cave of given size.
+    ///
+    /// `fill_ratio` is the chance (0.0 ..= 1.0) of each tile starting as a
+    /// wall before the cave gets smoothed - the higher, the narrower the
+    /// tunnels; `smoothing_passes` says how many times the cellular automaton
+    /// gets applied - the more, the rounder the cave.
+    pub fn new(size: UVec2, fill_ratio: f32, smoothing_passes: u32) -> Self {
+        Self {
+            size,
+            fill_ratio,
+            smoothing_passes,
+        }
+    }
+
+    pub fn create_map(&self, rng: &mut impl RngCore) -> Result<Map> {
+        let min_floor_tiles = self.size.element_product() / 4;
+
+        for _ in 0..128 {
+            let mut walls = self.generate_noise(rng);
+
+            for _ in 0..self.smoothing_passes {
+                walls = self.smooth(&walls);
+            }
+
+            let mut map = self.render(&walls);
+
+            super::remove_unreachable_tiles(rng, &mut map)
+                .context("couldn't remove unreachable features")?;
+
+            if self.count_floor_tiles(&map) < min_floor_tiles {
+                continue;
+            }
+
+            self.render_walls(&mut map);
+
+            return Ok(map);
+        }
+
+        Err(anyhow!(
+            "couldn't generate a valid cave within the time limit"
+        ))
+    }
+
+    fn generate_noise(&self, rng: &mut impl RngCore) -> Vec<bool> {
+        let fill_ratio = self.fill_ratio.clamp(0.0, 1.0) as f64;
+
+        (0..self.size.element_product())
+            .map(|idx| {
+                let pos = self.idx_to_pos(idx as usize);
+
+                self.is_border(pos) || rng.gen_bool(fill_ratio)
+            })
+            .collect()
+    }
+
+    /// Applies one pass of the 4-5 rule: a tile becomes a wall if it's
+    /// surrounded by at least five walls and it becomes a floor if it's
+    /// surrounded by at most three walls.
+    fn smooth(&self, walls: &[bool]) -> Vec<bool> {
+        (0..walls.len())
+            .map(|idx| {
+                let pos = self.idx_to_pos(idx);
+
+                if self.is_border(pos) {
+                    return true;
+                }
+
+                let mut neighbours = 0;
+
+                for dy in -1..=1 {
+                    for dx in -1..=1 {
+                        if (dx, dy) != (0, 0)
+                            && self.is_wall(walls, pos + ivec2(dx, dy))
+                        {
+                            neighbours += 1;
+                        }
+                    }
+                }
+
+                match neighbours {
+                    0..=3 => false,
+                    4 => walls[idx],
+                    _ => true,
+                }
+            })
+            .collect()
+    }
+
+    fn render(&self, walls: &[bool]) -> Map {
+        let mut map = Map::new(self.size);
+
+        for (idx, is_wall) in walls.iter().enumerate() {
+            let tile = if *is_wall {
+                TileKind::WALL_V
+            } else {
+                TileKind::FLOOR
+            };
+
+            map.set(self.idx_to_pos(idx), tile);
+        }
+
+        map
+    }
+
+    /// Turns walls into horizontal ones where they border floor from above or
+    /// below, so that the cave's outline reads nicer.
+    fn render_walls(&self, map: &mut Map) {
+        let mut horizontal = Vec::new();
+
+        map.for_each(|pos, tile| {
+            if tile.is_wall()
+                && (map.get(pos - ivec2(0, 1)).is_floor()
+                    || map.get(pos + ivec2(0, 1)).is_floor())
+            {
+                horizontal.push(pos);
+            }
+        });
+
+        for pos in horizontal {
+            map.set(pos, TileKind::WALL_H);
+        }
+    }
+
+    fn count_floor_tiles(&self, map: &Map) -> u32 {
+        let mut tiles = 0;
+
+        map.for_each(|_, tile| {
+            if tile.is_floor() {
+                tiles += 1;
+            }
+        });
+
+        tiles
+    }
+
+    fn is_wall(&self, walls: &[bool], pos: IVec2) -> bool {
+        let size = self.size.as_ivec2();
+
+        if pos.x < 0 || pos.y < 0 || pos.x >= size.x || pos.y >= size.y {
+            return true;
+        }
+
+        walls[(pos.x + pos.y * size.x) as usize]
+    }
+
+    fn is_border(&self, pos: IVec2) -> bool {
+        let size = self.size.as_ivec2();
+
+        pos.x == 0 || pos.y == 0 || pos.x == size.x - 1 || pos.y == size.y - 1
+    }
+
+    fn idx_to_pos(&self, idx: usize) -> IVec2 {
+        let width = self.size.x as usize;
+
+        ivec2((idx % width) as i32, (idx / width) as i32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::uvec2;
+    use kartoffels_utils::Asserter;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::path::Path;
+    use test_case::test_case;
+
+    #[test_case("small", uvec2(40, 20), 0.45, 4)]
+    #[test_case("medium", uvec2(80, 60), 0.45, 4)]
+    #[test_case("medium-open", uvec2(80, 60), 0.35, 2)]
+    #[test_case("large", uvec2(128, 128), 0.45, 5)]
+    fn test(case: &str, size: UVec2, fill_ratio: f32, smoothing_passes: u32) {
+        let dir = Path::new("src").join("theme").join("cave").join("tests");
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let actual = CaveTheme::new(size, fill_ratio, smoothing_passes)
+            .create_map(&mut rng)
+            .unwrap()
+            .to_string();
+
+        Asserter::new(dir).assert(format!("{case}.txt"), actual);
+    }
+}
@@ -3,12 +3,11 @@ mod room;
 
 use self::corridor::*;
 use self::room::*;
-use crate::{Dir, Map, Tile, TileKind};
+use crate::Map;
 use anyhow::{anyhow, Context, Result};
 use glam::{ivec2, UVec2};
 use rand::{Rng, RngCore};
 use serde::{Deserialize, Serialize};
-use std::collections::VecDeque;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DungeonTheme {
@@ -30,7 +29,7 @@ impl DungeonTheme {
 
             self.render_features(&mut map, rooms, corrs);
 
-            self.remove_unreachable_features(rng, &mut map)
+            super::remove_unreachable_tiles(rng, &mut map)
                 .context("couldn't remove unreachable features")?;
 
             if self.count_occupied_tiles(&map) < min_occupied_tiles {
@@ -109,92 +108,6 @@ impl DungeonTheme {
         }
     }
 
-    fn remove_unreachable_features(
-        &self,
-        rng: &mut impl RngCore,
-        map: &mut Map,
-    ) -> Result<()> {
-        const NOT_VISITED: u8 = 0;
-        const VISITED: u8 = 1;
-
-        let room_pos = (0..1024)
-            .find_map(|_| {
-                let pos = map.sample_pos(rng);
-
-                if map.get(pos).is_floor() {
-                    Some(pos)
-                } else {
-                    None
-                }
-            })
-            .context("couldn't find any room")?;
-
-        map.set(
-            room_pos,
-            Tile {
-                kind: TileKind::FLOOR,
-                meta: [VISITED, 0, 0],
-            },
-        );
-
-        let mut stack = VecDeque::from_iter([room_pos]);
-
-        while let Some(pos) = stack.pop_front() {
-            for dir in Dir::all() {
-                let pos = pos + dir;
-                let tile = map.get(pos);
-
-                if tile.is_floor() && tile.meta[0] == NOT_VISITED {
-                    map.set(
-                        pos,
-                        Tile {
-                            kind: TileKind::FLOOR,
-                            meta: [VISITED, 0, 0],
-                        },
-                    );
-
-                    stack.push_back(pos);
-                }
-            }
-        }
-
-        // ---
-
-        map.for_each_mut(|_, tile| {
-            if tile.is_floor() && tile.meta[0] == NOT_VISITED {
-                *tile = TileKind::VOID.into();
-            }
-        });
-
-        for y in 0..map.size().y {
-            for x in 0..map.size().x {
-                let pos = ivec2(x as i32, y as i32);
-                let tile = map.get(pos);
-
-                if tile.is_wall() {
-                    let mut has_floor_nearby = false;
-
-                    for dy in -1..=1 {
-                        for dx in -1..=1 {
-                            has_floor_nearby |=
-                                map.get(pos + ivec2(dx, dy)).is_floor();
-                        }
-                    }
-
-                    if !has_floor_nearby {
-                        map.set(pos, TileKind::VOID);
-                    }
-                }
-            }
-        }
-
-        map.for_each_mut(|_, tile| {
-            tile.meta[0] = 0;
-        });
-
-        Ok(())
-    }
-
     fn count_occupied_tiles(&self, map: &Map) -> u32 {
         let mut tiles = 0;
 
@@ -0,0 +1,215 @@
+use crate::{Map, Object, TileKind};
+use anyhow::{anyhow, Context, Result};
+use glam::IVec2;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Theme that loads a hand-authored map, like:
+///
+/// ```text
+/// |-------------|
+/// |.a....=....b.|
+/// |.....---.....|
+/// |..*.......*..|
+/// |-------------|
+/// ```
+///
+/// The map is parsed with [`Map::parse()`], with letters marking spawn points
+/// (and turning into floor) - note that `v` is a conveyor, not a spawn point.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(try_from = "SerializedFileTheme", into = "SerializedFileTheme")]
+pub struct FileTheme {
+    source: String,
+    map: Map,
+    spawn_points: Vec<IVec2>,
+    objects: Vec<(Object, IVec2)>,
+}
+
+impl FileTheme {
+    pub fn new(source: impl Into<String>) -> Result<Self> {
+        let source = source.into();
+        let (mut map, anchors) =
+            Map::parse(source.trim_end_matches(['\r', '\n']))?;
+
+        if map.size().x == 0 || map.size().y == 0 {
+            return Err(anyhow!("map is empty"));
+        }
+
+        anchors.fill(&mut map, TileKind::FLOOR);
+
+        let mut has_floor = false;
+
+        map.for_each(|_, tile| {
+            has_floor |= tile.is_floor();
+        });
+
+        if !has_floor {
+            return Err(anyhow!("map doesn't contain any floor"));
+        }
+
+        Ok(Self {
+            source,
+            map,
+            spawn_points: anchors.iter().map(|(_, pos)| pos).collect(),
+            objects: anchors.objects().to_vec(),
+        })
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let source = fs::read_to_string(path)
+            .with_context(|| format!("couldn't read {}", path.display()))?;
+
+        Self::new(source)
+            .with_context(|| format!("couldn't load {}", path.display()))
+    }
+
+    pub fn create_map(&self) -> Result<Map> {
+        Ok(self.map.clone())
+    }
+
+    pub fn spawn_points(&self) -> Vec<IVec2> {
+        self.spawn_points.clone()
+    }
+
+    pub fn objects(&self) -> Vec<(Object, IVec2)> {
+        self.objects.clone()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedFileTheme {
+    source: String,
+}
+
+impl TryFrom<SerializedFileTheme> for FileTheme {
+    type Error = anyhow::Error;
+
+    fn try_from(this: SerializedFileTheme) -> Result<Self> {
+        Self::new(this.source)
+    }
+}
+
+impl From<FileTheme> for SerializedFileTheme {
+    fn from(this: FileTheme) -> Self {
+        Self {
+            source: this.source,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Dir, ObjectKind};
+    use glam::{ivec2, uvec2};
+    use indoc::indoc;
+
+    #[test]
+    fn parse() {
+        let theme = FileTheme::new(indoc! {"
+            |-------|
+            |.a.=.b.|
+            |.*.%.#.|
+            |-------|
+        "})
+        .unwrap();
+
+        let map = theme.create_map().unwrap();
+
+        assert_eq!(uvec2(9, 4), map.size());
+
+        assert_eq!(
+            indoc! {"
+                |-------|
+                |.......|
+                |...%.#.|
+                |-------|"
+            },
+            map.to_string()
+        );
+
+        assert_eq!(vec![ivec2(2, 1), ivec2(6, 1)], theme.spawn_points());
+
+        assert_eq!(
+            vec![
+                (Object::new(ObjectKind::FLAG), ivec2(4, 1)),
+                (Object::new(ObjectKind::GEM), ivec2(2, 2)),
+            ],
+            theme.objects()
+        );
+    }
+
+    #[test]
+    fn teleporters() {
+        let mut map =
+            FileTheme::new(".&.&.&.&.").unwrap().create_map().unwrap();
+
+        assert_eq!(Some(ivec2(3, 0)), map.teleporter_pair(ivec2(1, 0)));
+        assert_eq!(Some(ivec2(1, 0)), map.teleporter_pair(ivec2(3, 0)));
+        assert_eq!(Some(ivec2(7, 0)), map.teleporter_pair(ivec2(5, 0)));
+        assert_eq!(Some(ivec2(5, 0)), map.teleporter_pair(ivec2(7, 0)));
+        assert_eq!(None, map.teleporter_pair(ivec2(0, 0)));
+
+        // Pairs get rebuilt once the map changes
+        map.set(ivec2(3, 0), TileKind::FLOOR);
+
+        assert_eq!(None, map.teleporter_pair(ivec2(1, 0)));
+        assert_eq!(Some(ivec2(7, 0)), map.teleporter_pair(ivec2(5, 0)));
+    }
+
+    #[test]
+    fn conveyors() {
+        let map = FileTheme::new(".^>v<.").unwrap().create_map().unwrap();
+
+        for (x, dir) in [(1, Dir::N), (2, Dir::E), (3, Dir::S), (4, Dir::W)] {
+            let tile = map.get(ivec2(x, 0));
+
+            assert_eq!(TileKind::CONVEYOR, tile.kind);
+            assert_eq!(dir, Dir::from(tile.meta[0]));
+        }
+
+        assert!(FileTheme::new(".v.").unwrap().spawn_points().is_empty());
+    }
+
+    #[test]
+    fn err_unknown_tile() {
+        let err = FileTheme::new(indoc! {"
+            |-----|
+            |..@..|
+            |-----|
+        "})
+        .unwrap_err();
+
+        assert_eq!("line 2, column 4: unknown tile `@`", err.to_string());
+    }
+
+    #[test]
+    fn err_unpaired_teleporter() {
+        let err = FileTheme::new(indoc! {"
+            |.&.|
+            |.&.|
+            |.&.|
+        "})
+        .unwrap_err();
+
+        assert_eq!(
+            "line 3, column 3: teleporter doesn't have a pair",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn err_empty() {
+        let err = FileTheme::new("").unwrap_err();
+
+        assert_eq!("map is empty", err.to_string());
+    }
+
+    #[test]
+    fn err_no_floor() {
+        let err = FileTheme::new("|---|").unwrap_err();
+
+        assert_eq!("map doesn't contain any floor", err.to_string());
+    }
+}
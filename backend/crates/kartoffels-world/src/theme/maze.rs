@@ -0,0 +1,316 @@
+use crate::{Dir, Map, MapBuilder, TileKind};
+use anyhow::{anyhow, Result};
+use futures_util::FutureExt;
+use glam::{ivec2, uvec2, IVec2, UVec2};
+use rand::{Rng, RngCore};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MazeTheme {
+    size: UVec2,
+    corridor_width: u32,
+    loop_chance: f32,
+}
+
+impl MazeTheme {
+    /// Creates a maze of given size.
+    ///
+    /// `corridor_width` says how wide (in tiles) the corridors are;
+    /// `loop_chance` is the chance (0.0 ..= 1.0) of knocking down each wall
+    /// left after the maze is carved - zero yields an acyclic maze (with
+    /// exactly one path between any two tiles), anything above introduces
+    /// loops.
+    pub fn new(size: UVec2, corridor_width: u32, loop_chance: f32) -> Self {
+        Self {
+            size,
+            corridor_width,
+            loop_chance,
+        }
+    }
+
+    /// Creates a maze using the randomized Prim's algorithm (see:
+    /// [`Self::build()`]), with an optional sprinkle of loops on top.
+    pub fn create_map(&self, rng: &mut impl RngCore) -> Result<Map> {
+        let cells = self.cells();
+
+        if self.corridor_width == 0 || cells.x == 0 || cells.y == 0 {
+            return Err(anyhow!(
+                "maze of size {} can't fit corridors of width {}",
+                self.size,
+                self.corridor_width
+            ));
+        }
+
+        let head = self.cell_origin(
+            uvec2(rng.gen_range(0..cells.x), rng.gen_range(0..cells.y))
+                .as_ivec2(),
+        );
+
+        // Leave room for the walls on the right and bottom
+        let area = Self {
+            size: cells * (self.corridor_width + 1),
+            ..self.clone()
+        };
+
+        let (mut map, rx) = MapBuilder::new();
+
+        // With nobody listening for the updates, building never has to wait
+        drop(rx);
+        map.init(self.size);
+
+        area.build(&mut map, rng, head)
+            .now_or_never()
+            .expect("building a maze shouldn't block");
+
+        let mut map = map.finish();
+
+        self.render_walls(&mut map);
+
+        Ok(map)
+    }
+
+    /// Draws the maze onto given builder, starting from the cell at `head`.
+    ///
+    /// Cells are laid out every `corridor_width + 1` tiles from `head`, so
+    /// e.g. for corridors one tile wide, a maze started at `(1, 1)` has its
+    /// corridors on odd coordinates and walls on even ones. Only cells that
+    /// fit within the maze's size are visited, and only walls around the
+    /// visited cells are drawn, so the caller can surround the maze with
+    /// something else.
+    pub async fn build(
+        &self,
+        map: &mut MapBuilder,
+        rng: &mut impl RngCore,
+        head: IVec2,
+    ) {
+        const NOT_VISITED: u8 = 0;
+        const VISITED: u8 = 1;
+
+        let width = self.corridor_width as i32;
+        let mut frontier = Vec::new();
+
+        for dir in Dir::shuffled(rng) {
+            frontier.push((head, dir));
+        }
+
+        map.update(|map| {
+            map.get_mut(head).meta[0] = VISITED;
+        });
+
+        while !frontier.is_empty() {
+            let idx = rng.gen_range(0..frontier.len());
+            let (src, dir) = frontier.swap_remove(idx);
+            let dst = src + dir.as_vec() * (width + 1);
+
+            if map.get(src).is_void() {
+                map.update(|map| {
+                    for pos in self.cell_tiles(src) {
+                        map.get_mut(pos).kind = TileKind::FLOOR;
+                    }
+                });
+
+                for dy in 0..width {
+                    let y = src.y + dy;
+
+                    map.set_if_void(ivec2(src.x - 1, y), TileKind::WALL_V)
+                        .await;
+
+                    map.set_if_void(ivec2(src.x + width, y), TileKind::WALL_V)
+                        .await;
+                }
+
+                for dx in 0..width {
+                    let x = src.x + dx;
+
+                    map.set_if_void(ivec2(x, src.y - 1), TileKind::WALL_H)
+                        .await;
+
+                    map.set_if_void(ivec2(x, src.y + width), TileKind::WALL_H)
+                        .await;
+                }
+            }
+
+            if self.contains_cell(dst) && map.get(dst).meta[0] == NOT_VISITED {
+                map.update(|map| {
+                    map.get_mut(dst).meta[0] = VISITED;
+                });
+
+                self.carve_passage(map, src, dir).await;
+
+                for dir in Dir::shuffled(rng) {
+                    frontier.push((dst, dir));
+                }
+            }
+        }
+
+        if self.loop_chance > 0.0 {
+            let loop_chance = self.loop_chance.min(1.0) as f64;
+            let size = self.size.as_ivec2();
+            let start = head.rem_euclid(IVec2::splat(width + 1));
+
+            for y in (start.y..size.y).step_by(width as usize + 1) {
+                for x in (start.x..size.x).step_by(width as usize + 1) {
+                    let src = ivec2(x, y);
+
+                    for dir in [Dir::E, Dir::S] {
+                        let dst = src + dir.as_vec() * (width + 1);
+
+                        if self.contains_cell(src)
+                            && self.contains_cell(dst)
+                            && map.get(src).is_floor()
+                            && map.get(dst).is_floor()
+                            && rng.gen_bool(loop_chance)
+                        {
+                            self.carve_passage(map, src, dir).await;
+                        }
+                    }
+                }
+            }
+        }
+
+        map.update(|map| {
+            map.for_each_mut(|_, tile| {
+                tile.meta[0] = 0;
+            });
+        });
+    }
+
+    /// Knocks down the wall between cell at `src` and its neighbour in given
+    /// direction, putting walls on both sides of the passage.
+    async fn carve_passage(&self, map: &mut MapBuilder, src: IVec2, dir: Dir) {
+        let width = self.corridor_width as i32;
+
+        let mid = match dir {
+            Dir::N => src - ivec2(0, 1),
+            Dir::E => src + ivec2(width, 0),
+            Dir::S => src + ivec2(0, width),
+            Dir::W => src - ivec2(1, 0),
+        };
+
+        match dir {
+            Dir::N | Dir::S => {
+                for dx in 0..width {
+                    map.set(mid + ivec2(dx, 0), TileKind::FLOOR).await;
+                }
+
+                map.set(mid - ivec2(1, 0), TileKind::WALL_V).await;
+                map.set(mid + ivec2(width, 0), TileKind::WALL_V).await;
+            }
+
+            Dir::E | Dir::W => {
+                for dy in 0..width {
+                    map.set(mid + ivec2(0, dy), TileKind::FLOOR).await;
+                }
+
+                map.set(mid - ivec2(0, 1), TileKind::WALL_H).await;
+                map.set(mid + ivec2(0, width), TileKind::WALL_H).await;
+            }
+        }
+    }
+
+    fn render_walls(&self, map: &mut Map) {
+        let step = self.corridor_width as i32 + 1;
+        let max = self.cells().as_ivec2() * step;
+        let is_line = |v: i32| v % step == 0;
+
+        for y in 0..=max.y {
+            for x in 0..=max.x {
+                let pos = ivec2(x, y);
+
+                if map.get(pos).is_floor() {
+                    continue;
+                }
+
+                let tile = match (is_line(y), is_line(x)) {
+                    (true, false) => TileKind::WALL_H,
+                    (false, true) => TileKind::WALL_V,
+
+                    // Corners follow the direction most walls around them go
+                    _ => {
+                        let is_wall = |pos: IVec2| {
+                            pos.cmpge(IVec2::ZERO).all()
+                                && pos.cmple(max).all()
+                                && !map.get(pos).is_floor()
+                        };
+
+                        let h = is_wall(pos - ivec2(1, 0)) as u8
+                            + is_wall(pos + ivec2(1, 0)) as u8;
+
+                        let v = is_wall(pos - ivec2(0, 1)) as u8
+                            + is_wall(pos + ivec2(0, 1)) as u8;
+
+                        if v > h {
+                            TileKind::WALL_V
+                        } else {
+                            TileKind::WALL_H
+                        }
+                    }
+                };
+
+                map.set(pos, tile);
+            }
+        }
+    }
+
+    fn cells(&self) -> UVec2 {
+        (self.size.max(UVec2::ONE) - 1) / (self.corridor_width + 1)
+    }
+
+    /// Returns whether cell with given top-left corner fits within the maze.
+    fn contains_cell(&self, cell: IVec2) -> bool {
+        let max = self.size.as_ivec2() - self.corridor_width as i32;
+
+        cell.x >= 0 && cell.y >= 0 && cell.x <= max.x && cell.y <= max.y
+    }
+
+    fn cell_tiles(&self, cell: IVec2) -> impl Iterator<Item = IVec2> {
+        let width = self.corridor_width as i32;
+
+        (0..width)
+            .flat_map(move |dy| (0..width).map(move |dx| cell + ivec2(dx, dy)))
+    }
+
+    fn cell_origin(&self, cell: IVec2) -> IVec2 {
+        IVec2::ONE + cell * (self.corridor_width as i32 + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kartoffels_utils::Asserter;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::path::Path;
+    use test_case::test_case;
+
+    #[test_case("acyclic", uvec2(41, 21), 1, 0.0)]
+    #[test_case("cyclic", uvec2(41, 21), 1, 0.2)]
+    #[test_case("wide", uvec2(61, 31), 2, 0.0)]
+    fn test(case: &str, size: UVec2, corridor_width: u32, loop_chance: f32) {
+        let dir = Path::new("src").join("theme").join("maze").join("tests");
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let actual = MazeTheme::new(size, corridor_width, loop_chance)
+            .create_map(&mut rng)
+            .unwrap()
+            .to_string();
+
+        Asserter::new(dir).assert(format!("{case}.txt"), actual);
+    }
+
+    #[test]
+    fn err_too_small() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let err = MazeTheme::new(uvec2(3, 3), 2, 0.0)
+            .create_map(&mut rng)
+            .unwrap_err();
+
+        assert_eq!(
+            "maze of size [3, 3] can't fit corridors of width 2",
+            err.to_string()
+        );
+    }
+}
@@ -0,0 +1,18 @@
+use crate::{Event, World};
+
+/// Places objects defined by the theme and refreshes its spawn points - called
+/// whenever the map gets created or loaded.
+pub fn run(world: &mut World) {
+    let Some(theme) = &world.theme else {
+        world.spawn_points.clear();
+        return;
+    };
+
+    world.spawn_points = theme.spawn_points();
+
+    for (obj, pos) in theme.objects() {
+        let id = world.objects.create(&mut world.rng, obj, Some(pos));
+
+        world.events.add(Event::ObjectCreated { id, at: Some(pos) });
+    }
+}
@@ -1,7 +1,9 @@
 use glam::{ivec2, uvec2};
+use indoc::indoc;
 use kartoffels_bots::{DUMMY, ROBERTO};
-use kartoffels_utils::Asserter;
+use kartoffels_utils::{Asserter, Id};
 use kartoffels_world::prelude::*;
+use std::collections::{BTreeMap, BTreeSet};
 use std::future::Future;
 use std::path::Path;
 use std::sync::Arc;
@@ -85,6 +87,190 @@ async fn pause_and_resume() {
     assert_ne!(snap3.bots(), snap4.bots());
 }
 
+#[tokio::test]
+async fn record_and_replay() {
+    let world = kartoffels_world::create(config());
+
+    for _ in 0..8 {
+        world
+            .create_bot(CreateBotRequest::new(ROBERTO))
+            .await
+            .unwrap();
+    }
+
+    for _ in 0..32 {
+        world.tick().await.unwrap();
+    }
+
+    world.start_recording().await.unwrap();
+
+    for _ in 0..8 {
+        world
+            .create_bot(CreateBotRequest::new(ROBERTO))
+            .await
+            .unwrap();
+    }
+
+    world
+        .create_object(Object::new(ObjectKind::GEM), None)
+        .await
+        .unwrap();
+
+    for _ in 0..64 {
+        world.tick().await.unwrap();
+    }
+
+    let expected = world.snapshot().await;
+    let replay = world.stop_recording().await.unwrap().unwrap();
+
+    // Make sure the recording survives serialization
+    let replay: Replay = ciborium::Value::serialized(&replay)
+        .unwrap()
+        .deserialized()
+        .unwrap();
+
+    let mut replayer = Replayer::new(replay).unwrap();
+
+    while replayer.tick() {}
+
+    let actual = replayer.snapshot();
+
+    // Comparing bots directly would fail, because bot events contain wall-clock
+    // timestamps
+    assert_eq!(expected.map(), actual.map());
+    assert_eq!(expected.to_string(), actual.to_string());
+    assert!(world.stop_recording().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn rewind() {
+    let world = kartoffels_world::create(Config {
+        checkpoints: true,
+        ..config()
+    });
+
+    for _ in 0..8 {
+        world
+            .create_bot(CreateBotRequest::new(ROBERTO))
+            .await
+            .unwrap();
+    }
+
+    for _ in 0..600 {
+        world.tick().await.unwrap();
+    }
+
+    let expected = world.snapshot().await;
+
+    for _ in 0..100 {
+        world.tick().await.unwrap();
+    }
+
+    // Rewinding happens at the beginning of a tick, which then gets simulated
+    // as usual - so we have to go back one tick further
+    assert_eq!(101, world.rewind(101).await.unwrap());
+
+    let actual = world.snapshot().await;
+
+    assert_eq!(expected.map(), actual.map());
+    assert_eq!(expected.to_string(), actual.to_string());
+}
+
+#[tokio::test]
+async fn fork() {
+    let world = kartoffels_world::create(config());
+
+    for _ in 0..8 {
+        world
+            .create_bot(CreateBotRequest::new(ROBERTO))
+            .await
+            .unwrap();
+    }
+
+    for _ in 0..32 {
+        world.tick().await.unwrap();
+    }
+
+    let forked = world.fork().await.unwrap();
+
+    // Forking happens at the beginning of a tick, which then gets simulated in
+    // the original world - so let's catch up
+    forked.tick().await.unwrap();
+
+    for _ in 0..64 {
+        world.tick().await.unwrap();
+        forked.tick().await.unwrap();
+    }
+
+    let expected = world.snapshot().await;
+    let actual = forked.snapshot().await;
+
+    assert_eq!(expected.map(), actual.map());
+    assert_eq!(expected.to_string(), actual.to_string());
+    assert_ne!(world.id(), forked.id());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn save_and_resume() {
+    let dir = std::env::temp_dir()
+        .join(format!("kartoffels-save-and-resume-{}", std::process::id()));
+
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let path = dir.join("world.world");
+
+    let saved = kartoffels_world::create(Config {
+        path: Some(path.clone()),
+        ..config()
+    });
+
+    let expected = kartoffels_world::create(config());
+
+    for world in [&saved, &expected] {
+        let mut ids = Vec::new();
+
+        for _ in 0..16 {
+            ids.push(
+                world
+                    .create_bot(CreateBotRequest::new(ROBERTO))
+                    .await
+                    .unwrap(),
+            );
+        }
+
+        world
+            .create_object(Object::new(ObjectKind::GEM), None)
+            .await
+            .unwrap();
+
+        for _ in 0..256 {
+            world.tick().await.unwrap();
+        }
+
+        // Make a hole in the layout of alive bots, which affects the order in
+        // which they get ticked
+        world.delete_bot(ids[4]).await.unwrap();
+    }
+
+    saved.shutdown().await.unwrap();
+
+    let resumed = kartoffels_world::resume(Id::new(1), &path).unwrap();
+
+    for _ in 0..256 {
+        resumed.tick().await.unwrap();
+        expected.tick().await.unwrap();
+    }
+
+    let expected = expected.snapshot().await;
+    let actual = resumed.snapshot().await;
+
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(expected.map(), actual.map());
+    assert_eq!(expected.objects(), actual.objects());
+    assert_eq!(expected.to_string(), actual.to_string());
+}
+
 #[tokio::test]
 async fn kill_bot() {
     let world = kartoffels_world::create(config());
@@ -196,6 +382,252 @@ async fn set_map() {
     assert_eq!(uvec2(22, 11), world.snapshot().await.raw_map().size());
 }
 
+#[tokio::test]
+async fn events() {
+    let world = kartoffels_world::create(Config {
+        events: true,
+        ..config()
+    });
+
+    world.tick().await.unwrap();
+
+    // ---
+
+    let mut events = world
+        .events(EventFilter::only([
+            EventKind::ObjectCreated,
+            EventKind::ObjectDeleted,
+            EventKind::MapChanged,
+        ]))
+        .unwrap();
+
+    let obj = world
+        .create_object(Object::new(ObjectKind::GEM), ivec2(1, 1))
+        .await
+        .unwrap();
+
+    world.delete_object(obj).await.unwrap();
+    world.set_map(Map::new(uvec2(11, 22))).await.unwrap();
+
+    assert_eq!(
+        Event::ObjectCreated {
+            id: obj,
+            at: Some(ivec2(1, 1)),
+        },
+        events.next().await.unwrap().event,
+    );
+
+    assert_eq!(
+        Event::ObjectDeleted { id: obj },
+        events.next().await.unwrap().event,
+    );
+
+    assert_eq!(Event::MapChanged, events.next().await.unwrap().event);
+
+    // ---
+
+    let world = kartoffels_world::create(Config {
+        events: true,
+        ..config()
+    });
+
+    let mut events = world
+        .events(EventFilter::only([EventKind::BotSpawned]))
+        .unwrap();
+
+    world
+        .create_bot(CreateBotRequest::new(ROBERTO).instant())
+        .await
+        .unwrap();
+
+    let Event::BotSpawned { id, .. } = events.next().await.unwrap().event
+    else {
+        unreachable!();
+    };
+
+    let mut events = world.events(EventFilter::all().with_bot(id)).unwrap();
+
+    let task = tokio::spawn(async move {
+        let mut kinds = Vec::new();
+
+        while kinds.len() < 3 {
+            let event = events.next().await.unwrap().event;
+
+            assert!(event.involves(id));
+
+            if matches!(
+                event.kind(),
+                EventKind::BotMoved
+                    | EventKind::BotTurned
+                    | EventKind::BotScanned
+            ) && !kinds.contains(&event.kind())
+            {
+                kinds.push(event.kind());
+            }
+        }
+    });
+
+    for _ in 0..1024 {
+        if task.is_finished() {
+            break;
+        }
+
+        world.tick().await.unwrap();
+    }
+
+    // Dropping the world closes the stream, failing the task if it's still
+    // waiting for some events
+    drop(world);
+
+    task.await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn event_log() {
+    let dir = std::env::temp_dir()
+        .join(format!("kartoffels-event-log-{}", std::process::id()));
+
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let path = dir.join("world.world");
+
+    let world = kartoffels_world::create(Config {
+        path: Some(path.clone()),
+        ..config()
+    });
+
+    world
+        .start_event_log(EventFilter::lifecycle())
+        .await
+        .unwrap();
+
+    let id = world
+        .create_bot(CreateBotRequest::new(DUMMY).instant().oneshot())
+        .await
+        .unwrap();
+
+    world.kill_bot(id, "because").await.unwrap();
+    world.shutdown().await.unwrap();
+
+    let events: Vec<_> = EventLog::read(&path.with_extension("events"))
+        .unwrap()
+        .map(|entry| entry.unwrap().event)
+        .collect();
+
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    // Only events matching the filter get logged
+    assert!(events
+        .iter()
+        .all(|event| EventFilter::lifecycle().matches(event)));
+
+    let events: Vec<_> = events
+        .into_iter()
+        .filter(|event| event.involves(id))
+        .collect();
+
+    assert_eq!(
+        vec![
+            EventKind::BotCreated,
+            EventKind::BotSpawned,
+            EventKind::BotKilled
+        ],
+        events.iter().map(|event| event.kind()).collect::<Vec<_>>()
+    );
+
+    assert!(matches!(
+        &events[2],
+        Event::BotKilled { reason, killer: None, .. } if reason == "because"
+    ));
+}
+
+#[tokio::test]
+async fn snapshot_deltas() {
+    let world = kartoffels_world::create(config());
+
+    for _ in 0..16 {
+        world
+            .create_bot(CreateBotRequest::new(ROBERTO))
+            .await
+            .unwrap();
+    }
+
+    let mut deltas = world.snapshots().into_deltas();
+    let mut map = Map::default();
+    let mut bots = BTreeMap::new();
+    let mut dead = BTreeSet::new();
+    let mut applied = 0;
+
+    for idx in 0..128 {
+        // Changing the map (without resizing it) should yield only the tiles
+        // that have changed - note that on the manual clock each request
+        // advances the world, so this substitutes the tick
+        if idx == 64 {
+            let mut map = world.snapshot().await.raw_map().clone();
+
+            map.set(ivec2(12, 12), TileKind::WATER);
+            world.set_map(map).await.unwrap();
+        } else {
+            world.tick().await.unwrap();
+        }
+
+        match deltas.next().await.unwrap() {
+            SnapshotUpdate::Full(snapshot) => {
+                map = snapshot.map().clone();
+
+                bots = snapshot
+                    .bots()
+                    .alive()
+                    .iter()
+                    .map(|bot| (bot.id, bot.pos))
+                    .collect();
+
+                dead = snapshot.bots().dead().ids().collect();
+            }
+
+            SnapshotUpdate::Delta(delta) => {
+                delta.apply_to_map(&mut map);
+
+                for bot in &delta.bots {
+                    bots.insert(bot.id, bot.pos);
+                }
+
+                for id in &delta.bots_removed {
+                    bots.remove(id);
+                }
+
+                dead.extend(&delta.dead);
+
+                for id in &delta.dead_removed {
+                    dead.remove(id);
+                }
+
+                applied += 1;
+            }
+        }
+
+        let snapshot = world.snapshot().await;
+
+        assert_eq!(snapshot.map(), &map);
+
+        assert_eq!(
+            snapshot
+                .bots()
+                .alive()
+                .iter()
+                .map(|bot| (bot.id, bot.pos))
+                .collect::<BTreeMap<_, _>>(),
+            bots
+        );
+
+        assert_eq!(snapshot.bots().dead().ids().collect::<BTreeSet<_>>(), dead);
+    }
+
+    // The first update is always complete, the rest should be deltas, since
+    // we're keeping up with the world
+    assert_eq!(127, applied);
+}
+
 #[tokio::test]
 async fn set_spawn() {
     let world = kartoffels_world::create(config());
@@ -257,7 +689,7 @@ async fn set_spawn() {
         .collect();
 
     let expected =
-        vec![ivec2(21, 7), ivec2(10, 9), ivec2(15, 19), ivec2(16, 1)];
+        vec![ivec2(19, 13), ivec2(10, 9), ivec2(15, 19), ivec2(16, 1)];
 
     assert_eq!(expected, actual);
 }
@@ -348,11 +780,429 @@ async fn without_auto_respawn() {
     assert_eq!(expected, actual);
 }
 
+#[tokio::test]
+async fn capture_the_flag() {
+    let bases = [ivec2(8, 12), ivec2(16, 12)];
+
+    let world = kartoffels_world::create(Config {
+        mode: Mode::new(CaptureTheFlagMode::with_bases(bases)),
+        ..config()
+    });
+
+    let bot1 = world
+        .create_bot(CreateBotRequest::new(DUMMY).at(ivec2(12, 10)))
+        .await
+        .unwrap();
+
+    let bot2 = world
+        .create_bot(CreateBotRequest::new(DUMMY).at(ivec2(12, 14)))
+        .await
+        .unwrap();
+
+    world.tick().await.unwrap();
+
+    let snapshot = world.snapshot().await;
+
+    // ---
+
+    let mut flags: Vec<_> = snapshot
+        .objects()
+        .iter()
+        .map(|obj| (obj.obj.kind, obj.obj.meta[0], obj.pos))
+        .collect();
+
+    flags.sort_unstable_by_key(|(_, team, _)| *team);
+
+    assert_eq!(
+        vec![
+            (ObjectKind::FLAG, 1, Some(bases[0])),
+            (ObjectKind::FLAG, 2, Some(bases[1])),
+        ],
+        flags,
+    );
+
+    // ---
+
+    let team1 = snapshot.bots().alive().get(bot1).unwrap().team;
+    let team2 = snapshot.bots().alive().get(bot2).unwrap().team;
+
+    assert_eq!(Some(0), team1);
+    assert_eq!(Some(1), team2);
+}
+
+#[tokio::test]
+async fn teams() {
+    let world = kartoffels_world::create(config());
+
+    let bot1 = world
+        .create_bot(CreateBotRequest::new(DUMMY).team(3))
+        .await
+        .unwrap();
+
+    let bot2 = world
+        .create_bot(CreateBotRequest::new(DUMMY).team(3))
+        .await
+        .unwrap();
+
+    let bot3 = world
+        .create_bot(CreateBotRequest::new(DUMMY).team(254))
+        .await
+        .unwrap();
+
+    let bot4 = world
+        .create_bot(CreateBotRequest::new(DUMMY))
+        .await
+        .unwrap();
+
+    world.tick().await.unwrap();
+
+    let snapshot = world.snapshot().await;
+    let bots = snapshot.bots().alive();
+
+    assert_eq!(Some(3), bots.get(bot1).unwrap().team);
+    assert_eq!(Some(3), bots.get(bot2).unwrap().team);
+    assert_eq!(Some(254), bots.get(bot3).unwrap().team);
+    assert_eq!(None, bots.get(bot4).unwrap().team);
+
+    let teams: Vec<_> = snapshot
+        .teams()
+        .iter()
+        .map(|team| (team.id, team.bots, team.score))
+        .collect();
+
+    assert_eq!(vec![(3, 2, 0), (254, 1, 0)], teams);
+}
+
+#[tokio::test]
+async fn king_of_the_hill() {
+    let world = kartoffels_world::create(Config {
+        mode: Mode::new(KingOfTheHillMode::with_zone(ivec2(12, 12), 1)),
+        ..config()
+    });
+
+    let bot1 = world
+        .create_bot(CreateBotRequest::new(DUMMY).at(ivec2(12, 12)))
+        .await
+        .unwrap();
+
+    let bot2 = world
+        .create_bot(CreateBotRequest::new(DUMMY).at(ivec2(4, 12)))
+        .await
+        .unwrap();
+
+    world.tick().await.unwrap();
+
+    let snapshot = world.snapshot().await;
+    let bots = snapshot.bots().alive();
+
+    assert!(bots.get(bot1).unwrap().score > 0);
+    assert_eq!(0, bots.get(bot2).unwrap().score);
+
+    assert_eq!(
+        Some(Zone {
+            center: ivec2(12, 12),
+            radius: 1,
+        }),
+        snapshot.zone(),
+    );
+}
+
+#[tokio::test]
+async fn gem_collection() {
+    let world = kartoffels_world::create(Config {
+        mode: Mode::new(
+            GemCollectionMode::new(0, 4).with_deposit(ivec2(12, 12)),
+        ),
+        ..config()
+    });
+
+    world.tick().await.unwrap();
+
+    let snapshot = world.snapshot().await;
+
+    let gems: Vec<_> = snapshot
+        .objects()
+        .iter()
+        .filter(|obj| obj.obj.kind == ObjectKind::GEM)
+        .filter_map(|obj| obj.pos)
+        .collect();
+
+    assert_eq!(4, gems.len());
+    assert!(!gems.contains(&ivec2(12, 12)));
+
+    assert_eq!(
+        Some(Zone {
+            center: ivec2(12, 12),
+            radius: 0,
+        }),
+        snapshot.zone(),
+    );
+}
+
+#[tokio::test]
+async fn last_bot_standing() {
+    let world = kartoffels_world::create(Config {
+        mode: Mode::new(LastBotStandingMode::default()),
+        ..config()
+    });
+
+    let bot1 = world
+        .create_bot(CreateBotRequest::new(DUMMY))
+        .await
+        .unwrap();
+
+    let bot2 = world
+        .create_bot(CreateBotRequest::new(DUMMY))
+        .await
+        .unwrap();
+
+    world.tick().await.unwrap();
+    world.kill_bot(bot1, "killed manually").await.unwrap();
+    world.tick().await.unwrap();
+
+    let snapshot = world.snapshot().await;
+    let rounds = snapshot.rounds().unwrap();
+
+    assert_eq!(1, rounds.len());
+    assert_eq!(1, rounds[0].nth);
+    assert_eq!(Some(bot2), rounds[0].winner);
+    assert_eq!(2, rounds[0].bots);
+
+    // ---
+
+    world.tick().await.unwrap();
+
+    let snapshot = world.snapshot().await;
+
+    assert!(snapshot.bots().alive().get(bot1).is_some());
+    assert!(snapshot.bots().alive().get(bot2).is_some());
+}
+
+#[tokio::test]
+async fn racing() {
+    let world = kartoffels_world::create(Config {
+        mode: Mode::new(RacingMode::new(4).unwrap()),
+        ..config()
+    });
+
+    world.tick().await.unwrap();
+
+    let snapshot = world.snapshot().await;
+    let checkpoints = snapshot.checkpoints();
+
+    assert_eq!(4, checkpoints.len());
+
+    for checkpoint in checkpoints {
+        assert!(snapshot.raw_map().get(*checkpoint).is_floor());
+    }
+
+    assert_eq!(Some(&[][..]), snapshot.leaderboard());
+}
+
+#[tokio::test]
+async fn file_theme() {
+    let theme = FileTheme::new(indoc! {"
+        |-------|
+        |a..=..b|
+        |...*...|
+        |-------|
+    "})
+    .unwrap();
+
+    let world = kartoffels_world::create(Config {
+        theme: Some(Theme::File(theme)),
+        ..config()
+    });
+
+    for _ in 0..3 {
+        world
+            .create_bot(CreateBotRequest::new(DUMMY))
+            .await
+            .unwrap();
+    }
+
+    world.tick().await.unwrap();
+
+    let snapshot = world.snapshot().await;
+
+    // There are only two spawn points, so the third bot remains queued
+    let mut bots: Vec<_> =
+        snapshot.bots().alive().iter().map(|bot| bot.pos).collect();
+
+    bots.sort_by_key(|pos| pos.x);
+
+    assert_eq!(vec![ivec2(1, 1), ivec2(7, 1)], bots);
+
+    let mut objects: Vec<_> = snapshot
+        .objects()
+        .iter()
+        .map(|obj| (obj.obj.kind, obj.pos))
+        .collect();
+
+    objects.sort_by_key(|(kind, _)| *kind);
+
+    assert_eq!(
+        vec![
+            (ObjectKind::GEM, Some(ivec2(4, 2))),
+            (ObjectKind::FLAG, Some(ivec2(4, 1))),
+        ],
+        objects
+    );
+}
+
+#[tokio::test]
+async fn conveyors_and_teleporters() {
+    let world = kartoffels_world::create(Config {
+        theme: None,
+        ..config()
+    });
+
+    let mut map = Map::new(uvec2(7, 3));
+
+    map.fill(TileKind::FLOOR);
+    world.set_map(map.clone()).await.unwrap();
+
+    let bot = world
+        .create_bot(CreateBotRequest::new(DUMMY).at(ivec2(0, 1)))
+        .await
+        .unwrap();
+
+    world.tick().await.unwrap();
+
+    // ---
+
+    map.set(ivec2(0, 1), TileKind::CONVEYOR);
+    map.set(ivec2(1, 1), TileKind::CONVEYOR);
+    map.set(ivec2(2, 1), TileKind::TELEPORTER);
+    map.set(ivec2(5, 2), TileKind::TELEPORTER);
+    map.get_mut(ivec2(0, 1)).meta[0] = Dir::E.into();
+    map.get_mut(ivec2(1, 1)).meta[0] = Dir::E.into();
+
+    world.set_map(map).await.unwrap();
+
+    let mut history = Vec::new();
+
+    for _ in 0..100 {
+        world.tick().await.unwrap();
+
+        let pos = world.snapshot().await.bots().alive().get(bot).unwrap().pos;
+
+        if history.last() != Some(&pos) {
+            history.push(pos);
+        }
+    }
+
+    assert_eq!(vec![ivec2(0, 1), ivec2(1, 1), ivec2(5, 2)], history);
+}
+
+#[tokio::test]
+async fn wasm() {
+    let code = wat::parse_str(
+        r#"
+        (module
+          (import "kartoffels" "bot_at" (func $bot_at (param i32 i32) (result i64)))
+          (import "kartoffels" "create_object" (func $create_object (param i32 i32 i32)))
+          (import "kartoffels" "kill_bot" (func $kill_bot (param i64)))
+          (import "kartoffels" "set_score" (func $set_score (param i64 i32)))
+
+          (func (export "init")
+            (call $create_object (i32.const 42) (i32.const 14) (i32.const 14)))
+
+          (func (export "on_bot_spawned") (param $bot i64)
+            (call $set_score (local.get $bot) (i32.const 7)))
+
+          (func (export "tick")
+            (call $kill_bot (call $bot_at (i32.const 12) (i32.const 12)))))
+        "#,
+    )
+    .unwrap();
+
+    let world = kartoffels_world::create(Config {
+        mode: Mode::new(WasmMode::new(code).unwrap()),
+        policy: Policy {
+            auto_respawn: false,
+            ..config().policy
+        },
+        ..config()
+    });
+
+    let bot1 = world
+        .create_bot(CreateBotRequest::new(DUMMY).at(ivec2(10, 10)))
+        .await
+        .unwrap();
+
+    let bot2 = world
+        .create_bot(CreateBotRequest::new(DUMMY).at(ivec2(12, 12)))
+        .await
+        .unwrap();
+
+    world.tick().await.unwrap();
+    world.tick().await.unwrap();
+
+    let snapshot = world.snapshot().await;
+
+    assert_eq!(7, snapshot.bots().alive().get(bot1).unwrap().score);
+    assert!(snapshot.bots().alive().get(bot2).is_none());
+
+    let objects: Vec<_> = snapshot
+        .objects()
+        .iter()
+        .map(|obj| (obj.obj.kind, obj.pos))
+        .collect();
+
+    assert_eq!(vec![(42, Some(ivec2(14, 14)))], objects);
+}
+
+#[tokio::test]
+async fn err_wasm() {
+    let err = WasmMode::new(vec![0x00]).unwrap_err();
+
+    assert!(format!("{err:?}").starts_with("couldn't parse module"));
+}
+
+#[tokio::test]
+async fn many_bots() {
+    let world = kartoffels_world::create(Config {
+        policy: Policy {
+            auto_respawn: true,
+            combat: Combat::InstantKill,
+            max_alive_bots: 300,
+            max_queued_bots: 300,
+        },
+        theme: Some(Theme::Arena(ArenaTheme::new(16))),
+        ..config()
+    });
+
+    world.tick().await.unwrap();
+
+    let center = world.snapshot().await.raw_map().center();
+
+    let reqs = (-10..=10)
+        .flat_map(|dy| (-10..=10).map(move |dx| ivec2(dx, dy)))
+        .take(300)
+        .map(|pos| CreateBotRequest::new(DUMMY).at(center + pos).instant());
+
+    world.create_bots(reqs).await.unwrap();
+    world.tick().await.unwrap();
+
+    let snapshot = world.snapshot().await;
+    let bots = snapshot.bots().alive();
+
+    assert_eq!(300, bots.len());
+
+    for bot in bots.iter() {
+        let idx = snapshot.map().get(bot.pos).bot_idx().unwrap();
+
+        assert_eq!(Some(bot), bots.get_by_idx(idx));
+    }
+}
+
 #[tokio::test]
 async fn err_too_many_robots_queued() {
     let world = kartoffels_world::create(Config {
         policy: Policy {
             auto_respawn: true,
+            combat: Combat::InstantKill,
             max_alive_bots: 10,
             max_queued_bots: 20,
         },
@@ -375,6 +1225,17 @@ async fn err_too_many_robots_queued() {
     assert_eq!("too many robots queued, try again in a moment", err);
 }
 
+#[tokio::test]
+async fn err_invalid_team() {
+    let err = kartoffels_world::create(config())
+        .create_bot(CreateBotRequest::new(DUMMY).team(255))
+        .await
+        .unwrap_err()
+        .to_string();
+
+    assert_eq!("team must be less than 255", err);
+}
+
 #[tokio::test]
 async fn err_couldnt_parse_firmware() {
     let err = kartoffels_world::create(config())
@@ -395,13 +1256,15 @@ async fn err_couldnt_parse_firmware() {
 
 fn config() -> Config {
     Config {
+        checkpoints: false,
         clock: Clock::Manual,
         events: false,
-        mode: Mode::Deathmatch(DeathmatchMode::default()),
+        mode: Mode::new(DeathmatchMode::default()),
         name: "world".into(),
         path: None,
         policy: Policy {
             auto_respawn: true,
+            combat: Combat::InstantKill,
             max_alive_bots: 16,
             max_queued_bots: 16,
         },
@@ -1,6 +1,6 @@
 use crate::TestContext;
 use kartoffels_bots::DUMMY;
-use kartoffels_world::prelude::{Config, Policy};
+use kartoffels_world::prelude::{Config, CreateBotRequest, Policy};
 use std::time::Duration;
 use termwiz::input::KeyCode;
 use tokio::time;
@@ -180,3 +180,41 @@ async fn http_upload_err() {
     ctxt.wait_while_modal("ouch").await;
     ctxt.see_frame("game/http-upload-err/2.txt").await;
 }
+
+#[tokio::test]
+async fn teams() {
+    let mut ctxt = {
+        let world = kartoffels_world::create(Config {
+            name: "world".into(),
+            policy: Policy {
+                max_alive_bots: 3,
+                max_queued_bots: 3,
+                ..Default::default()
+            },
+            seed: Some(Default::default()),
+            ..Default::default()
+        });
+
+        for team in [0, 0, 5] {
+            world
+                .create_bot(CreateBotRequest::new(DUMMY).team(team))
+                .await
+                .unwrap();
+        }
+
+        TestContext::new([world]).await
+    };
+
+    ctxt.wait_for(TestContext::INDEX).await;
+    ctxt.press(KeyCode::Char('p')).await;
+
+    ctxt.wait_for("[1] world").await;
+    ctxt.press(KeyCode::Char('1')).await;
+
+    ctxt.wait_for("[j] join-bot").await;
+    ctxt.press(KeyCode::Char('b')).await;
+
+    ctxt.wait_for_modal("bots").await;
+    ctxt.wait_for("team 0: 0 pts (2 bots)").await;
+    ctxt.see("team 5: 0 pts (1 bots)");
+}
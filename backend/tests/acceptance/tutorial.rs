@@ -162,7 +162,7 @@ async fn flow() {
 
     ctxt.press(KeyCode::Enter).await;
     ctxt.wait_while_modal("tutorial (10/16)").await;
-    ctxt.wait_while("0543-c377-57f0-8d9a").await;
+    ctxt.wait_while("72e1-4c98-1e1a-71ef").await;
     ctxt.see_frame("tutorial/flow/step-10-c.txt").await;
 
     ctxt.upload_bot(TUT_02).await;